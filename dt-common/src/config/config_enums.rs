@@ -68,6 +68,8 @@ pub enum SinkType {
     Push,
     #[strum(serialize = "merge")]
     Merge,
+    #[strum(serialize = "snapshot_file")]
+    SnapshotFile,
 }
 
 #[derive(EnumString, IntoStaticStr, Clone, Display)]
@@ -82,6 +84,8 @@ pub enum ParallelType {
     RdbMerge,
     #[strum(serialize = "rdb_check")]
     RdbCheck,
+    #[strum(serialize = "rdb_transaction")]
+    RdbTransaction,
     #[strum(serialize = "table")]
     Table,
     #[strum(serialize = "mongo")]
@@ -116,3 +120,51 @@ pub enum MetaCenterType {
     #[strum(serialize = "dbengine")]
     DbEngine,
 }
+
+/// Struct migration phases, split like pg_dump sections so that tables can be loaded
+/// before their secondary indexes and constraints are built
+#[derive(Clone, Debug, Display, EnumString, IntoStaticStr, PartialEq, Default)]
+pub enum StructPhase {
+    #[default]
+    #[strum(serialize = "all")]
+    All,
+    // databases, schemas, sequences, tables with primary keys and comments
+    #[strum(serialize = "pre_data")]
+    PreData,
+    // secondary indexes and constraints, built after snapshot
+    #[strum(serialize = "post_data")]
+    PostData,
+}
+
+#[derive(Clone, Debug, Display, EnumString, IntoStaticStr, PartialEq, Default)]
+pub enum ConflictResolveEnum {
+    #[default]
+    #[strum(serialize = "none")]
+    None,
+    #[strum(serialize = "source_wins")]
+    SourceWins,
+    #[strum(serialize = "target_wins")]
+    TargetWins,
+    #[strum(serialize = "timestamp")]
+    Timestamp,
+    #[strum(serialize = "lua")]
+    Lua,
+}
+
+#[derive(Clone, Debug, Display, EnumString, IntoStaticStr, PartialEq, Default)]
+pub enum DataMarkerStrategy {
+    #[default]
+    #[strum(serialize = "marker_table")]
+    MarkerTable,
+    #[strum(serialize = "replication_origin")]
+    ReplicationOrigin,
+}
+
+#[derive(Clone, Debug, Display, EnumString, IntoStaticStr, PartialEq, Default)]
+pub enum MessageFormat {
+    #[default]
+    #[strum(serialize = "avro")]
+    Avro,
+    #[strum(serialize = "json")]
+    Json,
+}
@@ -0,0 +1,11 @@
+use super::config_enums::ConflictResolveEnum;
+
+#[derive(Clone, Debug, Default)]
+pub struct ConflictResolveConfig {
+    pub policy: ConflictResolveEnum,
+    // for policy timestamp, the row with the larger value of this column wins
+    pub timestamp_col: String,
+    // for policy lua
+    pub lua_code_file: String,
+    pub lua_code: String,
+}
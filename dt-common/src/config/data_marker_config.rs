@@ -1,3 +1,5 @@
+use super::config_enums::DataMarkerStrategy;
+
 #[derive(Clone, Default)]
 pub struct DataMarkerConfig {
     pub topo_name: String,
@@ -7,4 +9,5 @@ pub struct DataMarkerConfig {
     pub do_nodes: String,
     pub ignore_nodes: String,
     pub marker: String,
+    pub strategy: DataMarkerStrategy,
 }
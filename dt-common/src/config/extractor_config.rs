@@ -29,9 +29,12 @@ pub enum ExtractorConfig {
         url: String,
         binlog_filename: String,
         binlog_position: u32,
+        binlog_dir: String,
         server_id: u64,
         gtid_enabled: bool,
         gtid_set: String,
+        failover_urls: String,
+        failover_timeout_secs: u64,
         binlog_heartbeat_interval_secs: u64,
         binlog_timeout_secs: u64,
         heartbeat_interval_secs: u64,
@@ -64,6 +67,11 @@ pub enum ExtractorConfig {
         heartbeat_interval_secs: u64,
         heartbeat_tb: String,
         ddl_meta_tb: String,
+        capture_ddl_by_message: bool,
+        message_prefixes: String,
+        proto_version: u8,
+        streaming: bool,
+        stream_spill_dir: String,
         start_time_utc: String,
         end_time_utc: String,
     },
@@ -79,6 +87,8 @@ pub enum ExtractorConfig {
         app_name: String,
         db: String,
         tb: String,
+        parallel_size: usize,
+        batch_size: usize,
     },
 
     MongoCdc {
@@ -102,6 +112,7 @@ pub enum ExtractorConfig {
     RedisSnapshot {
         url: String,
         repl_port: u64,
+        is_cluster: bool,
     },
 
     RedisCdc {
@@ -113,6 +124,7 @@ pub enum ExtractorConfig {
         heartbeat_interval_secs: u64,
         heartbeat_key: String,
         now_db_id: i64,
+        is_cluster: bool,
     },
 
     RedisSnapshotAndCdc {
@@ -122,6 +134,7 @@ pub enum ExtractorConfig {
         keepalive_interval_secs: u64,
         heartbeat_interval_secs: u64,
         heartbeat_key: String,
+        is_cluster: bool,
     },
 
     RedisSnapshotFile {
@@ -131,13 +144,22 @@ pub enum ExtractorConfig {
     RedisScan {
         url: String,
         scan_count: u64,
+        // empty to migrate keys by SCAN + DUMP, otherwise to analyze keys
         statistic_type: String,
+        method: String,
+        keyspace_notify: bool,
     },
 
     RedisReshard {
         url: String,
     },
 
+    RedisCheck {
+        url: String,
+        check_log_dir: String,
+        batch_size: usize,
+    },
+
     Kafka {
         url: String,
         group: String,
@@ -10,5 +10,7 @@ pub struct FilterConfig {
     pub do_ddls: String,
     pub do_dcls: String,
     pub ignore_cmds: String,
+    pub do_keys: String,
+    pub ignore_keys: String,
     pub where_conditions: String,
 }
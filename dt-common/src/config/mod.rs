@@ -1,10 +1,12 @@
 pub mod config_enums;
 pub mod config_token_parser;
+pub mod conflict_resolve_config;
 pub mod data_marker_config;
 pub mod extractor_config;
 pub mod filter_config;
 pub mod ini_loader;
 pub mod meta_center_config;
+pub mod mongo_flatten_config;
 pub mod monitor_config;
 pub mod parallelizer_config;
 pub mod pipeline_config;
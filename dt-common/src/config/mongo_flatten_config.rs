@@ -0,0 +1,58 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone)]
+pub struct MongoFlattenConfig {
+    pub mapping_file: String,
+    pub create_tables: bool,
+    pub tables: Vec<FlattenTable>,
+}
+
+/// mapping of a collection to a table, db/coll are the names after routing,
+/// and the table shares the same name as the collection
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct FlattenTable {
+    pub db: String,
+    pub coll: String,
+    #[serde(default)]
+    pub columns: Vec<FlattenColumn>,
+    /// column to keep the remaining fields as json, empty means dropping them
+    #[serde(default)]
+    pub json_col: String,
+    /// arrays exploded into child tables
+    #[serde(default)]
+    pub arrays: Vec<FlattenArray>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct FlattenColumn {
+    /// dotted field path, e.g. address.city
+    pub path: String,
+    pub col: String,
+    /// column type of the target, inferred by db_type if empty
+    #[serde(rename = "type", default)]
+    pub col_type: String,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct FlattenArray {
+    /// dotted path of the array in the document
+    pub path: String,
+    pub tb: String,
+    #[serde(default = "default_parent_col")]
+    pub parent_col: String,
+    #[serde(default = "default_index_col")]
+    pub index_col: String,
+    /// field paths inside each element, an empty path refers to the element itself
+    #[serde(default)]
+    pub columns: Vec<FlattenColumn>,
+    #[serde(default)]
+    pub json_col: String,
+}
+
+fn default_parent_col() -> String {
+    "parent_id".into()
+}
+
+fn default_index_col() -> String {
+    "idx".into()
+}
@@ -1,5 +1,6 @@
 use super::{
-    config_enums::{ConflictPolicyEnum, DbType},
+    config_enums::{ConflictPolicyEnum, DbType, MessageFormat, StructPhase},
+    conflict_resolve_config::ConflictResolveConfig,
     s3_config::S3Config,
 };
 
@@ -12,6 +13,7 @@ pub enum SinkerConfig {
         batch_size: usize,
         replace: bool,
         disable_foreign_key_checks: bool,
+        conflict_resolve: ConflictResolveConfig,
     },
 
     Pg {
@@ -19,6 +21,7 @@ pub enum SinkerConfig {
         batch_size: usize,
         replace: bool,
         disable_foreign_key_checks: bool,
+        conflict_resolve: ConflictResolveConfig,
     },
 
     Mongo {
@@ -49,11 +52,13 @@ pub enum SinkerConfig {
     MysqlStruct {
         url: String,
         conflict_policy: ConflictPolicyEnum,
+        phase: StructPhase,
     },
 
     PgStruct {
         url: String,
         conflict_policy: ConflictPolicyEnum,
+        phase: StructPhase,
     },
 
     Kafka {
@@ -62,6 +67,8 @@ pub enum SinkerConfig {
         ack_timeout_secs: u64,
         required_acks: String,
         with_field_defs: bool,
+        // format of messages converted from redis entries
+        message_format: MessageFormat,
     },
 
     Redis {
@@ -69,6 +76,20 @@ pub enum SinkerConfig {
         batch_size: usize,
         method: String,
         is_cluster: bool,
+        templates: String,
+    },
+
+    RedisSnapshotFile {
+        file_path: String,
+        rdb_version: u32,
+    },
+
+    RedisCheck {
+        url: String,
+        batch_size: usize,
+        is_cluster: bool,
+        check_log_dir: String,
+        ttl_tolerance_secs: u64,
     },
 
     RedisStatistic {
@@ -76,6 +97,11 @@ pub enum SinkerConfig {
         data_size_threshold: usize,
         freq_threshold: i64,
         statistic_log_dir: String,
+        time_window_secs: usize,
+        report_interval_secs: u64,
+        top_n: usize,
+        key_prefix_delimiter: String,
+        key_prefix_depth: usize,
     },
 
     StarRocks {
@@ -5,18 +5,20 @@ use std::{
 
 use anyhow::{bail, Ok};
 
-use crate::error::Error;
+use crate::{error::Error, meta::mongo::mongo_constant::MongoConstants};
 
 use super::{
     config_enums::{
-        ConflictPolicyEnum, DbType, ExtractType, MetaCenterType, ParallelType, PipelineType,
-        SinkType,
+        ConflictPolicyEnum, ConflictResolveEnum, DataMarkerStrategy, DbType, ExtractType,
+        MessageFormat, MetaCenterType, ParallelType, PipelineType, SinkType, StructPhase,
     },
+    conflict_resolve_config::ConflictResolveConfig,
     data_marker_config::DataMarkerConfig,
     extractor_config::{BasicExtractorConfig, ExtractorConfig},
     filter_config::FilterConfig,
     ini_loader::IniLoader,
     meta_center_config::MetaCenterConfig,
+    mongo_flatten_config::{FlattenColumn, FlattenTable, MongoFlattenConfig},
     parallelizer_config::ParallelizerConfig,
     pipeline_config::PipelineConfig,
     processor_config::ProcessorConfig,
@@ -42,6 +44,7 @@ pub struct TaskConfig {
     pub meta_center: Option<MetaCenterConfig>,
     pub data_marker: Option<DataMarkerConfig>,
     pub processor: Option<ProcessorConfig>,
+    pub mongo_flatten: Option<MongoFlattenConfig>,
 }
 
 // sections
@@ -56,6 +59,7 @@ const RESUMER: &str = "resumer";
 const DATA_MARKER: &str = "data_marker";
 const PROCESSOR: &str = "processor";
 const META_CENTER: &str = "metacenter";
+const MONGO_FLATTEN: &str = "mongo_flatten";
 // keys
 const CHECK_LOG_DIR: &str = "check_log_dir";
 const DB_TYPE: &str = "db_type";
@@ -85,6 +89,7 @@ impl TaskConfig {
         let resumer = Self::load_resumer_config(&loader, &runtime)?;
         let (extractor_basic, extractor) = Self::load_extractor_config(&loader, &pipeline)?;
         let (sinker_basic, sinker) = Self::load_sinker_config(&loader)?;
+        Self::check_redis_scan_method(&extractor, &sinker)?;
         Ok(Self {
             extractor_basic,
             extractor,
@@ -96,9 +101,18 @@ impl TaskConfig {
             filter: Self::load_filter_config(&loader)?,
             router: Self::load_router_config(&loader)?,
             resumer,
-            data_marker: Self::load_data_marker_config(&loader)?,
+            data_marker: Self::load_data_marker_config(
+                &loader,
+                &extractor_basic.db_type,
+                &sinker_basic.db_type,
+            )?,
             processor: Self::load_processor_config(&loader)?,
             meta_center: Self::load_meta_center_config(&loader)?,
+            mongo_flatten: Self::load_mongo_flatten_config(
+                &loader,
+                &extractor_basic.extract_type,
+                &sinker_basic.db_type,
+            )?,
         })
     }
 
@@ -140,9 +154,16 @@ impl TaskConfig {
                     url,
                     binlog_filename: loader.get_optional(EXTRACTOR, "binlog_filename"),
                     binlog_position: loader.get_optional(EXTRACTOR, "binlog_position"),
+                    binlog_dir: loader.get_optional(EXTRACTOR, "binlog_dir"),
                     server_id: loader.get_required(EXTRACTOR, "server_id"),
                     gtid_enabled: loader.get_optional(EXTRACTOR, "gtid_enabled"),
                     gtid_set: loader.get_optional(EXTRACTOR, "gtid_set"),
+                    failover_urls: loader.get_optional(EXTRACTOR, "failover_urls"),
+                    failover_timeout_secs: loader.get_with_default(
+                        EXTRACTOR,
+                        "failover_timeout_secs",
+                        60,
+                    ),
                     binlog_heartbeat_interval_secs: loader.get_with_default(
                         EXTRACTOR,
                         "binlog_heartbeat_interval_secs",
@@ -212,6 +233,16 @@ impl TaskConfig {
                     heartbeat_interval_secs,
                     heartbeat_tb,
                     ddl_meta_tb: loader.get_optional(EXTRACTOR, "ddl_meta_tb"),
+                    capture_ddl_by_message: loader
+                        .get_optional(EXTRACTOR, "capture_ddl_by_message"),
+                    message_prefixes: loader.get_optional(EXTRACTOR, "message_prefixes"),
+                    proto_version: loader.get_with_default(EXTRACTOR, "proto_version", 1),
+                    streaming: loader.get_optional(EXTRACTOR, "streaming"),
+                    stream_spill_dir: loader.get_with_default(
+                        EXTRACTOR,
+                        "stream_spill_dir",
+                        "./stream_spill".to_string(),
+                    ),
                     start_time_utc: loader.get_optional(EXTRACTOR, "start_time_utc"),
                     end_time_utc: loader.get_optional(EXTRACTOR, "end_time_utc"),
                 },
@@ -240,6 +271,8 @@ impl TaskConfig {
                         app_name,
                         db: String::new(),
                         tb: String::new(),
+                        parallel_size: loader.get_with_default(EXTRACTOR, PARALLEL_SIZE, 1),
+                        batch_size,
                     },
 
                     ExtractType::Cdc => ExtractorConfig::MongoCdc {
@@ -266,7 +299,11 @@ impl TaskConfig {
             DbType::Redis => match extract_type {
                 ExtractType::Snapshot => {
                     let repl_port = loader.get_with_default(EXTRACTOR, REPL_PORT, 10008);
-                    ExtractorConfig::RedisSnapshot { url, repl_port }
+                    ExtractorConfig::RedisSnapshot {
+                        url,
+                        repl_port,
+                        is_cluster: loader.get_optional(EXTRACTOR, "is_cluster"),
+                    }
                 }
 
                 ExtractType::SnapshotFile => ExtractorConfig::RedisSnapshotFile {
@@ -275,8 +312,10 @@ impl TaskConfig {
 
                 ExtractType::Scan => ExtractorConfig::RedisScan {
                     url,
-                    statistic_type: loader.get_required(EXTRACTOR, "statistic_type"),
+                    statistic_type: loader.get_optional(EXTRACTOR, "statistic_type"),
                     scan_count: loader.get_with_default(EXTRACTOR, "scan_count", 1000),
+                    method: loader.get_optional(EXTRACTOR, "method"),
+                    keyspace_notify: loader.get_optional(EXTRACTOR, "keyspace_notify"),
                 },
 
                 ExtractType::Cdc => {
@@ -290,6 +329,7 @@ impl TaskConfig {
                         heartbeat_interval_secs,
                         heartbeat_key: loader.get_optional(EXTRACTOR, "heartbeat_key"),
                         now_db_id: loader.get_optional(EXTRACTOR, "now_db_id"),
+                        is_cluster: loader.get_optional(EXTRACTOR, "is_cluster"),
                     }
                 }
 
@@ -302,11 +342,18 @@ impl TaskConfig {
                         keepalive_interval_secs,
                         heartbeat_interval_secs,
                         heartbeat_key: loader.get_optional(EXTRACTOR, "heartbeat_key"),
+                        is_cluster: loader.get_optional(EXTRACTOR, "is_cluster"),
                     }
                 }
 
                 ExtractType::Reshard => ExtractorConfig::RedisReshard { url },
 
+                ExtractType::CheckLog => ExtractorConfig::RedisCheck {
+                    url,
+                    check_log_dir: loader.get_required(EXTRACTOR, CHECK_LOG_DIR),
+                    batch_size: loader.get_with_default(EXTRACTOR, BATCH_SIZE, 200),
+                },
+
                 _ => bail! { not_supported_err },
             },
 
@@ -362,6 +409,7 @@ impl TaskConfig {
                         DISABLE_FOREIGN_KEY_CHECKS,
                         true,
                     ),
+                    conflict_resolve: Self::load_conflict_resolve_config(loader),
                 },
 
                 SinkType::Check => SinkerConfig::MysqlCheck {
@@ -373,6 +421,7 @@ impl TaskConfig {
                 SinkType::Struct => SinkerConfig::MysqlStruct {
                     url,
                     conflict_policy,
+                    phase: loader.get_with_default(SINKER, "phase", StructPhase::All),
                 },
 
                 SinkType::Sql => SinkerConfig::Sql {
@@ -392,6 +441,7 @@ impl TaskConfig {
                         DISABLE_FOREIGN_KEY_CHECKS,
                         true,
                     ),
+                    conflict_resolve: Self::load_conflict_resolve_config(loader),
                 },
 
                 SinkType::Check => SinkerConfig::PgCheck {
@@ -403,6 +453,7 @@ impl TaskConfig {
                 SinkType::Struct => SinkerConfig::PgStruct {
                     url,
                     conflict_policy,
+                    phase: loader.get_with_default(SINKER, "phase", StructPhase::All),
                 },
 
                 SinkType::Sql => SinkerConfig::Sql {
@@ -439,6 +490,11 @@ impl TaskConfig {
                 ack_timeout_secs: loader.get_with_default(SINKER, "ack_timeout_secs", 5),
                 required_acks: loader.get_with_default(SINKER, "required_acks", "one".to_string()),
                 with_field_defs: loader.get_with_default(SINKER, "with_field_defs", true),
+                message_format: loader.get_with_default(
+                    SINKER,
+                    "message_format",
+                    MessageFormat::Avro,
+                ),
             },
 
             DbType::Redis => match sink_type {
@@ -447,6 +503,20 @@ impl TaskConfig {
                     batch_size,
                     method: loader.get_optional(SINKER, "method"),
                     is_cluster: loader.get_optional(SINKER, "is_cluster"),
+                    templates: loader.get_optional(SINKER, "templates"),
+                },
+
+                SinkType::SnapshotFile => SinkerConfig::RedisSnapshotFile {
+                    file_path: loader.get_required(SINKER, "file_path"),
+                    rdb_version: loader.get_with_default(SINKER, "rdb_version", 12),
+                },
+
+                SinkType::Check => SinkerConfig::RedisCheck {
+                    url,
+                    batch_size,
+                    is_cluster: loader.get_optional(SINKER, "is_cluster"),
+                    check_log_dir: loader.get_optional(SINKER, CHECK_LOG_DIR),
+                    ttl_tolerance_secs: loader.get_with_default(SINKER, "ttl_tolerance_secs", 10),
                 },
 
                 SinkType::Statistic => SinkerConfig::RedisStatistic {
@@ -454,6 +524,19 @@ impl TaskConfig {
                     data_size_threshold: loader.get_optional(SINKER, "data_size_threshold"),
                     freq_threshold: loader.get_optional(SINKER, "freq_threshold"),
                     statistic_log_dir: loader.get_optional(SINKER, "statistic_log_dir"),
+                    time_window_secs: loader.get_with_default(SINKER, "time_window_secs", 60),
+                    report_interval_secs: loader.get_with_default(
+                        SINKER,
+                        "report_interval_secs",
+                        60,
+                    ),
+                    top_n: loader.get_with_default(SINKER, "top_n", 10),
+                    key_prefix_delimiter: loader.get_with_default(
+                        SINKER,
+                        "key_prefix_delimiter",
+                        ":".to_string(),
+                    ),
+                    key_prefix_depth: loader.get_with_default(SINKER, "key_prefix_depth", 1),
                 },
 
                 _ => bail! { not_supported_err },
@@ -552,6 +635,31 @@ impl TaskConfig {
         Ok((basic, sinker))
     }
 
+    // keys read by commands have no DUMP payloads, so they can only be written by rewrite
+    fn check_redis_scan_method(
+        extractor: &ExtractorConfig,
+        sinker: &SinkerConfig,
+    ) -> anyhow::Result<()> {
+        if let (
+            ExtractorConfig::RedisScan {
+                method: extractor_method,
+                ..
+            },
+            SinkerConfig::Redis {
+                method: sinker_method,
+                ..
+            },
+        ) = (extractor, sinker)
+        {
+            if extractor_method == "rewrite" && sinker_method != "rewrite" {
+                bail! {Error::ConfigError(
+                    "[extractor] method=rewrite requires [sinker] method=rewrite".into()
+                )}
+            }
+        }
+        Ok(())
+    }
+
     fn load_parallelizer_config(loader: &IniLoader) -> anyhow::Result<ParallelizerConfig> {
         Ok(ParallelizerConfig {
             parallel_size: loader.get_with_default(PARALLELIZER, PARALLEL_SIZE, 1),
@@ -613,6 +721,8 @@ impl TaskConfig {
             do_dcls: loader.get_optional(FILTER, "do_dcls"),
             do_structures: loader.get_with_default(FILTER, "do_structures", ASTRISK.to_string()),
             ignore_cmds: loader.get_optional(FILTER, "ignore_cmds"),
+            do_keys: loader.get_optional(FILTER, "do_keys"),
+            ignore_keys: loader.get_optional(FILTER, "ignore_keys"),
             where_conditions: loader.get_optional(FILTER, "where_conditions"),
         })
     }
@@ -623,6 +733,7 @@ impl TaskConfig {
             tb_map: loader.get_optional(ROUTER, "tb_map"),
             col_map: loader.get_optional(ROUTER, "col_map"),
             topic_map: loader.get_optional(ROUTER, "topic_map"),
+            key_map: loader.get_optional(ROUTER, "key_map"),
         })
     }
 
@@ -639,11 +750,40 @@ impl TaskConfig {
         })
     }
 
-    fn load_data_marker_config(loader: &IniLoader) -> anyhow::Result<Option<DataMarkerConfig>> {
+    fn load_data_marker_config(
+        loader: &IniLoader,
+        extractor_db_type: &DbType,
+        sinker_db_type: &DbType,
+    ) -> anyhow::Result<Option<DataMarkerConfig>> {
         if !loader.ini.sections().contains(&DATA_MARKER.to_string()) {
             return Ok(None);
         }
 
+        let strategy =
+            loader.get_with_default(DATA_MARKER, "strategy", DataMarkerStrategy::MarkerTable);
+        // with replication origins, no marker table is written
+        let marker = match strategy {
+            DataMarkerStrategy::MarkerTable => loader.get_required(DATA_MARKER, "marker"),
+            DataMarkerStrategy::ReplicationOrigin => loader.get_optional(DATA_MARKER, "marker"),
+        };
+
+        // the marker table is still needed on non-pg sides with replication origins
+        for db_type in [extractor_db_type, sinker_db_type] {
+            let uses_marker_table = match db_type {
+                DbType::Pg => strategy == DataMarkerStrategy::MarkerTable,
+                DbType::Mysql | DbType::Mongo => true,
+                _ => false,
+            };
+            let is_valid = matches!(marker.split_once('.'),
+                Some((schema, tb)) if !schema.is_empty() && !tb.is_empty());
+            if uses_marker_table && !is_valid {
+                bail! {Error::ConfigError(format!(
+                    "[data_marker] marker should be `schema.tb` for {}, but got: [{}]",
+                    db_type, marker
+                ))}
+            }
+        }
+
         Ok(Some(DataMarkerConfig {
             topo_name: loader.get_required(DATA_MARKER, "topo_name"),
             topo_nodes: loader.get_optional(DATA_MARKER, "topo_nodes"),
@@ -651,10 +791,32 @@ impl TaskConfig {
             dst_node: loader.get_required(DATA_MARKER, "dst_node"),
             do_nodes: loader.get_required(DATA_MARKER, "do_nodes"),
             ignore_nodes: loader.get_optional(DATA_MARKER, "ignore_nodes"),
-            marker: loader.get_required(DATA_MARKER, "marker"),
+            marker,
+            strategy,
         }))
     }
 
+    fn load_conflict_resolve_config(loader: &IniLoader) -> ConflictResolveConfig {
+        let policy = loader.get_with_default(SINKER, "conflict_resolve", ConflictResolveEnum::None);
+        let timestamp_col = loader.get_optional(SINKER, "conflict_timestamp_col");
+        let lua_code_file = loader.get_optional(SINKER, "conflict_lua_code_file");
+        let mut lua_code = String::new();
+
+        if fs::metadata(&lua_code_file).is_ok() {
+            let mut file =
+                File::open(&lua_code_file).expect("failed to open conflict lua code file");
+            file.read_to_string(&mut lua_code)
+                .expect("failed to read conflict lua code file");
+        }
+
+        ConflictResolveConfig {
+            policy,
+            timestamp_col,
+            lua_code_file,
+            lua_code,
+        }
+    }
+
     fn load_processor_config(loader: &IniLoader) -> anyhow::Result<Option<ProcessorConfig>> {
         if !loader.ini.sections().contains(&PROCESSOR.to_string()) {
             return Ok(None);
@@ -675,6 +837,63 @@ impl TaskConfig {
         }))
     }
 
+    fn load_mongo_flatten_config(
+        loader: &IniLoader,
+        extract_type: &ExtractType,
+        sinker_db_type: &DbType,
+    ) -> anyhow::Result<Option<MongoFlattenConfig>> {
+        if !loader.ini.sections().contains(&MONGO_FLATTEN.to_string()) {
+            return Ok(None);
+        }
+
+        let mapping_file: String = loader.get_required(MONGO_FLATTEN, "mapping_file");
+        let create_tables = loader.get_with_default(MONGO_FLATTEN, "create_tables", false);
+        let content = fs::read_to_string(&mapping_file).map_err(|err| {
+            Error::ConfigError(format!(
+                "failed to read mongo flatten mapping file: {}, error: {}",
+                mapping_file, err
+            ))
+        })?;
+        let mut tables: Vec<FlattenTable> = serde_json::from_str(&content).map_err(|err| {
+            Error::ConfigError(format!(
+                "invalid mongo flatten mapping file: {}, error: {}",
+                mapping_file, err
+            ))
+        })?;
+
+        for table in tables.iter_mut() {
+            // _id is always the primary key of the flattened table
+            if !table.columns.iter().any(|i| i.path == MongoConstants::ID) {
+                table.columns.insert(
+                    0,
+                    FlattenColumn {
+                        path: MongoConstants::ID.into(),
+                        col: MongoConstants::ID.into(),
+                        col_type: String::new(),
+                    },
+                );
+            }
+        }
+
+        // children of updated / deleted documents are deleted by parent_col,
+        // which is only supported by mysql / pg targets
+        if matches!(extract_type, ExtractType::Cdc)
+            && !matches!(sinker_db_type, DbType::Mysql | DbType::Pg)
+            && tables.iter().any(|i| !i.arrays.is_empty())
+        {
+            bail! {Error::ConfigError(format!(
+                "[mongo_flatten] arrays can not be flattened into child tables by cdc tasks for sinker: {}",
+                sinker_db_type
+            ))}
+        }
+
+        Ok(Some(MongoFlattenConfig {
+            mapping_file,
+            create_tables,
+            tables,
+        }))
+    }
+
     fn load_meta_center_config(loader: &IniLoader) -> anyhow::Result<Option<MetaCenterConfig>> {
         let mut config = MetaCenterConfig::Basic;
         let db_type: DbType = loader.get_required(EXTRACTOR, DB_TYPE);
@@ -38,6 +38,11 @@ macro_rules! log_sql {
     ($($arg:tt)+) => (log::log!(target: "sql_logger", log::Level::Info, $($arg)+));
 }
 
+#[macro_export(local_inner_macros)]
+macro_rules! log_conflict {
+    ($($arg:tt)+) => (log::log!(target: "conflict_logger", log::Level::Info, $($arg)+));
+}
+
 #[macro_export(local_inner_macros)]
 macro_rules! log_error {
     ($($arg:tt)+) => (log::log!(target: "default_logger", log::Level::Error, $($arg)+))
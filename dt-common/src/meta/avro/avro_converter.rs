@@ -11,6 +11,7 @@ use crate::{
         dt_data::DtData,
         rdb_meta_manager::RdbMetaManager,
         rdb_tb_meta::RdbTbMeta,
+        redis::redis_event::RedisEvent,
         row_data::RowData,
         row_type::RowType,
     },
@@ -36,6 +37,17 @@ const QUERY: &str = "query";
 const SCHEMA: &str = "schema";
 const TB: &str = "tb";
 const FIELDS: &str = "fields";
+const REDIS: &str = "redis";
+const IS_BASE: &str = "is_base";
+const COMMAND: &str = "command";
+const KEYS: &str = "keys";
+const ARGS: &str = "args";
+const VALUE_TYPE: &str = "value_type";
+const VALUE_CMDS: &str = "value_cmds";
+const IS_BASE64: &str = "is_base64";
+const EXPIRE_MS: &str = "expire_ms";
+const TIMESTAMP: &str = "timestamp";
+const POSITION: &str = "position";
 
 impl AvroConverter {
     pub fn new(meta_manager: Option<RdbMetaManager>, with_field_defs: bool) -> Self {
@@ -181,6 +193,47 @@ impl AvroConverter {
         Ok(to_avro_datum(&self.schema, value)?)
     }
 
+    /// The redis event is put into extra, with list fields (keys, args, value_cmds) in json
+    pub fn redis_event_to_avro_value(&self, event: RedisEvent) -> anyhow::Result<Vec<u8>> {
+        let mut col_values: HashMap<String, ColValue> = HashMap::new();
+        col_values.insert(IS_BASE.into(), ColValue::Bool(event.is_base));
+        col_values.insert(COMMAND.into(), ColValue::String(event.command));
+        col_values.insert(
+            KEYS.into(),
+            ColValue::String(serde_json::to_string(&event.keys)?),
+        );
+        col_values.insert(
+            ARGS.into(),
+            ColValue::String(serde_json::to_string(&event.args)?),
+        );
+        col_values.insert(VALUE_TYPE.into(), ColValue::String(event.value_type));
+        col_values.insert(
+            VALUE_CMDS.into(),
+            ColValue::String(serde_json::to_string(&event.value_cmds)?),
+        );
+        col_values.insert(IS_BASE64.into(), ColValue::Bool(event.is_base64));
+        col_values.insert(EXPIRE_MS.into(), ColValue::LongLong(event.expire_ms));
+        col_values.insert(TIMESTAMP.into(), ColValue::String(event.timestamp));
+        col_values.insert(
+            POSITION.into(),
+            ColValue::String(event.position.to_string()),
+        );
+
+        let (avro_values, _) = Self::col_values_to_avro(&Some(col_values));
+        let extra = Value::Union(1, Box::new(avro_values));
+
+        let value = Value::Record(vec![
+            (SCHEMA.into(), Value::String(event.db.to_string())),
+            (TB.into(), Value::String(String::new())),
+            (OPERATION.into(), Value::String(REDIS.into())),
+            (FIELDS.into(), Value::Union(0, Box::new(Value::Null))),
+            (BEFORE.into(), Value::Union(0, Box::new(Value::Null))),
+            (AFTER.into(), Value::Union(0, Box::new(Value::Null))),
+            (EXTRA.into(), extra),
+        ]);
+        Ok(to_avro_datum(&self.schema, value)?)
+    }
+
     pub fn avro_value_to_dt_data(&self, payload: Vec<u8>) -> anyhow::Result<DtData> {
         let mut reader = payload.as_slice();
         let value = from_avro_datum(&self.schema, &mut reader, None)?;
@@ -435,6 +488,48 @@ mod tests {
         validate_ddl_data(&mut avro_converter, &ddl_data).await;
     }
 
+    #[test]
+    fn test_redis_event_to_avro() {
+        let avro_converter = AvroConverter::new(None, false);
+        let event = RedisEvent {
+            db: 1,
+            command: "SET".into(),
+            keys: vec!["k1".into()],
+            args: vec!["set".into(), "k1".into(), "v1".into()],
+            timestamp: "2024-01-01 00:00:00.000".into(),
+            ..Default::default()
+        };
+        let payload = avro_converter
+            .redis_event_to_avro_value(event.clone())
+            .unwrap();
+
+        let mut reader = payload.as_slice();
+        let value = from_avro_datum(&avro_converter.schema, &mut reader, None).unwrap();
+        let mut avro_map = AvroConverter::avro_to_map(value);
+        assert_eq!(avro_map.remove(SCHEMA), Some(Value::String("1".into())));
+        assert_eq!(
+            avro_map.remove(OPERATION),
+            Some(Value::String(REDIS.into()))
+        );
+
+        let extra = avro_converter
+            .avro_to_col_values(avro_map.remove(EXTRA))
+            .unwrap();
+        assert_eq!(extra.get(IS_BASE), Some(&ColValue::Bool(false)));
+        assert_eq!(extra.get(COMMAND), Some(&ColValue::String("SET".into())));
+        assert_eq!(extra.get(KEYS), Some(&ColValue::String(r#"["k1"]"#.into())));
+        assert_eq!(
+            extra.get(ARGS),
+            Some(&ColValue::String(r#"["set","k1","v1"]"#.into()))
+        );
+        assert_eq!(extra.get(IS_BASE64), Some(&ColValue::Bool(false)));
+        assert_eq!(extra.get(EXPIRE_MS), Some(&ColValue::LongLong(0)));
+        assert_eq!(
+            extra.get(POSITION),
+            Some(&ColValue::String(event.position.to_string()))
+        );
+    }
+
     async fn validate_row_data(avro_converter: &mut AvroConverter, row_data: &RowData) {
         let payload = avro_converter
             .row_data_to_avro_value(row_data.clone())
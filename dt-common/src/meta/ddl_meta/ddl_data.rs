@@ -25,6 +25,17 @@ impl DdlData {
         self.statement.to_sql(&self.db_type)
     }
 
+    /// Truncates are compatible among dbs, render them in the dialect of the target,
+    /// multi-table truncates are split since most dbs truncate one table at a time.
+    pub fn to_truncate_sqls(&self, db_type: &DbType) -> Vec<String> {
+        self.statement
+            .clone()
+            .split_to_multi()
+            .iter()
+            .map(|i| i.to_sql(db_type))
+            .collect()
+    }
+
     pub fn get_schema_tb(&self) -> (String, String) {
         let (mut schema, tb) = self.statement.get_schema_tb();
         if schema.is_empty() {
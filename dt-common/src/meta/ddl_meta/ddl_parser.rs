@@ -516,6 +516,7 @@ impl DdlParser {
             if_exists: if_exists.is_some(),
             is_only: only.is_some(),
             unparsed: to_string(remaining_input),
+            ..Default::default()
         };
         let ddl = DdlData {
             ddl_type: DdlType::AlterTable,
@@ -576,6 +577,7 @@ impl DdlParser {
             tb,
             is_only: only.is_some(),
             unparsed: to_string(remaining_input),
+            ..Default::default()
         };
 
         let ddl = DdlData {
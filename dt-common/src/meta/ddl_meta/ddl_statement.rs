@@ -24,6 +24,7 @@ pub enum DdlStatement {
     PgAlterTableRename(PgAlterTableRenameStatement),
     PgAlterTableSetSchema(PgAlterTableSetSchemaStatement),
     PgTruncateTable(PgTruncateTableStatement),
+    PgTruncateMultiTable(PgTruncateMultiTableStatement),
     PgCreateIndex(PgCreateIndexStatement),
 
     DropMultiTable(DropMultiTableStatement),
@@ -34,6 +35,8 @@ pub enum DdlStatement {
     RenameTable(RenameTableStatement),
     PgDropIndex(PgDropIndexStatement),
 
+    MongoCommand(MongoCommandStatement),
+
     #[default]
     Unknown,
 }
@@ -68,6 +71,19 @@ impl DdlStatement {
                 }
             }
 
+            DdlStatement::PgTruncateMultiTable(s) => {
+                for (schema, tb) in s.schema_tbs.iter() {
+                    let statement = PgTruncateTableStatement {
+                        schema: schema.clone(),
+                        tb: tb.clone(),
+                        cascade: s.cascade,
+                        restart_identity: s.restart_identity,
+                        ..Default::default()
+                    };
+                    res.push(DdlStatement::PgTruncateTable(statement));
+                }
+            }
+
             DdlStatement::PgDropMultiIndex(s) => {
                 for index_name in s.index_names.iter() {
                     let statement = PgDropIndexStatement {
@@ -113,8 +129,11 @@ impl DdlStatement {
             DdlStatement::PgAlterTableRename(s) => (s.schema.clone(), s.tb.clone()),
             DdlStatement::PgAlterTableSetSchema(s) => (s.schema.clone(), s.tb.clone()),
 
+            DdlStatement::MongoCommand(s) => (s.db.clone(), s.coll.clone()),
+
             DdlStatement::PgDropIndex(_)
             | DdlStatement::PgDropMultiIndex(_)
+            | DdlStatement::PgTruncateMultiTable(_)
             | DdlStatement::DropMultiTable(_)
             | DdlStatement::RenameMultiTable(_)
             | DdlStatement::Unknown => (String::new(), String::new()),
@@ -126,6 +145,7 @@ impl DdlStatement {
             DdlStatement::RenameTable(s) => (s.new_schema.clone(), s.new_tb.clone()),
             DdlStatement::MysqlAlterTableRename(s) => (s.new_db.clone(), s.new_tb.clone()),
             DdlStatement::PgAlterTableRename(s) => (s.new_schema.clone(), s.new_tb.clone()),
+            DdlStatement::MongoCommand(s) => (s.new_db.clone(), s.new_coll.clone()),
             _ => (String::new(), String::new()),
         }
     }
@@ -171,6 +191,13 @@ impl DdlStatement {
                 s.new_tb = dst_new_tb;
             }
 
+            DdlStatement::MongoCommand(s) => {
+                s.db = dst_schema;
+                s.coll = dst_tb;
+                s.new_db = dst_new_schema;
+                s.new_coll = dst_new_tb;
+            }
+
             _ => {}
         }
     }
@@ -260,6 +287,13 @@ impl DdlStatement {
                 s.tb = dst_tb;
             }
 
+            DdlStatement::MongoCommand(s) => {
+                s.db = dst_schema;
+                if !s.coll.is_empty() {
+                    s.coll = dst_tb;
+                }
+            }
+
             // not supported
             DdlStatement::RenameTable(_)
             | DdlStatement::MysqlAlterTableRename(_)
@@ -274,6 +308,20 @@ impl DdlStatement {
     }
 }
 
+/// A MongoDB command captured from oplog / change stream, such as create, drop,
+/// renameCollection, createIndexes, dropIndexes and dropDatabase.
+/// db / coll (and new_db / new_coll for renameCollection) are kept apart from the command
+/// so they can be routed, the sinker rebuilds the command with them.
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq)]
+pub struct MongoCommandStatement {
+    pub db: String,
+    pub coll: String,
+    pub new_db: String,
+    pub new_coll: String,
+    // command document in relaxed extended json
+    pub command: String,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq)]
 pub struct CreateDatabaseStatement {
     pub db: String,
@@ -406,9 +454,21 @@ pub struct PgTruncateTableStatement {
     pub schema: String,
     pub tb: String,
     pub is_only: bool,
+    // set by truncates from logical replication, parsed ddls keep them in unparsed
+    #[serde(default)]
+    pub cascade: bool,
+    #[serde(default)]
+    pub restart_identity: bool,
     pub unparsed: String,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq)]
+pub struct PgTruncateMultiTableStatement {
+    pub schema_tbs: Vec<(String, String)>,
+    pub cascade: bool,
+    pub restart_identity: bool,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq)]
 pub struct RenameMultiTableStatement {
     pub schema_tbs: Vec<(String, String)>,
@@ -566,9 +626,12 @@ impl DdlStatement {
                     sql = format!("{} ONLY", sql);
                 }
                 sql = append_tb(&sql, &s.schema, &s.tb, db_type);
-                append_unparsed(sql, &s.unparsed)
+                sql = append_unparsed(sql, &s.unparsed);
+                append_truncate_options(sql, s.cascade, s.restart_identity, db_type)
             }
 
+            DdlStatement::PgTruncateMultiTable(s) => s.to_sql(db_type),
+
             DdlStatement::MysqlAlterTable(s) => {
                 let mut sql = "ALTER TABLE".to_string();
                 sql = append_tb(&sql, &s.db, &s.tb, db_type);
@@ -692,6 +755,8 @@ impl DdlStatement {
                 multi_s.to_sql(db_type)
             }
 
+            DdlStatement::MongoCommand(s) => s.command.clone(),
+
             _ => String::new(),
         }
     }
@@ -727,6 +792,19 @@ impl RenameMultiTableStatement {
     }
 }
 
+impl PgTruncateMultiTableStatement {
+    pub fn to_sql(&self, db_type: &DbType) -> String {
+        let mut sql = "TRUNCATE TABLE".to_string();
+        for (i, (schema, tb)) in self.schema_tbs.iter().enumerate() {
+            sql = append_tb(&sql, schema, tb, db_type);
+            if i < self.schema_tbs.len() - 1 {
+                sql = format!("{},", sql);
+            }
+        }
+        append_truncate_options(sql, self.cascade, self.restart_identity, db_type)
+    }
+}
+
 impl PgDropMultiIndexStatement {
     pub fn to_sql(&self, db_type: &DbType) -> String {
         let mut sql = "DROP INDEX".to_string();
@@ -756,6 +834,26 @@ fn append_tb(sql: &str, schema: &str, tb: &str, db_type: &DbType) -> String {
     }
 }
 
+fn append_truncate_options(
+    mut sql: String,
+    cascade: bool,
+    restart_identity: bool,
+    db_type: &DbType,
+) -> String {
+    // other dbs do not support these options
+    if *db_type != DbType::Pg {
+        return sql;
+    }
+
+    if restart_identity {
+        sql = format!("{} RESTART IDENTITY", sql);
+    }
+    if cascade {
+        sql = format!("{} CASCADE", sql);
+    }
+    sql
+}
+
 fn append_opt_str(sql: &str, opt_str: &Option<String>) -> String {
     if let Some(s) = opt_str {
         format!("{} {}", sql, s)
@@ -2,8 +2,8 @@ use serde::{Deserialize, Serialize};
 use serde_json::json;
 
 use super::{
-    ddl_meta::ddl_data::DdlData, foxlake::s3_file_meta::S3FileMeta, row_data::RowData,
-    struct_meta::struct_data::StructData,
+    ddl_meta::ddl_data::DdlData, foxlake::s3_file_meta::S3FileMeta, message_data::MessageData,
+    row_data::RowData, struct_meta::struct_data::StructData,
 };
 use crate::meta::dcl_meta::dcl_data::DclData;
 use crate::meta::{position::Position, redis::redis_entry::RedisEntry};
@@ -13,12 +13,19 @@ pub struct DtItem {
     pub dt_data: DtData,
     pub position: Position,
     pub data_origin_node: String,
+    // comma separated nodes the data has passed through, only set in multi-node topologies
+    #[serde(default)]
+    pub data_path: String,
 }
 
 impl DtItem {
     pub fn is_ddl(&self) -> bool {
         self.dt_data.is_ddl()
     }
+
+    pub fn is_message(&self) -> bool {
+        self.dt_data.is_message()
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -47,6 +54,9 @@ pub enum DtData {
     Foxlake {
         file_meta: S3FileMeta,
     },
+    Message {
+        message_data: MessageData,
+    },
 }
 
 impl DtData {
@@ -62,6 +72,10 @@ impl DtData {
         matches!(self, DtData::Ddl { .. })
     }
 
+    pub fn is_message(&self) -> bool {
+        matches!(self, DtData::Message { .. })
+    }
+
     pub fn get_data_size(&self) -> usize {
         match &self {
             DtData::Dml { row_data } => row_data.data_size,
@@ -0,0 +1,12 @@
+use serde::{Deserialize, Serialize};
+
+/// Custom message sent through the replication stream by applications,
+/// e.g. pg_logical_emit_message(transactional, prefix, content) in postgres
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MessageData {
+    pub prefix: String,
+    pub content: String,
+    // transactional messages are decoded in the transaction that emits them,
+    // non-transactional ones are decoded immediately even if the transaction rolls back
+    pub transactional: bool,
+}
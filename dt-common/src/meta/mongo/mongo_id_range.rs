@@ -0,0 +1,246 @@
+use anyhow::bail;
+use mongodb::bson::{doc, oid::ObjectId, Bson, Document};
+
+use crate::error::Error;
+
+use super::mongo_constant::MongoConstants;
+
+// refer: https://www.mongodb.com/docs/manual/reference/bson-type-comparison-order/
+// _id can not be array, regular expression or undefined
+const TYPE_BRACKETS: [&[&str]; 11] = [
+    &["minKey"],
+    &["null"],
+    &["int", "long", "double", "decimal"],
+    &["string", "symbol"],
+    &["object"],
+    &["binData"],
+    &["objectId"],
+    &["bool"],
+    &["date"],
+    &["timestamp"],
+    &["maxKey"],
+];
+
+/// A range of _id: (gt, lte], None means unbounded.
+/// Ids of different types are compared in BSON order, the same as sorting by _id.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MongoIdRange {
+    pub gt: Option<Bson>,
+    pub lte: Option<Bson>,
+}
+
+impl MongoIdRange {
+    /// split (-inf, +inf) into ranges by sorted split ids
+    pub fn from_split_ids(split_ids: &[Bson]) -> Vec<Self> {
+        let mut ranges = Vec::new();
+        let mut gt = None;
+        for id in split_ids {
+            ranges.push(Self {
+                gt,
+                lte: Some(id.clone()),
+            });
+            gt = Some(id.clone());
+        }
+        ranges.push(Self { gt, lte: None });
+        ranges
+    }
+
+    /// comparison operators of query only match values of the same type bracket
+    /// (e.g. {$gt: ObjectId} never matches strings), so values of the brackets
+    /// between gt and lte are matched by $type
+    pub fn to_filter(&self) -> Option<Document> {
+        let id = MongoConstants::ID;
+        let gt_bracket = self.gt.as_ref().map(Self::get_type_bracket);
+        let lte_bracket = self.lte.as_ref().map(Self::get_type_bracket);
+
+        let mut conditions = Vec::new();
+        match (&self.gt, &self.lte) {
+            (None, None) => return None,
+
+            (Some(gt), Some(lte)) if gt_bracket == lte_bracket => {
+                conditions.push(doc! {id: {"$gt": gt, "$lte": lte}});
+            }
+
+            _ => {
+                if let Some(gt) = &self.gt {
+                    conditions.push(doc! {id: {"$gt": gt}});
+                }
+                let start = gt_bracket.map_or(0, |i| i + 1);
+                let end = lte_bracket.unwrap_or(TYPE_BRACKETS.len());
+                let types: Vec<&str> = TYPE_BRACKETS[start.min(end)..end]
+                    .iter()
+                    .flat_map(|i| i.iter().copied())
+                    .collect();
+                if !types.is_empty() {
+                    conditions.push(doc! {id: {"$type": types}});
+                }
+                if let Some(lte) = &self.lte {
+                    conditions.push(doc! {id: {"$lte": lte}});
+                }
+            }
+        }
+
+        if conditions.len() == 1 {
+            conditions.pop()
+        } else {
+            Some(doc! {"$or": conditions})
+        }
+    }
+
+    fn get_type_bracket(value: &Bson) -> usize {
+        match value {
+            Bson::MinKey => 0,
+            Bson::Null | Bson::Undefined => 1,
+            Bson::Int32(_) | Bson::Int64(_) | Bson::Double(_) | Bson::Decimal128(_) => 2,
+            Bson::String(_) | Bson::Symbol(_) => 3,
+            Bson::Document(_) => 4,
+            Bson::Binary(_) => 5,
+            Bson::ObjectId(_) => 6,
+            Bson::Boolean(_) => 7,
+            Bson::DateTime(_) => 8,
+            Bson::Timestamp(_) => 9,
+            Bson::MaxKey => 10,
+            // not allowed as _id, put them before maxKey
+            _ => 9,
+        }
+    }
+
+    /// _id in position, canonical extended json
+    pub fn id_to_str(id: &Bson) -> String {
+        id.clone().into_canonical_extjson().to_string()
+    }
+
+    pub fn id_from_str(value: &str) -> anyhow::Result<Bson> {
+        // positions recorded by older versions are hex strings of ObjectId
+        if let Ok(oid) = ObjectId::parse_str(value) {
+            return Ok(Bson::ObjectId(oid));
+        }
+        let json: serde_json::Value = serde_json::from_str(value)?;
+        Ok(Bson::try_from(json)?)
+    }
+
+    /// unfinished ranges in position: [[gt, lte], ...]
+    pub fn ranges_to_str(ranges: &[Self]) -> String {
+        let to_json = |id: &Option<Bson>| {
+            id.clone()
+                .map_or(serde_json::Value::Null, |v| v.into_canonical_extjson())
+        };
+        let values: Vec<serde_json::Value> = ranges
+            .iter()
+            .map(|i| serde_json::json!([to_json(&i.gt), to_json(&i.lte)]))
+            .collect();
+        serde_json::Value::Array(values).to_string()
+    }
+
+    pub fn ranges_from_str(value: &str) -> anyhow::Result<Vec<Self>> {
+        let from_json = |v: &serde_json::Value| -> anyhow::Result<Option<Bson>> {
+            if v.is_null() {
+                Ok(None)
+            } else {
+                Ok(Some(Bson::try_from(v.clone())?))
+            }
+        };
+
+        let json: serde_json::Value = serde_json::from_str(value)?;
+        let mut ranges = Vec::new();
+        if let serde_json::Value::Array(items) = json {
+            for item in items.iter() {
+                match item.as_array() {
+                    Some(pair) if pair.len() == 2 => ranges.push(Self {
+                        gt: from_json(&pair[0])?,
+                        lte: from_json(&pair[1])?,
+                    }),
+                    _ => bail! {Error::MetadataError(format!("invalid _id range: {}", item))},
+                }
+            }
+        }
+        Ok(ranges)
+    }
+
+    pub fn is_ranges_str(value: &str) -> bool {
+        value.starts_with('[')
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_filter() {
+        let oid = ObjectId::parse_str("6501a1c7f1d2a3b4c5d6e7f8").unwrap();
+        let range = MongoIdRange {
+            gt: Some(Bson::ObjectId(oid)),
+            lte: None,
+        };
+        assert_eq!(
+            range.to_filter().unwrap(),
+            doc! {"$or": [
+                {"_id": {"$gt": oid}},
+                {"_id": {"$type": ["bool", "date", "timestamp", "maxKey"]}},
+            ]}
+        );
+
+        let range = MongoIdRange {
+            gt: Some(Bson::Int32(1)),
+            lte: Some(Bson::Int64(10)),
+        };
+        assert_eq!(
+            range.to_filter().unwrap(),
+            doc! {"_id": {"$gt": 1, "$lte": 10_i64}}
+        );
+
+        let range = MongoIdRange {
+            gt: Some(Bson::Int32(1)),
+            lte: Some(Bson::ObjectId(oid)),
+        };
+        assert_eq!(
+            range.to_filter().unwrap(),
+            doc! {"$or": [
+                {"_id": {"$gt": 1}},
+                {"_id": {"$type": ["string", "symbol", "object", "binData"]}},
+                {"_id": {"$lte": oid}},
+            ]}
+        );
+
+        let range = MongoIdRange {
+            gt: None,
+            lte: None,
+        };
+        assert_eq!(range.to_filter(), None);
+    }
+
+    #[test]
+    fn test_ranges_str() {
+        let oid = ObjectId::parse_str("6501a1c7f1d2a3b4c5d6e7f8").unwrap();
+        let ranges = MongoIdRange::from_split_ids(&[
+            Bson::Int32(100),
+            Bson::String("abc".into()),
+            Bson::ObjectId(oid),
+        ]);
+        assert_eq!(ranges.len(), 4);
+
+        let value = MongoIdRange::ranges_to_str(&ranges);
+        assert!(MongoIdRange::is_ranges_str(&value));
+        assert_eq!(MongoIdRange::ranges_from_str(&value).unwrap(), ranges);
+    }
+
+    #[test]
+    fn test_id_str() {
+        let oid = ObjectId::parse_str("6501a1c7f1d2a3b4c5d6e7f8").unwrap();
+        assert_eq!(
+            MongoIdRange::id_from_str("6501a1c7f1d2a3b4c5d6e7f8").unwrap(),
+            Bson::ObjectId(oid)
+        );
+
+        for id in [
+            Bson::ObjectId(oid),
+            Bson::String("6501a1c7f1d2a3b4c5d6e7f8".into()),
+            Bson::Int64(1),
+            Bson::Double(1.5),
+        ] {
+            let value = MongoIdRange::id_to_str(&id);
+            assert_eq!(MongoIdRange::id_from_str(&value).unwrap(), id);
+        }
+    }
+}
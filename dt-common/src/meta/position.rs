@@ -51,6 +51,9 @@ pub enum Position {
         repl_offset: u64,
         now_db_id: i64,
         timestamp: String,
+        /// address of the cluster node, empty if the source is not a cluster
+        #[serde(default, skip_serializing_if = "String::is_empty")]
+        address: String,
     },
     FoxlakeS3 {
         schema: String,
@@ -46,6 +46,12 @@ impl RedisEntry {
         }
     }
 
+    /// Entries from rdb carry commands only if they are aux data instead of keys,
+    /// E.g. lua scripts, function libraries
+    pub fn is_base_key(&self) -> bool {
+        self.is_base && self.cmd.args.is_empty()
+    }
+
     pub fn is_raw(&self) -> bool {
         self.is_base && !self.raw_bytes.is_empty()
     }
@@ -64,8 +70,15 @@ impl RedisEntry {
         self.value.get_type()
     }
 
+    /// Scripts and function libraries are server-wide, neither in any db nor bound to keys.
+    /// E.g. SCRIPT LOAD, FUNCTION LOAD / DELETE / FLUSH
+    pub fn is_global_cmd(&self) -> bool {
+        let name = self.cmd.get_str_arg(0);
+        name.eq_ignore_ascii_case("function") || name.eq_ignore_ascii_case("script")
+    }
+
     pub fn cal_slots(&mut self, key_parser: &KeyParser) -> anyhow::Result<Vec<u16>> {
-        if self.is_base {
+        if self.is_base_key() {
             Ok(vec![KeyParser::calc_slot(self.key.as_bytes())])
         } else {
             if self.cmd.keys.is_empty() {
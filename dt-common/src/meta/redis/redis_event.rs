@@ -0,0 +1,33 @@
+use serde::{Deserialize, Serialize};
+
+use crate::meta::position::Position;
+
+/// A redis change sent to message queues.
+/// For aof commands (and aux commands from rdb, E.g. function libraries): command, keys and args are set.
+/// For rdb entries: keys contains the entry key, value_type and value_cmds (commands to rebuild the value) are set.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct RedisEvent {
+    pub db: i64,
+    // true if the event comes from rdb
+    pub is_base: bool,
+    // upper case, E.g. SET, HSET, FUNCTION-LOAD
+    pub command: String,
+    pub keys: Vec<String>,
+    pub args: Vec<String>,
+    // E.g. string, hash, list
+    pub value_type: String,
+    pub value_cmds: Vec<Vec<String>>,
+    // true if keys, args and value_cmds are base64 encoded, since some of them are not valid utf-8
+    pub is_base64: bool,
+    // time to live in milliseconds, 0 if the key has no expiration
+    pub expire_ms: i64,
+    pub timestamp: String,
+    pub position: Position,
+}
+
+impl RedisEvent {
+    /// Messages with the same key go to the same partition
+    pub fn get_partition_key(&self) -> String {
+        self.keys.first().cloned().unwrap_or_default()
+    }
+}
@@ -83,14 +83,65 @@ impl ListObject {
 }
 
 #[derive(Debug, Clone)]
-pub struct ModuleObject {}
+pub struct ModuleObject {
+    pub key: RedisString,
+    // E.g. ReJSON-RL, MBbloom--, TSDB-TYPE
+    pub module_name: String,
+    pub value: ModuleValue,
+}
 
 impl ModuleObject {
     pub fn new() -> Self {
-        Self {}
+        Self {
+            key: RedisString::new(),
+            module_name: String::new(),
+            value: ModuleValue::Unknown,
+        }
     }
 }
 
+#[derive(Debug, Clone)]
+pub enum ModuleValue {
+    // serialized json text
+    Json(String),
+    Bloom(BloomObject),
+    TimeSeries(TimeSeriesObject),
+    // modules not supported, or failed to decode, only restore is available
+    Unknown,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct BloomObject {
+    pub size: u64,
+    pub options: u64,
+    pub growth: u64,
+    pub filters: Vec<BloomFilter>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct BloomFilter {
+    pub entries: u64,
+    pub error: f64,
+    pub hashes: u64,
+    pub bpe: f64,
+    pub bits: u64,
+    pub n2: u64,
+    pub data: Vec<u8>,
+    // items added to this filter
+    pub size: u64,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct TimeSeriesObject {
+    pub retention_ms: u64,
+    pub chunk_size: u64,
+    pub uncompressed: bool,
+    pub duplicate_policy: Option<String>,
+    pub labels: Vec<(RedisString, RedisString)>,
+    // (timestamp, value)
+    pub samples: Vec<(u64, f64)>,
+}
+
 #[derive(Debug, Clone)]
 pub struct SetObject {
     pub key: RedisString,
@@ -334,7 +385,15 @@ impl RedisObject {
                 }
                 size + v.key.bytes.len()
             }
-            RedisObject::Module(_) => 0,
+            RedisObject::Module(v) => {
+                let size = match &v.value {
+                    ModuleValue::Json(json) => json.len(),
+                    ModuleValue::Bloom(bloom) => bloom.filters.iter().map(|i| i.data.len()).sum(),
+                    ModuleValue::TimeSeries(ts) => ts.samples.len() * 16,
+                    ModuleValue::Unknown => 0,
+                };
+                size + v.key.bytes.len()
+            }
             RedisObject::Unknown => 0,
         }
     }
@@ -6,4 +6,7 @@ pub enum RedisStatisticType {
     BigKey,
     #[strum(serialize = "hot_key")]
     HotKey,
+    // aggregate key writes from psync continuously, see KeyStatistics
+    #[strum(serialize = "continuous")]
+    Continuous,
 }
@@ -23,6 +23,14 @@ pub struct RowData {
     pub before: Option<HashMap<String, ColValue>>,
     pub after: Option<HashMap<String, ColValue>>,
     pub data_size: usize,
+    // before / after only contain part of the columns, columns absent are unchanged,
+    // such as rows from mysql binlog with binlog_row_image=minimal/noblob
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub partial: bool,
+    // a delete of all rows matching the cols present in before instead of one row by keys,
+    // such as deleting the child rows of a flattened mongo document by parent_col
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub delete_all: bool,
 }
 
 impl std::fmt::Display for RowData {
@@ -46,6 +54,8 @@ impl RowData {
             before,
             after,
             data_size: 0,
+            partial: false,
+            delete_all: false,
         };
         me.data_size = me.get_data_malloc_size();
         me
@@ -65,6 +75,8 @@ impl RowData {
             before: self.after.clone(),
             after: self.before.clone(),
             data_size: self.data_size,
+            partial: self.partial,
+            delete_all: false,
         }
     }
 
@@ -183,7 +195,11 @@ impl RowData {
         // refer to: https://docs.oracle.com/javase/6/docs/api/java/util/List.html#hashCode%28%29
         let mut hash_code = 1u128;
         for col in tb_meta.id_cols.iter() {
-            let col_hash_code = col_values.get(col).unwrap().hash_code();
+            // key col may be absent in partial images
+            let Some(col_value) = col_values.get(col) else {
+                return 0;
+            };
+            let col_hash_code = col_value.hash_code();
             // col_hash_code is 0 if col_value is ColValue::None,
             // consider fowlling case,
             // create table a(id int, value int, unique key(id, value));
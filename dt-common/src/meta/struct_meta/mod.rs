@@ -1,3 +1,4 @@
 pub mod statement;
+pub mod struct_diff;
 pub mod struct_data;
 pub mod structure;
@@ -147,63 +147,70 @@ impl MysqlCreateTableStatement {
 
         columns.sort_by(|c1, c2| c1.ordinal_position.cmp(&c2.ordinal_position));
         for i in columns.iter() {
-            let mut line = String::new();
-            line.push_str(&format!("`{}` {}", i.column_name, i.column_type));
+            sql_lines.push(Self::column_to_sql(i));
 
-            if !i.character_set_name.is_empty() {
-                line.push_str(&format!(" CHARACTER SET {}", i.character_set_name))
+            if i.column_key == "PRI" {
+                pks.push(i.column_name.clone());
             }
+        }
 
-            if !i.collation_name.is_empty() {
-                line.push_str(&format!(" COLLATE {}", i.collation_name))
-            }
+        (sql_lines.join(", "), pks)
+    }
 
-            match &i.column_default {
-                Some(ColumnDefault::Expression(v)) => line.push_str(&format!(" DEFAULT {}", v)),
-                Some(ColumnDefault::Literal(v)) => {
-                    if i.column_type.to_lowercase().starts_with("bit") {
-                        // https://github.com/apecloud/ape-dts/issues/319
-                        // CREATE TABLE a(b bit(1) default b'1');
-                        line.push_str(&format!(" DEFAULT {}", v))
-                    } else {
-                        line.push_str(&format!(" DEFAULT '{}'", Self::escape(v)))
-                    }
-                }
-                _ => {}
-            }
+    pub(crate) fn column_to_sql(column: &Column) -> String {
+        let mut line = String::new();
+        line.push_str(&format!("`{}` {}", column.column_name, column.column_type));
 
-            // auto_increment
-            // on update CURRENT_TIMESTAMP
-            // mysql 8.0:
-            //  DEFAULT_GENERATED
-            //  DEFAULT_GENERATED on update CURRENT_TIMESTAMP
-            let extra = i.extra.replacen("DEFAULT_GENERATED", "", 1);
-            if !extra.is_empty() {
-                line.push_str(&format!(" {}", extra));
-            }
+        if !column.character_set_name.is_empty() {
+            line.push_str(&format!(" CHARACTER SET {}", column.character_set_name))
+        }
 
-            let nullable = if !i.is_nullable {
-                String::from("NOT NULL")
-            } else {
-                String::from("NULL")
-            };
+        if !column.collation_name.is_empty() {
+            line.push_str(&format!(" COLLATE {}", column.collation_name))
+        }
 
-            if !i.column_comment.is_empty() {
-                line.push_str(&format!(" COMMENT '{}'", Self::escape(&i.column_comment)))
+        match &column.column_default {
+            Some(ColumnDefault::Expression(v)) => line.push_str(&format!(" DEFAULT {}", v)),
+            Some(ColumnDefault::Literal(v)) => {
+                if column.column_type.to_lowercase().starts_with("bit") {
+                    // https://github.com/apecloud/ape-dts/issues/319
+                    // CREATE TABLE a(b bit(1) default b'1');
+                    line.push_str(&format!(" DEFAULT {}", v))
+                } else {
+                    line.push_str(&format!(" DEFAULT '{}'", Self::escape(v)))
+                }
             }
+            _ => {}
+        }
 
-            line.push_str(&format!(" {}", nullable));
-            sql_lines.push(line);
+        // auto_increment
+        // on update CURRENT_TIMESTAMP
+        // mysql 8.0:
+        //  DEFAULT_GENERATED
+        //  DEFAULT_GENERATED on update CURRENT_TIMESTAMP
+        let extra = column.extra.replacen("DEFAULT_GENERATED", "", 1);
+        if !extra.is_empty() {
+            line.push_str(&format!(" {}", extra));
+        }
 
-            if i.column_key == "PRI" {
-                pks.push(i.column_name.clone());
-            }
+        let nullable = if !column.is_nullable {
+            String::from("NOT NULL")
+        } else {
+            String::from("NULL")
+        };
+
+        if !column.column_comment.is_empty() {
+            line.push_str(&format!(
+                " COMMENT '{}'",
+                Self::escape(&column.column_comment)
+            ))
         }
 
-        (sql_lines.join(", "), pks)
+        line.push_str(&format!(" {}", nullable));
+        line
     }
 
-    fn index_to_sql(index: &mut Index) -> String {
+    pub(crate) fn index_to_sql(index: &mut Index) -> String {
         let columns_sql = Self::build_cols_for_index(index);
 
         // no need index_type in "CREATE {} INDEX `{}` USING {IndexType}"
@@ -252,7 +259,7 @@ impl MysqlCreateTableStatement {
             .join(",")
     }
 
-    fn constraint_to_sql(constraint: &Constraint) -> String {
+    pub(crate) fn constraint_to_sql(constraint: &Constraint) -> String {
         // TODO, check for escapes
         format!(
             "ALTER TABLE `{}`.`{}` ADD CONSTRAINT `{}` {} {} ",
@@ -264,7 +271,7 @@ impl MysqlCreateTableStatement {
         )
     }
 
-    fn escape(text: &str) -> String {
+    pub(crate) fn escape(text: &str) -> String {
         text.replace('\'', "\'\'").to_string()
     }
 }
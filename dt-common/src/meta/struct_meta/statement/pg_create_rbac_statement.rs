@@ -130,6 +130,8 @@ mod tests {
             do_dcls: HashSet::new(),
             do_ddls: HashSet::new(),
             ignore_cmds: HashSet::new(),
+            do_keys: Vec::new(),
+            ignore_keys: Vec::new(),
             where_conditions: HashMap::new(),
         };
         filter.do_structures.insert(StructureType::Rbac.to_string());
@@ -148,38 +148,44 @@ impl PgCreateTableStatement {
     }
 
     fn columns_to_sql(columns: &mut [Column]) -> String {
-        let mut sql = String::new();
-
         columns.sort_by(|a, b| a.ordinal_position.cmp(&b.ordinal_position));
-        for column in columns.iter() {
-            sql.push_str(format!(r#""{}" {} "#, column.column_name, column.column_type).as_str());
-            if !column.is_nullable {
-                sql.push_str("NOT NULL ");
-            }
-            match &column.column_default {
-                Some(ColumnDefault::Expression(v)) | Some(ColumnDefault::Literal(v)) => {
-                    sql.push_str(format!("DEFAULT {} ", v).as_str())
-                }
-                None => {}
-            }
-            if let Some(x) = &column.generated {
-                if x == "ALWAYS" {
-                    sql.push_str("GENERATED ALWAYS AS IDENTITY ")
-                } else {
-                    sql.push_str("GENERATED BY DEFAULT AS IDENTITY ")
-                }
+        columns
+            .iter()
+            .map(Self::column_to_sql)
+            .collect::<Vec<String>>()
+            .join(",")
+    }
+
+    pub(crate) fn column_to_sql(column: &Column) -> String {
+        let mut sql = String::new();
+        sql.push_str(format!(r#""{}" {} "#, column.column_name, column.column_type).as_str());
+        if !column.is_nullable {
+            sql.push_str("NOT NULL ");
+        }
+        match &column.column_default {
+            Some(ColumnDefault::Expression(v)) | Some(ColumnDefault::Literal(v)) => {
+                sql.push_str(format!("DEFAULT {} ", v).as_str())
             }
-            sql.push(',');
+            None => {}
         }
-
-        if sql.ends_with(',') {
-            sql = sql[0..sql.len() - 1].to_string();
+        if let Some(x) = &column.generated {
+            sql.push_str(&format!(
+                "GENERATED {} AS IDENTITY ",
+                Self::identity_kind(x)
+            ));
         }
-
         sql
     }
 
-    fn index_to_sql(index: &Index) -> anyhow::Result<String> {
+    pub(crate) fn identity_kind(generated: &str) -> &'static str {
+        if generated == "ALWAYS" {
+            "ALWAYS"
+        } else {
+            "BY DEFAULT"
+        }
+    }
+
+    pub(crate) fn index_to_sql(index: &Index) -> anyhow::Result<String> {
         let parser = DdlParser::new(DbType::Pg);
         if let Ok(mut ddl_data) = parser.parse(&index.definition) {
             if let DdlStatement::PgCreateIndex(s) = &mut ddl_data.statement {
@@ -197,7 +203,7 @@ impl PgCreateTableStatement {
         }
     }
 
-    fn comment_to_sql(comment: &Comment) -> String {
+    pub(crate) fn comment_to_sql(comment: &Comment) -> String {
         if comment.column_name.is_empty() {
             format!(
                 r#"COMMENT ON TABLE "{}"."{}" is '{}'"#,
@@ -242,7 +248,7 @@ impl PgCreateTableStatement {
         )
     }
 
-    fn constraint_to_sql(constraint: &Constraint) -> String {
+    pub(crate) fn constraint_to_sql(constraint: &Constraint) -> String {
         format!(
             r#"ALTER TABLE "{}"."{}" ADD CONSTRAINT "{}" {}"#,
             constraint.schema_name,
@@ -1,4 +1,7 @@
-use crate::rdb_filter::RdbFilter;
+use crate::{
+    meta::struct_meta::structure::constraint::{Constraint, ConstraintType},
+    rdb_filter::RdbFilter,
+};
 
 use super::{
     mysql_create_database_statement::MysqlCreateDatabaseStatement,
@@ -8,6 +11,17 @@ use super::{
     pg_create_table_statement::PgCreateTableStatement,
 };
 
+/// Order in which struct sqls are executed when index and constraint creation is deferred
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum SqlPhase {
+    // databases, schemas, sequences, tables with primary keys and comments
+    PreData,
+    // secondary indexes, unique and check constraints
+    PostData,
+    // foreign keys may reference unique keys built in PostData, so they go last
+    ForeignKey,
+}
+
 #[derive(Debug, Clone, Default)]
 pub enum StructStatement {
     MysqlCreateDatabase(MysqlCreateDatabaseStatement),
@@ -30,4 +44,206 @@ impl StructStatement {
             _ => Ok(vec![]),
         }
     }
+
+    pub fn get_sql_phase(&self, key: &str) -> SqlPhase {
+        match self {
+            Self::MysqlCreateTable(s) => {
+                // without primary key, sinkers identify rows by unique keys,
+                // so they must exist before data is loaded
+                let has_pk = s.table.columns.iter().any(|c| c.column_key == "PRI");
+                if key.starts_with("index.") {
+                    return Self::get_index_phase(has_pk);
+                }
+                Self::get_constraint_phase(&s.constraints, key, has_pk, |c| {
+                    format!(
+                        "constraint.{}.{}.{}",
+                        c.database_name, c.table_name, c.constraint_name
+                    )
+                })
+            }
+
+            Self::PgCreateTable(s) => {
+                let has_pk = s
+                    .constraints
+                    .iter()
+                    .any(|c| c.constraint_type == ConstraintType::Primary);
+                if key.starts_with("index.") {
+                    return Self::get_index_phase(has_pk);
+                }
+                Self::get_constraint_phase(&s.constraints, key, has_pk, |c| {
+                    format!(
+                        "constraint.{}.{}.{}",
+                        c.schema_name, c.table_name, c.constraint_name
+                    )
+                })
+            }
+
+            _ => SqlPhase::PreData,
+        }
+    }
+
+    fn get_index_phase(has_pk: bool) -> SqlPhase {
+        if has_pk {
+            SqlPhase::PostData
+        } else {
+            SqlPhase::PreData
+        }
+    }
+
+    fn get_constraint_phase<F>(
+        constraints: &[Constraint],
+        key: &str,
+        has_pk: bool,
+        build_key: F,
+    ) -> SqlPhase
+    where
+        F: Fn(&Constraint) -> String,
+    {
+        for c in constraints.iter() {
+            if build_key(c) != key {
+                continue;
+            }
+            return match c.constraint_type {
+                ConstraintType::Primary => SqlPhase::PreData,
+                ConstraintType::Unique if !has_pk => SqlPhase::PreData,
+                ConstraintType::Foregin => SqlPhase::ForeignKey,
+                _ => SqlPhase::PostData,
+            };
+        }
+        SqlPhase::PreData
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::meta::struct_meta::structure::{column::Column, table::Table};
+
+    fn build_constraint(name: &str, constraint_type: ConstraintType) -> Constraint {
+        Constraint {
+            database_name: "db_1".to_string(),
+            schema_name: "db_1".to_string(),
+            table_name: "tb_1".to_string(),
+            constraint_name: name.to_string(),
+            constraint_type,
+            definition: String::new(),
+        }
+    }
+
+    fn build_mysql_statement(has_pk: bool) -> StructStatement {
+        let column_key = if has_pk { "PRI" } else { "UNI" };
+        StructStatement::MysqlCreateTable(MysqlCreateTableStatement {
+            table: Table {
+                database_name: "db_1".to_string(),
+                table_name: "tb_1".to_string(),
+                columns: vec![Column {
+                    column_name: "id".to_string(),
+                    column_key: column_key.to_string(),
+                    ..Default::default()
+                }],
+                ..Default::default()
+            },
+            constraints: vec![
+                build_constraint("chk_1", ConstraintType::Check),
+                build_constraint("fk_1", ConstraintType::Foregin),
+            ],
+            indexes: vec![],
+        })
+    }
+
+    fn build_pg_statement(has_pk: bool) -> StructStatement {
+        let mut constraints = vec![
+            build_constraint("uk_1", ConstraintType::Unique),
+            build_constraint("chk_1", ConstraintType::Check),
+            build_constraint("fk_1", ConstraintType::Foregin),
+        ];
+        if has_pk {
+            constraints.push(build_constraint("pk_1", ConstraintType::Primary));
+        }
+        StructStatement::PgCreateTable(PgCreateTableStatement {
+            table: Table::default(),
+            table_comments: vec![],
+            column_comments: vec![],
+            constraints,
+            indexes: vec![],
+            sequences: vec![],
+            sequence_owners: vec![],
+        })
+    }
+
+    #[test]
+    fn test_mysql_sql_phase() {
+        let statement = build_mysql_statement(true);
+        assert_eq!(
+            statement.get_sql_phase("table.db_1.tb_1"),
+            SqlPhase::PreData
+        );
+        assert_eq!(
+            statement.get_sql_phase("index.db_1.tb_1"),
+            SqlPhase::PostData
+        );
+        assert_eq!(
+            statement.get_sql_phase("constraint.db_1.tb_1.chk_1"),
+            SqlPhase::PostData
+        );
+        assert_eq!(
+            statement.get_sql_phase("constraint.db_1.tb_1.fk_1"),
+            SqlPhase::ForeignKey
+        );
+
+        // without primary key, indexes may be needed to identify rows
+        let statement = build_mysql_statement(false);
+        assert_eq!(
+            statement.get_sql_phase("index.db_1.tb_1"),
+            SqlPhase::PreData
+        );
+        assert_eq!(
+            statement.get_sql_phase("constraint.db_1.tb_1.chk_1"),
+            SqlPhase::PostData
+        );
+    }
+
+    #[test]
+    fn test_pg_sql_phase() {
+        let statement = build_pg_statement(true);
+        assert_eq!(
+            statement.get_sql_phase("table.db_1.tb_1"),
+            SqlPhase::PreData
+        );
+        assert_eq!(
+            statement.get_sql_phase("sequence.db_1.tb_1_id_seq"),
+            SqlPhase::PreData
+        );
+        assert_eq!(
+            statement.get_sql_phase("constraint.db_1.tb_1.pk_1"),
+            SqlPhase::PreData
+        );
+        assert_eq!(
+            statement.get_sql_phase("constraint.db_1.tb_1.uk_1"),
+            SqlPhase::PostData
+        );
+        assert_eq!(
+            statement.get_sql_phase("constraint.db_1.tb_1.chk_1"),
+            SqlPhase::PostData
+        );
+        assert_eq!(
+            statement.get_sql_phase("constraint.db_1.tb_1.fk_1"),
+            SqlPhase::ForeignKey
+        );
+        assert_eq!(
+            statement.get_sql_phase("index.db_1.tb_1.idx_1"),
+            SqlPhase::PostData
+        );
+
+        // without primary key, unique constraints and indexes go with the table
+        let statement = build_pg_statement(false);
+        assert_eq!(
+            statement.get_sql_phase("constraint.db_1.tb_1.uk_1"),
+            SqlPhase::PreData
+        );
+        assert_eq!(
+            statement.get_sql_phase("index.db_1.tb_1.idx_1"),
+            SqlPhase::PreData
+        );
+    }
 }
@@ -0,0 +1,924 @@
+use std::collections::{BTreeMap, HashMap};
+
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use crate::rdb_filter::RdbFilter;
+
+use super::{
+    statement::{
+        mysql_create_table_statement::MysqlCreateTableStatement,
+        pg_create_table_statement::PgCreateTableStatement,
+    },
+    structure::{
+        column::{Column, ColumnDefault},
+        comment::Comment,
+        constraint::{Constraint, ConstraintType},
+        index::{Index, IndexKind},
+        structure_type::StructureType,
+    },
+};
+
+/// Semantic difference between a source table and its target table,
+/// revise_sqls are the statements that reconcile the target to the source.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct StructDiff {
+    pub key: String,
+    pub schema: String,
+    pub tb: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub table_diffs: Vec<ItemDiff>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub column_diffs: Vec<ColumnDiff>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub index_diffs: Vec<ItemDiff>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub constraint_diffs: Vec<ItemDiff>,
+    #[serde(default)]
+    pub revise_sqls: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ColumnDiff {
+    pub column: String,
+    pub diff_type: ColumnDiffType,
+    pub src: Option<String>,
+    pub dst: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ColumnDiffType {
+    // exists in source but not in target
+    Miss,
+    // exists in target but not in source
+    Extra,
+    Type,
+    Nullable,
+    Default,
+    CharacterSet,
+    Collation,
+    Comment,
+    // mysql: auto_increment, on update CURRENT_TIMESTAMP
+    Attribute,
+    // pg: GENERATED ALWAYS / BY DEFAULT AS IDENTITY
+    Identity,
+}
+
+/// Difference of a named item: a table property, an index or a constraint
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ItemDiff {
+    pub name: String,
+    pub diff_type: ItemDiffType,
+    pub src: Option<String>,
+    pub dst: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ItemDiffType {
+    Miss,
+    Extra,
+    Diff,
+}
+
+impl std::fmt::Display for StructDiff {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", json!(self))
+    }
+}
+
+impl StructDiff {
+    pub fn new(schema: &str, tb: &str) -> Self {
+        Self {
+            key: format!("table.{}.{}", schema, tb),
+            schema: schema.to_string(),
+            tb: tb.to_string(),
+            ..Default::default()
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.table_diffs.is_empty()
+            && self.column_diffs.is_empty()
+            && self.index_diffs.is_empty()
+            && self.constraint_diffs.is_empty()
+    }
+
+    pub fn from_mysql_table(
+        src: &MysqlCreateTableStatement,
+        dst: &MysqlCreateTableStatement,
+        filter: &RdbFilter,
+    ) -> Self {
+        let (db, tb) = (&src.table.database_name, &src.table.table_name);
+        let mut me = Self::new(db, tb);
+        let alter_prefix = format!("ALTER TABLE `{}`.`{}`", db, tb);
+        let mut drop_sqls = Vec::new();
+
+        if !filter.filter_structure(&StructureType::Table) {
+            // table options
+            let options = [
+                ("engine", &src.table.engine_name, &dst.table.engine_name),
+                (
+                    "character_set",
+                    &src.table.character_set,
+                    &dst.table.character_set,
+                ),
+                (
+                    "collation",
+                    &src.table.table_collation,
+                    &dst.table.table_collation,
+                ),
+                (
+                    "comment",
+                    &src.table.table_comment,
+                    &dst.table.table_comment,
+                ),
+            ];
+            for (name, src_value, dst_value) in options {
+                if src_value == dst_value {
+                    continue;
+                }
+                me.table_diffs.push(ItemDiff::new(
+                    name,
+                    ItemDiffType::Diff,
+                    Some(src_value.into()),
+                    Some(dst_value.into()),
+                ));
+                let option_sql = match name {
+                    "engine" => format!("ENGINE={}", src_value),
+                    "character_set" => format!("DEFAULT CHARSET={}", src_value),
+                    "collation" => format!("COLLATE={}", src_value),
+                    _ => format!("COMMENT='{}'", MysqlCreateTableStatement::escape(src_value)),
+                };
+                me.revise_sqls
+                    .push(format!("{} {}", alter_prefix, option_sql));
+            }
+
+            // columns, column names are case insensitive in mysql
+            let src_cols = Self::sorted_columns(&src.table.columns);
+            let dst_cols: HashMap<String, &Column> = dst
+                .table
+                .columns
+                .iter()
+                .map(|c| (c.column_name.to_lowercase(), c))
+                .collect();
+
+            let mut prev_col: Option<&str> = None;
+            for src_col in src_cols.iter() {
+                let position = match prev_col {
+                    Some(prev) => format!("AFTER `{}`", prev),
+                    None => "FIRST".to_string(),
+                };
+                prev_col = Some(src_col.column_name.as_str());
+
+                let Some(dst_col) = dst_cols.get(&src_col.column_name.to_lowercase()) else {
+                    me.push_column_diff(
+                        &src_col.column_name,
+                        ColumnDiffType::Miss,
+                        Some(MysqlCreateTableStatement::column_to_sql(src_col)),
+                        None,
+                    );
+                    me.revise_sqls.push(format!(
+                        "{} ADD COLUMN {} {}",
+                        alter_prefix,
+                        MysqlCreateTableStatement::column_to_sql(src_col),
+                        position
+                    ));
+                    continue;
+                };
+
+                let count = me.column_diffs.len();
+                me.diff_column_basics(src_col, dst_col);
+                let src_extra = Self::normalize_mysql_extra(&src_col.extra);
+                let dst_extra = Self::normalize_mysql_extra(&dst_col.extra);
+                let props = [
+                    (
+                        ColumnDiffType::CharacterSet,
+                        &src_col.character_set_name,
+                        &dst_col.character_set_name,
+                    ),
+                    (
+                        ColumnDiffType::Collation,
+                        &src_col.collation_name,
+                        &dst_col.collation_name,
+                    ),
+                    (
+                        ColumnDiffType::Comment,
+                        &src_col.column_comment,
+                        &dst_col.column_comment,
+                    ),
+                    (ColumnDiffType::Attribute, &src_extra, &dst_extra),
+                ];
+                for (diff_type, src_value, dst_value) in props {
+                    if src_value != dst_value {
+                        me.push_column_diff(
+                            &src_col.column_name,
+                            diff_type,
+                            Some(src_value.into()),
+                            Some(dst_value.into()),
+                        );
+                    }
+                }
+
+                if me.column_diffs.len() > count {
+                    me.revise_sqls.push(format!(
+                        "{} MODIFY COLUMN {}",
+                        alter_prefix,
+                        MysqlCreateTableStatement::column_to_sql(src_col)
+                    ));
+                }
+            }
+
+            for dst_col in Self::sorted_columns(&dst.table.columns) {
+                if !src_cols
+                    .iter()
+                    .any(|c| c.column_name.eq_ignore_ascii_case(&dst_col.column_name))
+                {
+                    me.push_column_diff(
+                        &dst_col.column_name,
+                        ColumnDiffType::Extra,
+                        None,
+                        Some(MysqlCreateTableStatement::column_to_sql(dst_col)),
+                    );
+                    drop_sqls.push(format!(
+                        "{} DROP COLUMN `{}`",
+                        alter_prefix, dst_col.column_name
+                    ));
+                }
+            }
+
+            // primary key, decided by column_key in mysql
+            let src_pks = Self::mysql_pk_cols(&src.table.columns);
+            let dst_pks = Self::mysql_pk_cols(&dst.table.columns);
+            if src_pks != dst_pks {
+                me.table_diffs.push(ItemDiff::new(
+                    "primary_key",
+                    ItemDiffType::Diff,
+                    Some(src_pks.join(",")),
+                    Some(dst_pks.join(",")),
+                ));
+                let mut actions = Vec::new();
+                if !dst_pks.is_empty() {
+                    actions.push("DROP PRIMARY KEY".to_string());
+                }
+                if !src_pks.is_empty() {
+                    let cols: Vec<String> = src_pks.iter().map(|c| format!("`{}`", c)).collect();
+                    actions.push(format!("ADD PRIMARY KEY ({})", cols.join(",")));
+                }
+                me.revise_sqls
+                    .push(format!("{} {}", alter_prefix, actions.join(", ")));
+            }
+        }
+
+        // indexes
+        let src_indexes = Self::mysql_index_sqls(&src.indexes, filter);
+        let dst_indexes = Self::mysql_index_sqls(&dst.indexes, filter);
+        let drop_index = |name: &str| format!("DROP INDEX `{}` ON `{}`.`{}`", name, db, tb);
+        me.index_diffs = Self::diff_items(
+            &src_indexes,
+            &dst_indexes,
+            &mut me.revise_sqls,
+            &mut drop_sqls,
+            drop_index,
+        );
+
+        // constraints
+        let mut src_constraints = BTreeMap::new();
+        let mut dst_constraints = BTreeMap::new();
+        if !filter.filter_structure(&StructureType::Constraint) {
+            for (constraints, sqls) in [
+                (&src.constraints, &mut src_constraints),
+                (&dst.constraints, &mut dst_constraints),
+            ] {
+                for i in constraints.iter() {
+                    sqls.insert(
+                        i.constraint_name.clone(),
+                        MysqlCreateTableStatement::constraint_to_sql(i),
+                    );
+                }
+            }
+        }
+        let dst_constraint_types: HashMap<&str, &ConstraintType> = dst
+            .constraints
+            .iter()
+            .map(|i| (i.constraint_name.as_str(), &i.constraint_type))
+            .collect();
+        let drop_constraint = |name: &str| {
+            let action = match dst_constraint_types.get(name) {
+                Some(ConstraintType::Foregin) => "FOREIGN KEY",
+                Some(ConstraintType::Check) => "CHECK",
+                _ => "CONSTRAINT",
+            };
+            format!("{} DROP {} `{}`", alter_prefix, action, name)
+        };
+        me.constraint_diffs = Self::diff_items(
+            &src_constraints,
+            &dst_constraints,
+            &mut me.revise_sqls,
+            &mut drop_sqls,
+            drop_constraint,
+        );
+
+        // drop statements go last so that nothing still depends on the dropped items
+        me.revise_sqls.extend(drop_sqls);
+        me
+    }
+
+    pub fn from_pg_table(
+        src: &PgCreateTableStatement,
+        dst: &PgCreateTableStatement,
+        filter: &RdbFilter,
+    ) -> Self {
+        let (schema, tb) = (&src.table.schema_name, &src.table.table_name);
+        let mut me = Self::new(schema, tb);
+        let alter_prefix = format!(r#"ALTER TABLE "{}"."{}""#, schema, tb);
+        let mut drop_sqls = Vec::new();
+
+        if !filter.filter_structure(&StructureType::Table) {
+            // table comment
+            let src_comment = Self::pg_table_comment(&src.table_comments);
+            let dst_comment = Self::pg_table_comment(&dst.table_comments);
+            if src_comment.map(|c| &c.comment) != dst_comment.map(|c| &c.comment) {
+                me.table_diffs.push(ItemDiff::new(
+                    "comment",
+                    ItemDiffType::Diff,
+                    src_comment.map(|c| c.comment.clone()),
+                    dst_comment.map(|c| c.comment.clone()),
+                ));
+                me.revise_sqls.push(match src_comment {
+                    Some(c) => PgCreateTableStatement::comment_to_sql(c),
+                    None => format!(r#"COMMENT ON TABLE "{}"."{}" IS NULL"#, schema, tb),
+                });
+            }
+
+            // columns
+            let src_cols = Self::sorted_columns(&src.table.columns);
+            let dst_cols: HashMap<&str, &Column> = dst
+                .table
+                .columns
+                .iter()
+                .map(|c| (c.column_name.as_str(), c))
+                .collect();
+            let src_col_comments = Self::pg_column_comments(&src.column_comments);
+            let dst_col_comments = Self::pg_column_comments(&dst.column_comments);
+
+            for src_col in src_cols.iter() {
+                let col = &src_col.column_name;
+                let Some(dst_col) = dst_cols.get(col.as_str()) else {
+                    me.push_column_diff(
+                        col,
+                        ColumnDiffType::Miss,
+                        Some(PgCreateTableStatement::column_to_sql(src_col)),
+                        None,
+                    );
+                    me.revise_sqls.push(format!(
+                        "{} ADD COLUMN {}",
+                        alter_prefix,
+                        PgCreateTableStatement::column_to_sql(src_col).trim_end()
+                    ));
+                    if let Some(c) = src_col_comments.get(col.as_str()) {
+                        me.revise_sqls
+                            .push(PgCreateTableStatement::comment_to_sql(c));
+                    }
+                    continue;
+                };
+
+                let alter_col = format!(r#"{} ALTER COLUMN "{}""#, alter_prefix, col);
+                for diff in Self::column_basic_diffs(src_col, dst_col) {
+                    let sql = match diff.diff_type {
+                        ColumnDiffType::Type => {
+                            format!("{} TYPE {}", alter_col, src_col.column_type)
+                        }
+                        ColumnDiffType::Nullable if src_col.is_nullable => {
+                            format!("{} DROP NOT NULL", alter_col)
+                        }
+                        ColumnDiffType::Nullable => format!("{} SET NOT NULL", alter_col),
+                        _ => match &src_col.column_default {
+                            Some(ColumnDefault::Expression(v))
+                            | Some(ColumnDefault::Literal(v)) => {
+                                format!("{} SET DEFAULT {}", alter_col, v)
+                            }
+                            None => format!("{} DROP DEFAULT", alter_col),
+                        },
+                    };
+                    me.column_diffs.push(diff);
+                    me.revise_sqls.push(sql);
+                }
+
+                if src_col.generated != dst_col.generated {
+                    me.push_column_diff(
+                        col,
+                        ColumnDiffType::Identity,
+                        src_col.generated.clone(),
+                        dst_col.generated.clone(),
+                    );
+                    let sql = match (&src_col.generated, &dst_col.generated) {
+                        (Some(x), None) => format!(
+                            "{} ADD GENERATED {} AS IDENTITY",
+                            alter_col,
+                            PgCreateTableStatement::identity_kind(x)
+                        ),
+                        (Some(x), Some(_)) => format!(
+                            "{} SET GENERATED {}",
+                            alter_col,
+                            PgCreateTableStatement::identity_kind(x)
+                        ),
+                        _ => format!("{} DROP IDENTITY IF EXISTS", alter_col),
+                    };
+                    me.revise_sqls.push(sql);
+                }
+
+                let src_comment = src_col_comments.get(col.as_str());
+                let dst_comment = dst_col_comments.get(col.as_str());
+                if src_comment.map(|c| &c.comment) != dst_comment.map(|c| &c.comment) {
+                    me.push_column_diff(
+                        col,
+                        ColumnDiffType::Comment,
+                        src_comment.map(|c| c.comment.clone()),
+                        dst_comment.map(|c| c.comment.clone()),
+                    );
+                    me.revise_sqls.push(match src_comment {
+                        Some(c) => PgCreateTableStatement::comment_to_sql(c),
+                        None => format!(
+                            r#"COMMENT ON COLUMN "{}"."{}"."{}" IS NULL"#,
+                            schema, tb, col
+                        ),
+                    });
+                }
+            }
+
+            for dst_col in Self::sorted_columns(&dst.table.columns) {
+                if !src_cols
+                    .iter()
+                    .any(|c| c.column_name == dst_col.column_name)
+                {
+                    me.push_column_diff(
+                        &dst_col.column_name,
+                        ColumnDiffType::Extra,
+                        None,
+                        Some(PgCreateTableStatement::column_to_sql(dst_col)),
+                    );
+                    drop_sqls.push(format!(
+                        r#"{} DROP COLUMN "{}""#,
+                        alter_prefix, dst_col.column_name
+                    ));
+                }
+            }
+        }
+
+        // constraints, primary and unique constraints belong to table structure
+        let pg_constraint_sqls = |constraints: &[Constraint]| {
+            let mut sqls = BTreeMap::new();
+            for i in constraints.iter() {
+                let structure_type = match i.constraint_type {
+                    ConstraintType::Primary | ConstraintType::Unique => StructureType::Table,
+                    _ => StructureType::Constraint,
+                };
+                if !filter.filter_structure(&structure_type) {
+                    sqls.insert(
+                        i.constraint_name.clone(),
+                        PgCreateTableStatement::constraint_to_sql(i),
+                    );
+                }
+            }
+            sqls
+        };
+        let drop_constraint =
+            |name: &str| format!(r#"{} DROP CONSTRAINT IF EXISTS "{}""#, alter_prefix, name);
+        me.constraint_diffs = Self::diff_items(
+            &pg_constraint_sqls(&src.constraints),
+            &pg_constraint_sqls(&dst.constraints),
+            &mut me.revise_sqls,
+            &mut drop_sqls,
+            drop_constraint,
+        );
+
+        // indexes
+        let pg_index_sqls = |indexes: &[Index]| {
+            let mut sqls = BTreeMap::new();
+            for i in indexes.iter() {
+                if Self::filter_index(i, filter) {
+                    continue;
+                }
+                let sql = PgCreateTableStatement::index_to_sql(i)
+                    .unwrap_or_else(|_| i.definition.clone());
+                sqls.insert(i.index_name.clone(), sql);
+            }
+            sqls
+        };
+        let drop_index = |name: &str| format!(r#"DROP INDEX IF EXISTS "{}"."{}""#, schema, name);
+        me.index_diffs = Self::diff_items(
+            &pg_index_sqls(&src.indexes),
+            &pg_index_sqls(&dst.indexes),
+            &mut me.revise_sqls,
+            &mut drop_sqls,
+            drop_index,
+        );
+
+        me.revise_sqls.extend(drop_sqls);
+        me
+    }
+
+    /// Build the diff for a table which exists in source but is missing in target
+    pub fn for_missing_table(schema: &str, tb: &str, create_sqls: Vec<(String, String)>) -> Self {
+        let mut me = Self::new(schema, tb);
+        me.table_diffs.push(ItemDiff::new(
+            "table",
+            ItemDiffType::Miss,
+            Some(tb.to_string()),
+            None,
+        ));
+        me.revise_sqls = create_sqls.into_iter().map(|(_, sql)| sql).collect();
+        me
+    }
+
+    fn diff_column_basics(&mut self, src_col: &Column, dst_col: &Column) {
+        let diffs = Self::column_basic_diffs(src_col, dst_col);
+        self.column_diffs.extend(diffs);
+    }
+
+    fn column_basic_diffs(src_col: &Column, dst_col: &Column) -> Vec<ColumnDiff> {
+        let mut diffs = Vec::new();
+        let col = &src_col.column_name;
+        if src_col.column_type != dst_col.column_type {
+            diffs.push(ColumnDiff {
+                column: col.clone(),
+                diff_type: ColumnDiffType::Type,
+                src: Some(src_col.column_type.clone()),
+                dst: Some(dst_col.column_type.clone()),
+            });
+        }
+
+        if src_col.is_nullable != dst_col.is_nullable {
+            diffs.push(ColumnDiff {
+                column: col.clone(),
+                diff_type: ColumnDiffType::Nullable,
+                src: Some(src_col.is_nullable.to_string()),
+                dst: Some(dst_col.is_nullable.to_string()),
+            });
+        }
+
+        if src_col.column_default != dst_col.column_default {
+            diffs.push(ColumnDiff {
+                column: col.clone(),
+                diff_type: ColumnDiffType::Default,
+                src: Self::default_to_string(&src_col.column_default),
+                dst: Self::default_to_string(&dst_col.column_default),
+            });
+        }
+        diffs
+    }
+
+    /// Compare items keyed by name with their creation sqls,
+    /// missing items are created, extra items are dropped and changed items are recreated
+    fn diff_items<F>(
+        src: &BTreeMap<String, String>,
+        dst: &BTreeMap<String, String>,
+        create_sqls: &mut Vec<String>,
+        drop_sqls: &mut Vec<String>,
+        drop_sql: F,
+    ) -> Vec<ItemDiff>
+    where
+        F: Fn(&str) -> String,
+    {
+        let mut diffs = Vec::new();
+        for (name, src_sql) in src.iter() {
+            match dst.get(name) {
+                Some(dst_sql) if dst_sql == src_sql => continue,
+                Some(dst_sql) => {
+                    diffs.push(ItemDiff::new(
+                        name,
+                        ItemDiffType::Diff,
+                        Some(src_sql.clone()),
+                        Some(dst_sql.clone()),
+                    ));
+                    // the item must be dropped before it is recreated
+                    create_sqls.push(drop_sql(name));
+                }
+                None => {
+                    diffs.push(ItemDiff::new(
+                        name,
+                        ItemDiffType::Miss,
+                        Some(src_sql.clone()),
+                        None,
+                    ));
+                }
+            }
+            create_sqls.push(src_sql.trim_end().to_string());
+        }
+
+        for (name, dst_sql) in dst.iter() {
+            if !src.contains_key(name) {
+                diffs.push(ItemDiff::new(
+                    name,
+                    ItemDiffType::Extra,
+                    None,
+                    Some(dst_sql.clone()),
+                ));
+                drop_sqls.push(drop_sql(name));
+            }
+        }
+        diffs
+    }
+
+    fn mysql_index_sqls(indexes: &[Index], filter: &RdbFilter) -> BTreeMap<String, String> {
+        let mut sqls = BTreeMap::new();
+        for i in indexes.iter() {
+            if Self::filter_index(i, filter) {
+                continue;
+            }
+            // index_to_sql sorts index columns by seq_in_index
+            let mut index = i.clone();
+            sqls.insert(
+                i.index_name.clone(),
+                MysqlCreateTableStatement::index_to_sql(&mut index),
+            );
+        }
+        sqls
+    }
+
+    fn filter_index(index: &Index, filter: &RdbFilter) -> bool {
+        // unique indexes belong to table structure, the same as to_sqls
+        match index.index_kind {
+            IndexKind::Unique => filter.filter_structure(&StructureType::Table),
+            _ => filter.filter_structure(&StructureType::Index),
+        }
+    }
+
+    fn mysql_pk_cols(columns: &[Column]) -> Vec<String> {
+        Self::sorted_columns(columns)
+            .into_iter()
+            .filter(|c| c.column_key == "PRI")
+            .map(|c| c.column_name.clone())
+            .collect()
+    }
+
+    fn normalize_mysql_extra(extra: &str) -> String {
+        extra
+            .replacen("DEFAULT_GENERATED", "", 1)
+            .trim()
+            .to_string()
+    }
+
+    fn pg_table_comment(comments: &[Comment]) -> Option<&Comment> {
+        comments.iter().find(|c| c.column_name.is_empty())
+    }
+
+    fn pg_column_comments(comments: &[Comment]) -> HashMap<&str, &Comment> {
+        comments
+            .iter()
+            .map(|c| (c.column_name.as_str(), c))
+            .collect()
+    }
+
+    fn sorted_columns(columns: &[Column]) -> Vec<&Column> {
+        let mut columns: Vec<&Column> = columns.iter().collect();
+        columns.sort_by_key(|c| c.ordinal_position);
+        columns
+    }
+
+    fn default_to_string(column_default: &Option<ColumnDefault>) -> Option<String> {
+        match column_default {
+            Some(ColumnDefault::Literal(v)) | Some(ColumnDefault::Expression(v)) => Some(v.clone()),
+            None => None,
+        }
+    }
+
+    fn push_column_diff(
+        &mut self,
+        column: &str,
+        diff_type: ColumnDiffType,
+        src: Option<String>,
+        dst: Option<String>,
+    ) {
+        self.column_diffs.push(ColumnDiff {
+            column: column.to_string(),
+            diff_type,
+            src,
+            dst,
+        });
+    }
+}
+
+impl ItemDiff {
+    pub fn new(
+        name: &str,
+        diff_type: ItemDiffType,
+        src: Option<String>,
+        dst: Option<String>,
+    ) -> Self {
+        Self {
+            name: name.to_string(),
+            diff_type,
+            src,
+            dst,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{config_enums::DbType, filter_config::FilterConfig};
+    use crate::meta::struct_meta::structure::{index::IndexColumn, table::Table};
+
+    fn build_filter(db_type: DbType) -> RdbFilter {
+        let config = FilterConfig {
+            do_structures: "*".to_string(),
+            ..Default::default()
+        };
+        RdbFilter::from_config(&config, &db_type).unwrap()
+    }
+
+    fn build_column(name: &str, position: u32, column_type: &str) -> Column {
+        Column {
+            column_name: name.to_string(),
+            ordinal_position: position,
+            column_type: column_type.to_string(),
+            is_nullable: true,
+            ..Default::default()
+        }
+    }
+
+    fn build_mysql_statement(
+        columns: Vec<Column>,
+        indexes: Vec<Index>,
+    ) -> MysqlCreateTableStatement {
+        MysqlCreateTableStatement {
+            table: Table {
+                database_name: "db_1".to_string(),
+                table_name: "tb_1".to_string(),
+                columns,
+                ..Default::default()
+            },
+            constraints: vec![],
+            indexes,
+        }
+    }
+
+    fn build_index(name: &str, cols: &[&str]) -> Index {
+        Index {
+            database_name: "db_1".to_string(),
+            table_name: "tb_1".to_string(),
+            index_name: name.to_string(),
+            columns: cols
+                .iter()
+                .enumerate()
+                .map(|(i, c)| IndexColumn {
+                    column_name: c.to_string(),
+                    seq_in_index: i as u32 + 1,
+                })
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_mysql_same_table() {
+        let filter = build_filter(DbType::Mysql);
+        let columns = vec![build_column("id", 1, "int"), build_column("f_1", 2, "text")];
+        let indexes = vec![build_index("idx_1", &["f_1"])];
+        let src = build_mysql_statement(columns.clone(), indexes.clone());
+        let dst = build_mysql_statement(columns, indexes);
+
+        let diff = StructDiff::from_mysql_table(&src, &dst, &filter);
+        assert!(diff.is_empty());
+        assert!(diff.revise_sqls.is_empty());
+    }
+
+    #[test]
+    fn test_mysql_column_diffs() {
+        let filter = build_filter(DbType::Mysql);
+        let mut src_f_1 = build_column("f_1", 2, "varchar(20)");
+        src_f_1.is_nullable = false;
+        let src = build_mysql_statement(
+            vec![
+                build_column("id", 1, "int"),
+                src_f_1,
+                build_column("f_2", 3, "int"),
+            ],
+            vec![],
+        );
+        let dst = build_mysql_statement(
+            vec![
+                build_column("id", 1, "int"),
+                build_column("f_1", 2, "varchar(10)"),
+                build_column("f_3", 3, "int"),
+            ],
+            vec![],
+        );
+
+        let diff = StructDiff::from_mysql_table(&src, &dst, &filter);
+        let diff_types: Vec<(&str, &ColumnDiffType)> = diff
+            .column_diffs
+            .iter()
+            .map(|i| (i.column.as_str(), &i.diff_type))
+            .collect();
+        assert_eq!(
+            diff_types,
+            vec![
+                ("f_1", &ColumnDiffType::Type),
+                ("f_1", &ColumnDiffType::Nullable),
+                ("f_2", &ColumnDiffType::Miss),
+                ("f_3", &ColumnDiffType::Extra),
+            ]
+        );
+        assert_eq!(
+            diff.revise_sqls,
+            vec![
+                "ALTER TABLE `db_1`.`tb_1` MODIFY COLUMN `f_1` varchar(20) NOT NULL",
+                "ALTER TABLE `db_1`.`tb_1` ADD COLUMN `f_2` int NULL AFTER `f_1`",
+                "ALTER TABLE `db_1`.`tb_1` DROP COLUMN `f_3`",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_mysql_column_name_case() {
+        let filter = build_filter(DbType::Mysql);
+        let src = build_mysql_statement(vec![build_column("F_1", 1, "int")], vec![]);
+        let dst = build_mysql_statement(vec![build_column("f_1", 1, "int")], vec![]);
+
+        let diff = StructDiff::from_mysql_table(&src, &dst, &filter);
+        assert!(diff.is_empty());
+    }
+
+    #[test]
+    fn test_mysql_index_diffs() {
+        let filter = build_filter(DbType::Mysql);
+        let columns = vec![build_column("f_1", 1, "int"), build_column("f_2", 2, "int")];
+        let src = build_mysql_statement(
+            columns.clone(),
+            vec![
+                build_index("idx_diff", &["f_1", "f_2"]),
+                build_index("idx_miss", &["f_1"]),
+            ],
+        );
+        let dst = build_mysql_statement(
+            columns,
+            vec![
+                build_index("idx_diff", &["f_2", "f_1"]),
+                build_index("idx_extra", &["f_2"]),
+            ],
+        );
+
+        let diff = StructDiff::from_mysql_table(&src, &dst, &filter);
+        let diff_types: Vec<(&str, &ItemDiffType)> = diff
+            .index_diffs
+            .iter()
+            .map(|i| (i.name.as_str(), &i.diff_type))
+            .collect();
+        assert_eq!(
+            diff_types,
+            vec![
+                ("idx_diff", &ItemDiffType::Diff),
+                ("idx_miss", &ItemDiffType::Miss),
+                ("idx_extra", &ItemDiffType::Extra),
+            ]
+        );
+        assert_eq!(
+            diff.revise_sqls,
+            vec![
+                "DROP INDEX `idx_diff` ON `db_1`.`tb_1`",
+                "CREATE  INDEX `idx_diff` ON `db_1`.`tb_1` (`f_1`,`f_2`)",
+                "CREATE  INDEX `idx_miss` ON `db_1`.`tb_1` (`f_1`)",
+                "DROP INDEX `idx_extra` ON `db_1`.`tb_1`",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_pg_column_diffs() {
+        let filter = build_filter(DbType::Pg);
+        let build_statement = |columns: Vec<Column>| PgCreateTableStatement {
+            table: Table {
+                schema_name: "s_1".to_string(),
+                table_name: "tb_1".to_string(),
+                columns,
+                ..Default::default()
+            },
+            table_comments: vec![],
+            column_comments: vec![],
+            constraints: vec![],
+            indexes: vec![],
+            sequences: vec![],
+            sequence_owners: vec![],
+        };
+
+        let mut src_f_1 = build_column("f_1", 1, "integer");
+        src_f_1.column_default = Some(ColumnDefault::Literal("0".to_string()));
+        let src = build_statement(vec![src_f_1]);
+        let dst = build_statement(vec![build_column("f_1", 1, "bigint")]);
+
+        let diff = StructDiff::from_pg_table(&src, &dst, &filter);
+        assert_eq!(diff.column_diffs.len(), 2);
+        assert_eq!(
+            diff.revise_sqls,
+            vec![
+                r#"ALTER TABLE "s_1"."tb_1" ALTER COLUMN "f_1" TYPE integer"#,
+                r#"ALTER TABLE "s_1"."tb_1" ALTER COLUMN "f_1" SET DEFAULT 0"#,
+            ]
+        );
+    }
+}
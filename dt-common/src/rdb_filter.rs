@@ -13,13 +13,14 @@ use crate::{
 
 use crate::meta::dcl_meta::dcl_type::DclType;
 use anyhow::Context;
-use regex::Regex;
+use regex::{bytes, Regex};
 use serde::{Deserialize, Serialize};
 
 type IgnoreCols = HashMap<(String, String), HashSet<String>>;
 type WhereConditions = HashMap<(String, String), String>;
 
 const JSON_PREFIX: &str = "json:";
+const REGEX_PREFIX: &str = "regex:";
 
 #[derive(Debug, Clone)]
 pub struct RdbFilter {
@@ -34,6 +35,9 @@ pub struct RdbFilter {
     pub do_ddls: HashSet<String>,
     pub do_dcls: HashSet<String>,
     pub ignore_cmds: HashSet<String>,
+    // patterns of redis keys
+    pub do_keys: Vec<bytes::Regex>,
+    pub ignore_keys: Vec<bytes::Regex>,
     pub where_conditions: WhereConditions,
     pub cache: HashMap<(String, String), bool>,
 }
@@ -52,6 +56,8 @@ impl RdbFilter {
             do_ddls: Self::parse_single_tokens(&config.do_ddls, db_type)?,
             do_dcls: Self::parse_single_tokens(&config.do_dcls, db_type)?,
             ignore_cmds: Self::parse_single_tokens(&config.ignore_cmds, db_type)?,
+            do_keys: Self::parse_key_patterns(&config.do_keys)?,
+            ignore_keys: Self::parse_key_patterns(&config.ignore_keys)?,
             where_conditions: Self::parse_where_conditions(&config.where_conditions)?,
             cache: HashMap::new(),
         })
@@ -131,6 +137,18 @@ impl RdbFilter {
         self.ignore_cmds.contains(cmd)
     }
 
+    pub fn has_key_filter(&self) -> bool {
+        !self.do_keys.is_empty() || !self.ignore_keys.is_empty()
+    }
+
+    pub fn filter_key(&self, key: &[u8]) -> bool {
+        if self.ignore_keys.iter().any(|i| i.is_match(key)) {
+            return true;
+        }
+        // empty do_keys means all keys
+        !self.do_keys.is_empty() && !self.do_keys.iter().any(|i| i.is_match(key))
+    }
+
     pub fn get_ignore_cols(&self, schema: &str, tb: &str) -> Option<&HashSet<String>> {
         self.ignore_cols.get(&(schema.to_string(), tb.to_string()))
     }
@@ -234,6 +252,74 @@ impl RdbFilter {
         ConfigTokenParser::parse_config(config_str, db_type, &delimiters)
     }
 
+    fn parse_key_patterns(config_str: &str) -> anyhow::Result<Vec<bytes::Regex>> {
+        // do_keys=user:*,order:[0-9]*,regex:^cache:\d+$
+        let mut results = Vec::new();
+        for token in config_str.split(',') {
+            let token = token.trim();
+            if token.is_empty() {
+                continue;
+            }
+
+            let pattern = if let Some(regex) = token.strip_prefix(REGEX_PREFIX) {
+                regex.to_string()
+            } else {
+                Self::glob_to_regex(token)
+            };
+            let regex = bytes::Regex::new(&pattern)
+                .with_context(|| format!("invalid key pattern: [{}]", token))?;
+            results.push(regex);
+        }
+        Ok(results)
+    }
+
+    fn glob_to_regex(glob: &str) -> String {
+        // same as the pattern of redis KEYS / SCAN:
+        // * : matching any chars
+        // ? : matching exactly 1 char
+        // [abc], [^abc], [a-z] : matching 1 char in / not in the set
+        // \ : escaping the next char
+        let mut pattern = String::from("(?s-u)^");
+        let mut chars = glob.chars().peekable();
+        while let Some(c) = chars.next() {
+            match c {
+                '*' => pattern.push_str(".*"),
+                '?' => pattern.push('.'),
+                '\\' => {
+                    if let Some(next) = chars.next() {
+                        pattern.push_str(&regex::escape(&next.to_string()));
+                    }
+                }
+                '[' => {
+                    let mut class = String::from("[");
+                    if chars.peek() == Some(&'^') {
+                        class.push(chars.next().unwrap());
+                    }
+                    let mut closed = false;
+                    for c in chars.by_ref() {
+                        if c == ']' {
+                            closed = true;
+                            break;
+                        }
+                        match c {
+                            '-' => class.push(c),
+                            _ => class.push_str(&regex::escape(&c.to_string())),
+                        }
+                    }
+                    if closed {
+                        class.push(']');
+                        pattern.push_str(&class);
+                    } else {
+                        pattern.push_str(&regex::escape(&format!("[{}", &class[1..])));
+                    }
+                }
+                _ => pattern.push_str(&regex::escape(&c.to_string())),
+            }
+        }
+        pattern.push('$');
+        pattern
+    }
+
     fn parse_ignore_cols(config_str: &str) -> anyhow::Result<IgnoreCols> {
         let mut results = IgnoreCols::new();
         if config_str.trim().is_empty() {
@@ -871,4 +957,34 @@ mod tests {
         assert!(rdb_fitler.filter_event("test_db_1", "aaaa", &RowType::Update));
         assert!(rdb_fitler.filter_event("test_db_1", "aaaa", &RowType::Delete));
     }
+
+    #[test]
+    fn test_rdb_filter_key() {
+        let db_type = DbType::Redis;
+
+        // empty do_keys means all keys
+        let config = FilterConfig::default();
+        let rdb_fitler = RdbFilter::from_config(&config, &db_type).unwrap();
+        assert!(!rdb_fitler.has_key_filter());
+        assert!(!rdb_fitler.filter_key(b"aaa"));
+
+        let config = FilterConfig {
+            do_keys: r"user:*, order:[0-9]?,regex:^cache:\d+$,中文*".to_string(),
+            ignore_keys: r"user:tmp:*,order:\*".to_string(),
+            ..Default::default()
+        };
+        let rdb_fitler = RdbFilter::from_config(&config, &db_type).unwrap();
+        assert!(rdb_fitler.has_key_filter());
+        assert!(!rdb_fitler.filter_key(b"user:1"));
+        assert!(!rdb_fitler.filter_key(b"user:\n\xff"));
+        assert!(rdb_fitler.filter_key(b"user:tmp:1"));
+        assert!(!rdb_fitler.filter_key(b"order:1a"));
+        assert!(rdb_fitler.filter_key(b"order:1"));
+        assert!(rdb_fitler.filter_key(b"order:a1"));
+        assert!(rdb_fitler.filter_key(b"order:*"));
+        assert!(!rdb_fitler.filter_key(b"cache:123"));
+        assert!(rdb_fitler.filter_key(b"cache:12a"));
+        assert!(!rdb_fitler.filter_key("中文_key".as_bytes()));
+        assert!(rdb_fitler.filter_key(b"other"));
+    }
 }
@@ -3,12 +3,16 @@ use crate::log_info;
 use crate::meta::redis::cluster_node::ClusterNode;
 use crate::meta::redis::command::cmd_encoder::CmdEncoder;
 use crate::meta::redis::command::key_parser::KeyParser;
-use crate::meta::redis::redis_object::RedisCmd;
+use crate::meta::redis::redis_object::{
+    HashObject, ListObject, ModuleObject, RedisCmd, RedisObject, RedisString, SetObject,
+    StreamObject, StringObject, ZSetEntry, ZsetObject,
+};
 use anyhow::{bail, Context};
 use redis::{Connection, ConnectionLike, Value};
 use regex::Regex;
 use std::collections::HashMap;
 use std::str::FromStr;
+use url::Url;
 
 pub struct RedisUtil {}
 
@@ -61,6 +65,22 @@ impl RedisUtil {
         slot_address_map
     }
 
+    /// url of a cluster node with the same credentials as the given url
+    pub fn get_node_url(url: &str, address: &str) -> anyhow::Result<String> {
+        let url_info = Url::parse(url)?;
+        let username = url_info.username();
+        let password = url_info.password().unwrap_or("");
+        Ok(format!("redis://{}:{}@{}", username, password, address))
+    }
+
+    /// host:port of the url, the same format as ClusterNode.address
+    pub fn get_url_address(url: &str) -> anyhow::Result<String> {
+        let url_info = Url::parse(url)?;
+        let host = url_info.host_str().unwrap_or_default();
+        let port = url_info.port().unwrap_or(6379);
+        Ok(format!("{}:{}", host, port))
+    }
+
     pub fn get_redis_version(conn: &mut redis::Connection) -> anyhow::Result<f32> {
         let cmd = RedisCmd::from_str_args(&["INFO"]);
         let value = conn.req_packed_command(&CmdEncoder::encode(&cmd))?;
@@ -89,6 +109,180 @@ impl RedisUtil {
         )}
     }
 
+    /// read the key from a live connection as RedisObject, None if the key does not exist.
+    /// values of module types are not read, only the type is checked
+    pub fn get_object(
+        conn: &mut Connection,
+        key: &RedisString,
+    ) -> anyhow::Result<Option<RedisObject>> {
+        let key_type = match Self::send_key_cmd(conn, "TYPE", key, &[])? {
+            Value::SimpleString(v) => v,
+            Value::Okay => "ok".to_string(),
+            v => bail! {Error::RedisResultError(format!(
+                "unexpected TYPE result: {:?}, key: {}",
+                v, key
+            ))},
+        };
+
+        let object = match key_type.as_str() {
+            "none" => return Ok(None),
+
+            "string" => {
+                let value = Self::send_key_cmd(conn, "GET", key, &[])?;
+                let mut obj = StringObject::new();
+                obj.key = key.clone();
+                if let Some(v) = Self::parse_result_as_bytes(value)?.pop() {
+                    obj.value = RedisString::from(v);
+                }
+                RedisObject::String(obj)
+            }
+
+            "list" => {
+                let value = Self::send_key_cmd(conn, "LRANGE", key, &["0", "-1"])?;
+                let mut obj = ListObject::new();
+                obj.key = key.clone();
+                obj.elements = Self::parse_result_as_bytes(value)?
+                    .into_iter()
+                    .map(RedisString::from)
+                    .collect();
+                RedisObject::List(obj)
+            }
+
+            "set" => {
+                let value = Self::send_key_cmd(conn, "SMEMBERS", key, &[])?;
+                let mut obj = SetObject::new();
+                obj.key = key.clone();
+                obj.elements = Self::parse_result_as_bytes(value)?
+                    .into_iter()
+                    .map(RedisString::from)
+                    .collect();
+                RedisObject::Set(obj)
+            }
+
+            "hash" => {
+                let value = Self::send_key_cmd(conn, "HGETALL", key, &[])?;
+                let mut obj = HashObject::new();
+                obj.key = key.clone();
+                let mut items = Self::parse_result_as_bytes(value)?.into_iter();
+                while let (Some(field), Some(value)) = (items.next(), items.next()) {
+                    obj.value
+                        .insert(RedisString::from(field), (RedisString::from(value), None));
+                }
+                RedisObject::Hash(obj)
+            }
+
+            "zset" => {
+                let value = Self::send_key_cmd(conn, "ZRANGE", key, &["0", "-1", "WITHSCORES"])?;
+                let mut obj = ZsetObject::new();
+                obj.key = key.clone();
+                let mut items = Self::parse_result_as_bytes(value)?.into_iter();
+                while let (Some(member), Some(score)) = (items.next(), items.next()) {
+                    obj.elements.push(ZSetEntry {
+                        member: RedisString::from(member),
+                        score: RedisString::from(score),
+                    });
+                }
+                RedisObject::Zset(obj)
+            }
+
+            "stream" => {
+                let value = Self::send_key_cmd(conn, "XRANGE", key, &["-", "+"])?;
+                let mut obj = StreamObject::new();
+                obj.key = key.clone();
+                // [[id, [field, value, ...]], ...], rebuilt as xadd commands like the rdb parser does
+                if let Value::Array(entries) = value {
+                    for entry in entries {
+                        let mut cmd = RedisCmd::new();
+                        cmd.add_str_arg("xadd");
+                        cmd.add_redis_arg(key);
+                        for arg in Self::parse_result_as_bytes(entry)? {
+                            cmd.add_arg(arg);
+                        }
+                        obj.cmds.push(cmd);
+                    }
+                }
+                RedisObject::Stream(obj)
+            }
+
+            _ => RedisObject::Module(ModuleObject::new()),
+        };
+        Ok(Some(object))
+    }
+
+    /// remaining time to live in milliseconds, -1 if no expire, -2 if the key does not exist
+    pub fn get_pttl(conn: &mut Connection, key: &RedisString) -> anyhow::Result<i64> {
+        match Self::send_key_cmd(conn, "PTTL", key, &[])? {
+            Value::Int(v) => Ok(v),
+            v => bail! {Error::RedisResultError(format!(
+                "unexpected PTTL result: {:?}, key: {}",
+                v, key
+            ))},
+        }
+    }
+
+    /// DUMP payload of the key, None if the key does not exist
+    pub fn get_dump(conn: &mut Connection, key: &RedisString) -> anyhow::Result<Option<Vec<u8>>> {
+        match Self::send_key_cmd(conn, "DUMP", key, &[])? {
+            Value::Nil => Ok(None),
+            Value::BulkString(v) => Ok(Some(v)),
+            v => bail! {Error::RedisResultError(format!(
+                "unexpected DUMP result: {:?}, key: {}",
+                v, key
+            ))},
+        }
+    }
+
+    fn send_key_cmd(
+        conn: &mut Connection,
+        name: &str,
+        key: &RedisString,
+        args: &[&str],
+    ) -> anyhow::Result<Value> {
+        let mut cmd = RedisCmd::new();
+        cmd.add_str_arg(name);
+        cmd.add_redis_arg(key);
+        for arg in args {
+            cmd.add_str_arg(arg);
+        }
+        match conn.req_packed_command(&CmdEncoder::encode(&cmd))? {
+            Value::ServerError(err) => bail! {Error::RedisResultError(format!(
+                "cmd: [{}] failed, error: {:?}",
+                cmd, err
+            ))},
+            value => Ok(value),
+        }
+    }
+
+    /// flatten the result into raw bytes, nested arrays / maps / sets are flattened in order
+    pub fn parse_result_as_bytes(value: Value) -> anyhow::Result<Vec<Vec<u8>>> {
+        let mut results = Vec::new();
+        match value {
+            Value::Nil => {}
+
+            Value::BulkString(data) => results.push(data),
+
+            Value::Array(data) | Value::Set(data) => {
+                for i in data {
+                    results.extend(Self::parse_result_as_bytes(i)?);
+                }
+            }
+
+            Value::Map(data) => {
+                for (k, v) in data {
+                    results.extend(Self::parse_result_as_bytes(k)?);
+                    results.extend(Self::parse_result_as_bytes(v)?);
+                }
+            }
+
+            value => {
+                for i in Self::parse_result_as_string(value)? {
+                    results.push(i.into_bytes());
+                }
+            }
+        }
+        Ok(results)
+    }
+
     pub fn parse_result_as_string(value: Value) -> anyhow::Result<Vec<String>> {
         let mut results = Vec::new();
         match value {
@@ -1,7 +1,12 @@
 use std::collections::HashSet;
 
+use anyhow::bail;
 use dt_common::{
-    config::{config_enums::DbType, data_marker_config::DataMarkerConfig},
+    config::{
+        config_enums::{DataMarkerStrategy, DbType},
+        data_marker_config::DataMarkerConfig,
+    },
+    error::Error,
     meta::{dt_data::DtData, redis::redis_entry::RedisEntry},
 };
 
@@ -14,6 +19,7 @@ pub struct DataMarker {
     pub dst_node: String,
     pub do_nodes: HashSet<String>,
     pub ignore_nodes: HashSet<String>,
+    pub strategy: DataMarkerStrategy,
     // mysql/pg/mongo
     pub marker_schema: String,
     pub marker_tb: String,
@@ -21,24 +27,25 @@ pub struct DataMarker {
     pub marker: String,
 
     pub data_origin_node: String,
+    // nodes the current transaction has passed through, starting with data_origin_node
+    pub data_path: Vec<String>,
     pub filter: bool,
     pub reseted: bool,
 }
 
 const DATA_ORIGIN_NODE: &str = "data_origin_node";
+const DATA_PATH: &str = "data_path";
+const PATH_DELIMITER: char = ',';
+const ORIGIN_PREFIX: &str = "ape_dts";
+const ORIGIN_DELIMITER: char = ':';
 
 impl DataMarker {
     pub fn from_config(config: &DataMarkerConfig, db_type: &DbType) -> anyhow::Result<Self> {
-        let topo_nodes: Vec<String> = config
-            .topo_nodes
-            .split(',')
-            .map(|i| i.to_string())
-            .collect();
-        let do_nodes: HashSet<String> = config.do_nodes.split(',').map(|i| i.to_string()).collect();
-        let ignore_nodes: HashSet<String> = config
-            .ignore_nodes
-            .split(',')
-            .map(|i| i.to_string())
+        let topo_nodes: Vec<String> = Self::parse_nodes(&config.topo_nodes);
+        // empty do_nodes means data from any node can be synced
+        let do_nodes: HashSet<String> = Self::parse_nodes(&config.do_nodes).into_iter().collect();
+        let ignore_nodes: HashSet<String> = Self::parse_nodes(&config.ignore_nodes)
+            .into_iter()
             .collect();
 
         let mut me = Self {
@@ -49,14 +56,21 @@ impl DataMarker {
             dst_node: config.dst_node.clone(),
             do_nodes,
             ignore_nodes,
+            strategy: config.strategy.clone(),
             ..Default::default()
         };
 
         match *db_type {
+            _ if me.use_replication_origin() => {}
             DbType::Mysql | DbType::Pg | DbType::Mongo => {
-                let marker_info: Vec<&str> = config.marker.split('.').collect();
-                me.marker_schema = marker_info[0].to_string();
-                me.marker_tb = marker_info[1].to_string();
+                let Some((marker_schema, marker_tb)) = config.marker.split_once('.') else {
+                    bail! {Error::ConfigError(format!(
+                        "[data_marker] marker should be `schema.tb`, but got: [{}]",
+                        config.marker
+                    ))}
+                };
+                me.marker_schema = marker_schema.to_string();
+                me.marker_tb = marker_tb.to_string();
             }
             _ => me.marker = config.marker.clone(),
         }
@@ -65,8 +79,83 @@ impl DataMarker {
         Ok(me)
     }
 
+    fn parse_nodes(nodes: &str) -> Vec<String> {
+        nodes
+            .split(PATH_DELIMITER)
+            .map(|i| i.trim().to_string())
+            .filter(|i| !i.is_empty())
+            .collect()
+    }
+
+    /// Path tracking is needed only when the topology has more than 2 nodes,
+    /// for two-way topologies data_origin_node is enough and old marker tables are kept.
+    pub fn track_path(&self) -> bool {
+        self.topo_nodes.len() > 2
+    }
+
+    /// The path carried by extracted items, empty if not tracked
+    pub fn get_data_path(&self) -> String {
+        if self.track_path() {
+            self.data_path.join(&PATH_DELIMITER.to_string())
+        } else {
+            String::new()
+        }
+    }
+
+    /// The path to be written into the marker of dst_node
+    pub fn get_dst_data_path(&self) -> String {
+        let mut data_path = self.data_path.clone();
+        data_path.push(self.dst_node.clone());
+        data_path.join(&PATH_DELIMITER.to_string())
+    }
+
+    pub fn set_data_path(&mut self, data_path: &str) {
+        let data_path = Self::parse_nodes(data_path);
+        if let Some(data_origin_node) = data_path.first() {
+            self.data_origin_node = data_origin_node.clone();
+            self.data_path = data_path;
+        } else {
+            // path not tracked, the data comes from data_origin_node directly
+            self.data_path = vec![self.data_origin_node.clone()];
+        }
+    }
+
+    /// Only pg supports marking the target session with replication origins
+    pub fn use_replication_origin(&self) -> bool {
+        self.db_type == DbType::Pg && self.strategy == DataMarkerStrategy::ReplicationOrigin
+    }
+
+    /// Replication origin of the target session: ape_dts:{topo_name}:{data_path}:{session_index},
+    /// an origin can be set up by only one session at a time, so each sinker uses its own.
+    pub fn get_replication_origin(&self, session_index: usize) -> String {
+        let data_path = if self.track_path() {
+            self.get_dst_data_path()
+        } else {
+            self.data_origin_node.clone()
+        };
+        [
+            ORIGIN_PREFIX.to_string(),
+            self.topo_name.clone(),
+            data_path,
+            session_index.to_string(),
+        ]
+        .join(&ORIGIN_DELIMITER.to_string())
+    }
+
+    /// Refresh by the replication origin of the current transaction,
+    /// origins not created by this topology are ignored
+    pub fn refresh_by_origin(&mut self, origin: &str) {
+        let tokens: Vec<&str> = origin.split(ORIGIN_DELIMITER).collect();
+        if tokens.len() != 4 || tokens[0] != ORIGIN_PREFIX || tokens[1] != self.topo_name {
+            return;
+        }
+        self.set_data_path(tokens[2]);
+        self.update_filter();
+    }
+
     pub fn reset(&mut self) {
         self.data_origin_node = self.src_node.clone();
+        self.data_path = vec![self.src_node.clone()];
         // by default, no filter
         self.filter = false;
         self.reseted = true;
@@ -106,19 +195,31 @@ impl DataMarker {
                         // pg, ColValue::String
                         self.data_origin_node = col_value.to_string();
                     }
+
+                    // markers written by old versions or two-way topologies have no data_path
+                    let data_path = after
+                        .get(DATA_PATH)
+                        .and_then(|i| i.to_option_string())
+                        .unwrap_or_default();
+                    self.set_data_path(&data_path);
                 }
             }
 
             DtData::Redis { entry } => {
-                self.data_origin_node = entry.cmd.get_str_arg(2);
+                // value: data_origin_node, or data_path if path tracked
+                self.set_data_path(&entry.cmd.get_str_arg(2));
             }
 
             _ => {}
         }
+        self.update_filter();
+    }
 
-        // update filter
+    fn update_filter(&mut self) {
         self.filter = self.ignore_nodes.contains(&self.data_origin_node)
-            || !self.do_nodes.contains(&self.data_origin_node);
+            || (!self.do_nodes.is_empty() && !self.do_nodes.contains(&self.data_origin_node))
+            // the transaction has already visited dst_node
+            || self.data_path.contains(&self.dst_node);
         self.reseted = false;
     }
 
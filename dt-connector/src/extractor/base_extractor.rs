@@ -67,16 +67,20 @@ impl BaseExtractor {
         self.monitor.counters.data_size += dt_data.get_data_size();
         self.monitor.try_flush(false).await;
 
-        let data_origin_node = if let Some(data_marker) = &mut self.data_marker {
-            data_marker.data_origin_node.clone()
+        let (data_origin_node, data_path) = if let Some(data_marker) = &mut self.data_marker {
+            (
+                data_marker.data_origin_node.clone(),
+                data_marker.get_data_path(),
+            )
         } else {
-            String::new()
+            (String::new(), String::new())
         };
 
         let item = DtItem {
             dt_data,
             position,
             data_origin_node,
+            data_path,
         };
         log_debug!("extracted item: {}", json!(item));
         self.buffer.push(item).await
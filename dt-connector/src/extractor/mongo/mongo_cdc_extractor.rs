@@ -6,6 +6,7 @@ use std::{
     },
 };
 
+use anyhow::bail;
 use async_trait::async_trait;
 use chrono::Utc;
 use serde_json::json;
@@ -17,8 +18,14 @@ use crate::{
 };
 use dt_common::{
     config::config_enums::DbType,
+    error::Error,
     log_error, log_info,
     meta::col_value::ColValue,
+    meta::ddl_meta::{
+        ddl_data::DdlData,
+        ddl_statement::{DdlStatement, MongoCommandStatement},
+        ddl_type::DdlType,
+    },
     meta::dt_data::DtData,
     meta::mongo::{mongo_cdc_source::MongoCdcSource, mongo_constant::MongoConstants},
     meta::position::Position,
@@ -30,7 +37,7 @@ use dt_common::{
 };
 use mongodb::{
     bson::{doc, Bson, Document, Timestamp},
-    change_stream::event::{OperationType, ResumeToken},
+    change_stream::event::{ChangeNamespace, OperationType, ResumeToken},
     options::{ChangeStreamOptions, FullDocumentBeforeChangeType, FullDocumentType, UpdateOptions},
     Client,
 };
@@ -78,6 +85,14 @@ impl Extractor for MongoCdcExtractor {
             self.source,
         );
 
+        // a sharded cluster is connected through mongos which has no oplog,
+        // its change stream merges events of all shards and excludes chunk migrations
+        if matches!(self.source, MongoCdcSource::OpLog) && self.is_mongos().await? {
+            bail! {Error::ConfigError(
+                "oplog is not available on mongos, use change_stream for sharded clusters, or tail each shard separately".into()
+            )}
+        }
+
         // start heartbeat
         self.start_heartbeat(self.base_extractor.shut_down.clone())?;
 
@@ -122,6 +137,12 @@ impl MongoCdcExtractor {
             //     "xi" insert global index key
             //     "xd" delete global index key
 
+            // writes of chunk migrations between shards, the documents were synced
+            // through the shard that they were originally written to
+            if doc.get_bool("fromMigrate").unwrap_or(false) {
+                continue;
+            }
+
             let op = Self::get_op(&doc);
             let mut row_type = RowType::Insert;
             let mut before = HashMap::new();
@@ -190,8 +211,12 @@ impl MongoCdcExtractor {
                         ColValue::MongoDoc(o.unwrap().as_document().unwrap().clone()),
                     );
                 }
-                // TODO, DDL
                 "c" | "xi" | "xd" => {
+                    if let Some((ddl_data, position)) = Self::parse_oplog_command(&doc) {
+                        self.push_ddl_to_buf(ddl_data, position).await?;
+                        continue;
+                    }
+
                     // after version 7.0, the oplog generated by deleteMany is "c" instead of "d"
                     let data = Self::extract_oplog_delete_many(&doc);
                     for (row_data, position) in data {
@@ -277,7 +302,7 @@ impl MongoCdcExtractor {
             let op = Self::get_op(item);
             let ns = item.get("ns");
 
-            if op.as_str() != "d" {
+            if op.as_str() != "d" || item.get_bool("fromMigrate").unwrap_or(false) {
                 continue;
             }
 
@@ -320,14 +345,119 @@ impl MongoCdcExtractor {
         };
         let after = if after.is_empty() { None } else { Some(after) };
 
-        // get ts for position
-        let position = Position::MongoCdc {
+        let position = Self::build_oplog_position(ts);
+        let row_data = RowData::new(db, tb, row_type, before, after);
+        (row_data, position)
+    }
+
+    fn build_oplog_position(ts: Timestamp) -> Position {
+        Position::MongoCdc {
             resume_token: String::new(),
             operation_time: ts.time,
             timestamp: Position::format_timestamp_millis(ts.time as i64 * 1000),
+        }
+    }
+
+    fn parse_oplog_command(doc: &Document) -> Option<(DdlData, Position)> {
+        // Document({"op": String("c"), "ns": String("test_db_1.$cmd"), "o": Document({"create": String("tb_1"), "idIndex": Document({...})}), "ts": Timestamp {...}, ...})
+        let db = doc.get_str("ns").ok()?.strip_suffix(".$cmd")?;
+        let command = doc.get_document("o").ok()?;
+        let ts = doc.get_timestamp("ts").ok()?;
+        let ddl_data = Self::build_command_ddl(db, command)?;
+        Some((ddl_data, Self::build_oplog_position(ts)))
+    }
+
+    /// build DdlData from a command in oplog format, None if the command is not a DDL
+    fn build_command_ddl(db: &str, command: &Document) -> Option<DdlData> {
+        let (name, value) = command.iter().next()?;
+        let (mut db, mut coll) = (
+            db.to_string(),
+            value.as_str().unwrap_or_default().to_string(),
+        );
+        let (mut new_db, mut new_coll) = (String::new(), String::new());
+
+        let (ddl_type, command) = match name.as_str() {
+            "create" => {
+                let mut command = command.clone();
+                // _id index is created along with the collection
+                command.remove("idIndex");
+                (DdlType::CreateTable, command)
+            }
+
+            "drop" => (DdlType::DropTable, doc! {"drop": &coll}),
+
+            "renameCollection" => {
+                // both are full namespaces: db.coll, the command is run on admin
+                (db, coll) = Self::split_ns(value.as_str()?);
+                (new_db, new_coll) = Self::split_ns(command.get_str("to").ok()?);
+                // in oplog, dropTarget is the uuid of the dropped target collection
+                let drop_target =
+                    !matches!(command.get("dropTarget"), None | Some(Bson::Boolean(false)));
+                let command = doc! {
+                    "renameCollection": value.as_str()?,
+                    "to": command.get_str("to").ok()?,
+                    "dropTarget": drop_target,
+                };
+                (DdlType::RenameTable, command)
+            }
+
+            "createIndexes" => {
+                let indexes = if let Ok(indexes) = command.get_array("indexes") {
+                    indexes.clone()
+                } else {
+                    // before 4.4, each index is logged with its spec inline
+                    let mut spec = command.clone();
+                    spec.remove("createIndexes");
+                    vec![Bson::Document(spec)]
+                };
+                let command = doc! {"createIndexes": &coll, "indexes": indexes};
+                (DdlType::CreateIndex, command)
+            }
+
+            // since 4.4, indexes are built in two phases: startIndexBuild and commitIndexBuild
+            "commitIndexBuild" => {
+                let indexes = command.get_array("indexes").ok()?.clone();
+                let command = doc! {"createIndexes": &coll, "indexes": indexes};
+                (DdlType::CreateIndex, command)
+            }
+
+            "dropIndexes" | "deleteIndexes" => {
+                let index = command.get("index")?.clone();
+                let command = doc! {"dropIndexes": &coll, "index": index};
+                (DdlType::DropIndex, command)
+            }
+
+            "dropDatabase" => {
+                coll = String::new();
+                (DdlType::DropDatabase, doc! {"dropDatabase": 1})
+            }
+
+            _ => return None,
         };
-        let row_data = RowData::new(db, tb, row_type, before, after);
-        (row_data, position)
+
+        let command = Bson::Document(command).into_relaxed_extjson().to_string();
+        let statement = MongoCommandStatement {
+            db: db.clone(),
+            coll,
+            new_db,
+            new_coll,
+            command: command.clone(),
+        };
+        Some(DdlData {
+            default_schema: db,
+            query: command,
+            ddl_type,
+            db_type: DbType::Mongo,
+            statement: DdlStatement::MongoCommand(statement),
+        })
+    }
+
+    fn split_ns(ns: &str) -> (String, String) {
+        if let Some((db, coll)) = ns.split_once('.') {
+            (db.into(), coll.into())
+        } else {
+            (ns.into(), String::new())
+        }
     }
 
     async fn extract_change_stream(&mut self) -> anyhow::Result<()> {
@@ -341,11 +471,19 @@ impl MongoCdcExtractor {
         // refer: https://www.mongodb.com/docs/manual/changeStreams/
         // Starting in MongoDB 6.0, you can use change stream events to output the version of
         // a document before and after changes (the document pre- and post-images)
+        // create / createIndexes / dropIndexes are expanded events (since 6.0),
+        // only ask for them when DDL is needed, older servers reject the option
+        let show_expanded_events = if self.filter.filter_all_ddl() {
+            None
+        } else {
+            Some(true)
+        };
         let stream_options = ChangeStreamOptions::builder()
             .start_at_operation_time(start_timestamp)
             .start_after(resume_token)
             .full_document(Some(FullDocumentType::UpdateLookup))
             .full_document_before_change(Some(FullDocumentBeforeChangeType::WhenAvailable))
+            .show_expanded_events(show_expanded_events)
             .build();
 
         let mut change_stream = self.mongo_client.watch(None, stream_options).await?;
@@ -411,7 +549,26 @@ impl MongoCdcExtractor {
                         }
                     }
 
-                    // TODO, heartbeat and DDL
+                    OperationType::Drop
+                    | OperationType::Rename
+                    | OperationType::DropDatabase
+                    | OperationType::Other(_) => {
+                        let command = Self::build_change_stream_command(
+                            &doc.operation_type,
+                            &db,
+                            &tb,
+                            &doc.to,
+                            &doc.operation_description,
+                        );
+                        if let Some(ddl_data) =
+                            command.and_then(|command| Self::build_command_ddl(&db, &command))
+                        {
+                            self.push_ddl_to_buf(ddl_data, position).await?;
+                        }
+                        continue;
+                    }
+
+                    // TODO, heartbeat
                     _ => {
                         continue;
                     }
@@ -423,6 +580,89 @@ impl MongoCdcExtractor {
         }
     }
 
+    /// convert a DDL change event to the command in oplog format
+    fn build_change_stream_command(
+        operation_type: &OperationType,
+        db: &str,
+        coll: &str,
+        to: &Option<ChangeNamespace>,
+        description: &Option<Document>,
+    ) -> Option<Document> {
+        match operation_type {
+            OperationType::Drop => Some(doc! {"drop": coll}),
+
+            OperationType::Rename => {
+                let to = to.as_ref()?;
+                let drop_target = description
+                    .as_ref()
+                    .map_or(false, |d| d.contains_key("dropTarget"));
+                Some(doc! {
+                    "renameCollection": format!("{}.{}", db, coll),
+                    "to": format!("{}.{}", to.db, to.coll.as_deref().unwrap_or_default()),
+                    "dropTarget": drop_target,
+                })
+            }
+
+            OperationType::DropDatabase => Some(doc! {"dropDatabase": 1}),
+
+            OperationType::Other(op) => {
+                let description = description.as_ref()?;
+                match op.as_str() {
+                    "create" => {
+                        let mut command = doc! {"create": coll};
+                        for (k, v) in description.iter() {
+                            command.insert(k, v.clone());
+                        }
+                        Some(command)
+                    }
+
+                    "createIndexes" => {
+                        let indexes = description.get_array("indexes").ok()?;
+                        Some(doc! {"createIndexes": coll, "indexes": indexes.clone()})
+                    }
+
+                    "dropIndexes" => {
+                        let names: Vec<Bson> = description
+                            .get_array("indexes")
+                            .ok()?
+                            .iter()
+                            .filter_map(|i| i.as_document()?.get("name").cloned())
+                            .collect();
+                        Some(doc! {"dropIndexes": coll, "index": names})
+                    }
+
+                    _ => None,
+                }
+            }
+
+            _ => None,
+        }
+    }
+
+    async fn push_ddl_to_buf(
+        &mut self,
+        ddl_data: DdlData,
+        position: Position,
+    ) -> anyhow::Result<()> {
+        let (db, coll) = ddl_data.get_schema_tb();
+        if SYSTEM_DBS.contains(&db.as_str())
+            || coll.starts_with("system.")
+            || self.filter.filter_ddl(&db, &coll, &ddl_data.ddl_type)
+        {
+            return Ok(());
+        }
+        self.base_extractor.push_ddl(ddl_data, position).await
+    }
+
+    async fn is_mongos(&self) -> anyhow::Result<bool> {
+        let result = self
+            .mongo_client
+            .database("admin")
+            .run_command(doc! {"isMaster": 1}, None)
+            .await?;
+        Ok(result.get_str("msg").map_or(false, |msg| msg == "isdbgrid"))
+    }
+
     async fn push_row_to_buf(
         &mut self,
         row_data: RowData,
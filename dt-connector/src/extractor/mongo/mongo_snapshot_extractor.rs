@@ -1,29 +1,40 @@
+use std::{cmp, collections::HashMap, sync::Arc};
+
 use async_trait::async_trait;
 use dt_common::meta::{
     col_value::ColValue,
-    mongo::{mongo_constant::MongoConstants, mongo_key::MongoKey},
+    dt_data::{DtData, DtItem},
+    dt_queue::DtQueue,
+    mongo::{mongo_constant::MongoConstants, mongo_id_range::MongoIdRange, mongo_key::MongoKey},
     position::Position,
     row_data::RowData,
     row_type::RowType,
 };
-use dt_common::{config::config_enums::DbType, log_info};
+use dt_common::{config::config_enums::DbType, log_debug, log_info, log_warn};
 use mongodb::{
-    bson::{doc, oid::ObjectId, Bson, Document},
-    options::FindOptions,
-    Client,
+    bson::{doc, Bson, Document},
+    options::{AggregateOptions, FindOptions},
+    Client, Collection,
 };
-use std::collections::HashMap;
+use serde_json::json;
+use tokio::task::JoinHandle;
 
 use crate::{
     extractor::{base_extractor::BaseExtractor, resumer::snapshot_resumer::SnapshotResumer},
+    rdb_router::RdbRouter,
     Extractor,
 };
 
+// sample more ids than needed so split ids are closer to the real distribution
+const SAMPLE_FACTOR: usize = 100;
+
 pub struct MongoSnapshotExtractor {
     pub base_extractor: BaseExtractor,
     pub resumer: SnapshotResumer,
     pub db: String,
     pub tb: String,
+    pub parallel_size: usize,
+    pub batch_size: usize,
     pub mongo_client: Client,
 }
 
@@ -31,9 +42,11 @@ pub struct MongoSnapshotExtractor {
 impl Extractor for MongoSnapshotExtractor {
     async fn extract(&mut self) -> anyhow::Result<()> {
         log_info!(
-            "MongoSnapshotExtractor starts, schema: {}, tb: {}",
+            "MongoSnapshotExtractor starts, schema: {}, tb: {}, batch_size: {}, parallel_size: {}",
             self.db,
-            self.tb
+            self.tb,
+            self.batch_size,
+            self.parallel_size
         );
         self.extract_internal().await?;
         self.base_extractor.wait_task_finish().await
@@ -49,15 +62,67 @@ impl MongoSnapshotExtractor {
     pub async fn extract_internal(&mut self) -> anyhow::Result<()> {
         log_info!("start extracting data from {}.{}", self.db, self.tb);
 
-        let filter = if let Some(resume_value) =
+        let collection = self
+            .mongo_client
+            .database(&self.db)
+            .collection::<Document>(&self.tb);
+
+        let parallel_extract = self.parallel_size > 1;
+        let resume_value =
             self.resumer
-                .get_resume_value(&self.db, &self.tb, MongoConstants::ID, false)
-        {
-            let start_id = ObjectId::parse_str(resume_value)?;
-            log_info!("start_id: {}", start_id.to_string());
-            Some(doc! {MongoConstants::ID: {"$gt": start_id}})
-        } else {
-            None
+                .get_resume_value(&self.db, &self.tb, MongoConstants::ID, parallel_extract);
+
+        let extracted_count = match resume_value {
+            Some(value) if MongoIdRange::is_ranges_str(&value) => {
+                let ranges = MongoIdRange::ranges_from_str(&value)?;
+                log_info!(
+                    "resume parallel extracting, parallel_size: {}, ranges: {}",
+                    self.parallel_size,
+                    value
+                );
+                self.parallel_extract_by_batch(&collection, ranges).await?
+            }
+
+            Some(value) => {
+                let start_id = MongoIdRange::id_from_str(&value)?;
+                log_info!("start_id: {}", start_id);
+                self.extract_all(&collection, Some(start_id)).await?
+            }
+
+            None => {
+                let count = collection.estimated_document_count(None).await? as usize;
+                if parallel_extract && count > self.batch_size {
+                    let split_ids = self.get_split_ids(&collection).await?;
+                    let ranges = MongoIdRange::from_split_ids(&split_ids);
+                    log_info!(
+                        "parallel extracting, parallel_size: {}, ranges: {}",
+                        self.parallel_size,
+                        MongoIdRange::ranges_to_str(&ranges)
+                    );
+                    self.parallel_extract_by_batch(&collection, ranges).await?
+                } else {
+                    self.extract_all(&collection, None).await?
+                }
+            }
+        };
+
+        log_info!(
+            "end extracting data from {}.{}, all count: {}",
+            self.db,
+            self.tb,
+            extracted_count
+        );
+        Ok(())
+    }
+
+    async fn extract_all(
+        &mut self,
+        collection: &Collection<Document>,
+        start_id: Option<Bson>,
+    ) -> anyhow::Result<usize> {
+        let range = MongoIdRange {
+            gt: start_id,
+            lte: None,
         };
 
         // order by asc
@@ -65,57 +130,199 @@ impl MongoSnapshotExtractor {
             .sort(doc! {MongoConstants::ID: 1})
             .build();
 
-        let collection = self
-            .mongo_client
-            .database(&self.db)
-            .collection::<Document>(&self.tb);
-        let mut cursor = collection.find(filter, find_options).await?;
+        let mut cursor = collection.find(range.to_filter(), find_options).await?;
         while cursor.advance().await? {
             let doc = cursor.deserialize_current()?;
-            let object_id = Self::get_object_id(&doc);
-
-            let mut after = HashMap::new();
-            let id: String = if let Some(key) = MongoKey::from_doc(&doc) {
-                key.to_string()
+            let position = if let Some(id) = doc.get(MongoConstants::ID) {
+                Self::build_position(&self.db, &self.tb, MongoIdRange::id_to_str(id))
             } else {
-                String::new()
-            };
-            after.insert(MongoConstants::ID.to_string(), ColValue::String(id));
-            after.insert(MongoConstants::DOC.to_string(), ColValue::MongoDoc(doc));
-            let row_data = RowData::new(
-                self.db.clone(),
-                self.tb.clone(),
-                RowType::Insert,
-                None,
-                Some(after),
-            );
-            let position = Position::RdbSnapshot {
-                db_type: DbType::Mongo.to_string(),
-                schema: self.db.clone(),
-                tb: self.tb.clone(),
-                order_col: MongoConstants::ID.into(),
-                value: object_id,
+                Position::None
             };
-
+            let row_data = Self::build_row_data(&self.db, &self.tb, doc);
             self.base_extractor.push_row(row_data, position).await?;
         }
+        Ok(self.base_extractor.monitor.counters.record_count)
+    }
 
-        log_info!(
-            "end extracting data from {}.{}, all count: {}",
-            self.db,
-            self.tb,
-            self.base_extractor.monitor.counters.record_count
-        );
-        Ok(())
+    /// extract ranges in rounds, each round pulls at most batch_size documents from
+    /// every unfinished range in parallel, the unfinished ranges are checkpointed between rounds
+    async fn parallel_extract_by_batch(
+        &mut self,
+        collection: &Collection<Document>,
+        mut ranges: Vec<MongoIdRange>,
+    ) -> anyhow::Result<usize> {
+        let mut all_extracted_count = 0;
+        let batch_size = cmp::max(self.batch_size / self.parallel_size, 1);
+        let router = Arc::new(self.base_extractor.router.clone());
+
+        while !ranges.is_empty() {
+            self.send_checkpoint_position(&ranges).await?;
+
+            let mut futures = Vec::new();
+            for range in ranges.iter() {
+                let collection = collection.clone();
+                let buffer = self.base_extractor.buffer.clone();
+                let router = router.clone();
+                let (db, tb) = (self.db.clone(), self.tb.clone());
+                let filter = range.to_filter();
+
+                let future: JoinHandle<anyhow::Result<(Option<Bson>, usize)>> =
+                    tokio::spawn(async move {
+                        let find_options = FindOptions::builder()
+                            .sort(doc! {MongoConstants::ID: 1})
+                            .limit(batch_size as i64)
+                            .build();
+                        let mut cursor = collection.find(filter, find_options).await?;
+
+                        let mut last_id = None;
+                        let mut slice_count = 0;
+                        while cursor.advance().await? {
+                            let doc = cursor.deserialize_current()?;
+                            last_id = doc.get(MongoConstants::ID).cloned();
+                            let row_data = Self::build_row_data(&db, &tb, doc);
+                            Self::push_row(&buffer, &router, row_data, Position::None).await?;
+                            slice_count += 1;
+                        }
+                        Ok((last_id, slice_count))
+                    });
+                futures.push(future);
+            }
+
+            let mut next_ranges = Vec::new();
+            for (range, future) in ranges.into_iter().zip(futures) {
+                let (last_id, slice_count) = future.await??;
+                all_extracted_count += slice_count;
+                // range not finished
+                if slice_count == batch_size {
+                    next_ranges.push(MongoIdRange {
+                        gt: last_id,
+                        lte: range.lte,
+                    });
+                }
+            }
+            ranges = next_ranges;
+        }
+
+        Ok(all_extracted_count)
     }
 
-    fn get_object_id(doc: &Document) -> String {
-        if let Some(id) = doc.get(MongoConstants::ID) {
-            match id {
-                Bson::ObjectId(v) => return v.to_string(),
-                _ => return String::new(),
+    /// parallel_size - 1 ids splitting the collection into ranges of similar sizes,
+    /// by splitVector if permitted, otherwise by $sample
+    async fn get_split_ids(&self, collection: &Collection<Document>) -> anyhow::Result<Vec<Bson>> {
+        let mut ids = match self.get_split_ids_by_split_vector().await {
+            Ok(ids) if !ids.is_empty() => ids,
+            Ok(_) => self.get_split_ids_by_sample(collection).await?,
+            Err(err) => {
+                log_warn!(
+                    "splitVector failed, split {}.{} by $sample instead, error: {}",
+                    self.db,
+                    self.tb,
+                    err
+                );
+                self.get_split_ids_by_sample(collection).await?
             }
+        };
+
+        if ids.len() >= self.parallel_size {
+            let step = ids.len() as f64 / self.parallel_size as f64;
+            ids = (1..self.parallel_size)
+                .map(|i| ids[(i as f64 * step) as usize].clone())
+                .collect();
         }
-        String::new()
+        ids.dedup();
+        Ok(ids)
+    }
+
+    async fn get_split_ids_by_split_vector(&self) -> anyhow::Result<Vec<Bson>> {
+        let db = self.mongo_client.database(&self.db);
+        let stats = db.run_command(doc! {"collStats": &self.tb}, None).await?;
+        let size = match stats.get("size") {
+            Some(Bson::Int32(v)) => *v as i64,
+            Some(Bson::Int64(v)) => *v,
+            Some(Bson::Double(v)) => *v as i64,
+            _ => 0,
+        };
+
+        let command = doc! {
+            "splitVector": format!("{}.{}", self.db, self.tb),
+            "keyPattern": {MongoConstants::ID: 1},
+            "maxChunkSizeBytes": cmp::max(size / self.parallel_size as i64, 1),
+        };
+        let result = db.run_command(command, None).await?;
+        let ids = result
+            .get_array("splitKeys")?
+            .iter()
+            .filter_map(|i| i.as_document()?.get(MongoConstants::ID).cloned())
+            .collect();
+        Ok(ids)
+    }
+
+    async fn get_split_ids_by_sample(
+        &self,
+        collection: &Collection<Document>,
+    ) -> anyhow::Result<Vec<Bson>> {
+        let pipeline = vec![
+            doc! {"$sample": {"size": (self.parallel_size * SAMPLE_FACTOR) as i64}},
+            doc! {"$project": {MongoConstants::ID: 1}},
+            doc! {"$sort": {MongoConstants::ID: 1}},
+        ];
+        let options = AggregateOptions::builder().allow_disk_use(true).build();
+        let mut cursor = collection.aggregate(pipeline, options).await?;
+
+        let mut ids = Vec::new();
+        while cursor.advance().await? {
+            let doc = cursor.deserialize_current()?;
+            if let Some(id) = doc.get(MongoConstants::ID) {
+                ids.push(id.clone());
+            }
+        }
+        Ok(ids)
+    }
+
+    fn build_row_data(db: &str, tb: &str, doc: Document) -> RowData {
+        let mut after = HashMap::new();
+        let id: String = if let Some(key) = MongoKey::from_doc(&doc) {
+            key.to_string()
+        } else {
+            String::new()
+        };
+        after.insert(MongoConstants::ID.to_string(), ColValue::String(id));
+        after.insert(MongoConstants::DOC.to_string(), ColValue::MongoDoc(doc));
+        RowData::new(db.into(), tb.into(), RowType::Insert, None, Some(after))
+    }
+
+    async fn push_row(
+        buffer: &Arc<DtQueue>,
+        router: &Arc<RdbRouter>,
+        row_data: RowData,
+        position: Position,
+    ) -> anyhow::Result<()> {
+        let row_data = router.route_row(row_data);
+        let dt_data = DtData::Dml { row_data };
+        let item = DtItem {
+            dt_data,
+            position,
+            data_origin_node: String::new(),
+            data_path: String::new(),
+        };
+        log_debug!("extracted item: {}", json!(item));
+        buffer.push(item).await
+    }
+
+    fn build_position(db: &str, tb: &str, value: String) -> Position {
+        Position::RdbSnapshot {
+            db_type: DbType::Mongo.to_string(),
+            schema: db.into(),
+            tb: tb.into(),
+            order_col: MongoConstants::ID.into(),
+            value,
+        }
+    }
+
+    async fn send_checkpoint_position(&mut self, ranges: &[MongoIdRange]) -> anyhow::Result<()> {
+        let value = MongoIdRange::ranges_to_str(ranges);
+        let position = Self::build_position(&self.db, &self.tb, value);
+        let commit = DtData::Commit { xid: String::new() };
+        self.base_extractor.push_dt_data(commit, position).await
     }
 }
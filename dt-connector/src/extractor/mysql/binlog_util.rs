@@ -1,7 +1,27 @@
-use dt_common::{log_info, utils::time_util::TimeUtil};
+use std::{
+    collections::HashMap,
+    fs,
+    fs::File,
+    io::{BufReader, Read},
+    path::PathBuf,
+    time::Duration,
+};
+
+use anyhow::bail;
+use dt_common::{error::Error, log_info, log_warn, utils::time_util::TimeUtil};
 use futures::TryStreamExt;
-use mysql_binlog_connector_rust::{binlog_client::BinlogClient, event::event_data::EventData};
-use sqlx::{MySql, Pool, Row};
+use mysql_binlog_connector_rust::{
+    binlog_client::BinlogClient, binlog_parser::BinlogParser, event::event_data::EventData,
+};
+use sqlx::{mysql::MySqlPoolOptions, MySql, Pool, Row};
+use tokio::time::Instant;
+
+const BINLOG_MAGIC: [u8; 4] = [0xfe, 0x62, 0x69, 0x6e];
+const FORMAT_DESCRIPTION_EVENT: u8 = 15;
+const EVENT_HEADER_LENGTH: usize = 19;
+const CHECKSUM_ALG_LENGTH: usize = 1;
+const CHECKSUM_LENGTH: usize = 4;
+const CHECKSUM_ALG_OFF: u8 = 0;
 
 pub struct BinlogUtil {}
 
@@ -114,4 +134,311 @@ impl BinlogUtil {
         // the timestamp in binlog is since the epoch in UTC, no matter what @@global.time_zone in mysql
         Ok(timestamp)
     }
+
+    /// Binlog files in binlog_dir sorted by their numeric suffixes, like: mysql-bin.999999,
+    /// mysql-bin.1000000, the index file (mysql-bin.index) and other files are ignored
+    pub fn get_local_binlogs(binlog_dir: &str) -> anyhow::Result<Vec<String>> {
+        let mut binlogs = Vec::new();
+        for entry in fs::read_dir(binlog_dir)? {
+            let path = entry?.path();
+            let is_binlog = path.is_file()
+                && path.extension().is_some_and(|i| {
+                    !i.is_empty() && i.to_string_lossy().chars().all(|c| c.is_ascii_digit())
+                });
+            if is_binlog {
+                binlogs.push(path.file_name().unwrap().to_string_lossy().to_string());
+            }
+        }
+        binlogs.sort_by_key(|i| (Self::get_binlog_index(i), i.clone()));
+        Ok(binlogs)
+    }
+
+    /// The numeric suffix of binlog name, 0 if absent
+    pub fn get_binlog_index(binlog: &str) -> u64 {
+        binlog
+            .rsplit_once('.')
+            .and_then(|(_, suffix)| suffix.parse().ok())
+            .unwrap_or(0)
+    }
+
+    pub fn find_last_local_binlog_before_timestamp(
+        start_timestamp: u32,
+        binlog_dir: &str,
+    ) -> anyhow::Result<String> {
+        let binlogs = Self::get_local_binlogs(binlog_dir)?;
+        log_info!(
+            "finding the last local binlog before start_time: {}",
+            TimeUtil::timestamp_to_str(start_timestamp)?
+        );
+
+        let mut found = String::new();
+        for binlog in binlogs {
+            let binlog_start_timestamp =
+                Self::get_local_binlog_start_timestamp(binlog_dir, &binlog)?;
+            if binlog_start_timestamp > start_timestamp && !found.is_empty() {
+                break;
+            }
+            found = binlog;
+        }
+        log_info!("found local binlog: {}", found);
+        Ok(found)
+    }
+
+    /// Open a local binlog file, the returned reader is positioned after FormatDescriptionEvent
+    pub fn open_local_binlog(
+        binlog_dir: &str,
+        binlog: &str,
+    ) -> anyhow::Result<(BinlogParser, BufReader<File>, u64)> {
+        let path = PathBuf::from(binlog_dir).join(binlog);
+        let file = File::open(&path)?;
+        let file_len = file.metadata()?.len();
+        let mut reader = BufReader::new(file);
+        let (_, checksum_length) = Self::read_format_description(&mut reader)?;
+        let parser = BinlogParser {
+            checksum_length,
+            table_map_event_by_table_id: HashMap::new(),
+        };
+        Ok((parser, reader, file_len))
+    }
+
+    fn get_local_binlog_start_timestamp(binlog_dir: &str, binlog: &str) -> anyhow::Result<u32> {
+        let path = PathBuf::from(binlog_dir).join(binlog);
+        let mut reader = BufReader::new(File::open(&path)?);
+        let (timestamp, _) = Self::read_format_description(&mut reader)?;
+        Ok(timestamp)
+    }
+
+    /// A binlog file starts with the magic number and FormatDescriptionEvent,
+    /// returns the timestamp of the event and the checksum length of the following events
+    fn read_format_description<R: Read>(reader: &mut R) -> anyhow::Result<(u32, u8)> {
+        let mut magic = [0u8; 4];
+        reader.read_exact(&mut magic)?;
+        if magic != BINLOG_MAGIC {
+            bail! {Error::ExtractorError("not a binlog file, magic number mismatch".into())}
+        }
+
+        // header: timestamp (4), type (1), server_id (4), event_length (4), next_position (4), flags (2)
+        let mut header = [0u8; EVENT_HEADER_LENGTH];
+        reader.read_exact(&mut header)?;
+        let timestamp = u32::from_le_bytes(header[0..4].try_into()?);
+        let event_length = u32::from_le_bytes(header[9..13].try_into()?) as usize;
+        if header[4] != FORMAT_DESCRIPTION_EVENT
+            || event_length < EVENT_HEADER_LENGTH + CHECKSUM_ALG_LENGTH + CHECKSUM_LENGTH
+        {
+            bail! {Error::ExtractorError(
+                "the first event in binlog file is not FormatDescriptionEvent".into()
+            )}
+        }
+
+        // FormatDescriptionEvent always ends with the checksum algorithm and the checksum,
+        // even if checksum is off (binlog_checksum=NONE) for the following events
+        let mut body = vec![0u8; event_length - EVENT_HEADER_LENGTH];
+        reader.read_exact(&mut body)?;
+        let checksum_length = match body[body.len() - CHECKSUM_LENGTH - CHECKSUM_ALG_LENGTH] {
+            CHECKSUM_ALG_OFF => 0,
+            _ => CHECKSUM_LENGTH as u8,
+        };
+        Ok((timestamp, checksum_length))
+    }
+
+    /// Whether the gtid (uuid:gno) is in the gtid_set, like: uuid_1:1-10:12,uuid_2:1-5
+    pub fn gtid_set_contains(gtid_set: &str, gtid: &str) -> bool {
+        let Some((uuid, gno)) = gtid.trim().rsplit_once(':') else {
+            return false;
+        };
+        let Ok(gno) = gno.parse::<u64>() else {
+            return false;
+        };
+
+        for uuid_set in gtid_set.split(',') {
+            let mut parts = uuid_set.trim().split(':');
+            if !parts.next().is_some_and(|i| i.eq_ignore_ascii_case(uuid)) {
+                continue;
+            }
+            for interval in parts {
+                let (start, end) = interval.split_once('-').unwrap_or((interval, interval));
+                if let (Ok(start), Ok(end)) = (start.parse::<u64>(), end.parse::<u64>()) {
+                    if start <= gno && gno <= end {
+                        return true;
+                    }
+                }
+            }
+        }
+        false
+    }
+
+    /// Find the writable instance in candidate urls after the primary failed, and check it
+    /// has all committed gtids and the binlogs after them are not purged
+    pub async fn find_failover_primary(
+        urls: &[String],
+        committed_gtid_set: &str,
+        timeout_secs: u64,
+    ) -> anyhow::Result<(String, Pool<MySql>)> {
+        let start_time = Instant::now();
+        loop {
+            for url in urls.iter() {
+                let conn_pool = match MySqlPoolOptions::new()
+                    .max_connections(2)
+                    .acquire_timeout(Duration::from_secs(5))
+                    .connect(url)
+                    .await
+                {
+                    Ok(conn_pool) => conn_pool,
+                    Err(err) => {
+                        log_warn!(
+                            "failover candidate not available, url: {}, error: {}",
+                            url,
+                            err
+                        );
+                        continue;
+                    }
+                };
+
+                let (read_only, has_committed, purged_committed) =
+                    match Self::get_failover_status(&conn_pool, committed_gtid_set).await {
+                        Ok(status) => status,
+                        Err(err) => {
+                            log_warn!(
+                                "failed to check failover candidate, url: {}, error: {}",
+                                url,
+                                err
+                            );
+                            conn_pool.close().await;
+                            continue;
+                        }
+                    };
+
+                if read_only != 0 {
+                    conn_pool.close().await;
+                    continue;
+                }
+
+                if has_committed == 0 {
+                    conn_pool.close().await;
+                    bail! {Error::ExtractorError(format!(
+                        "new primary: {} lost some committed gtids: {}, refuse to failover",
+                        url, committed_gtid_set
+                    ))}
+                }
+
+                if purged_committed == 0 {
+                    conn_pool.close().await;
+                    bail! {Error::ExtractorError(format!(
+                        "new primary: {} has purged binlogs after committed gtids: {}, refuse to failover",
+                        url, committed_gtid_set
+                    ))}
+                }
+
+                return Ok((url.to_owned(), conn_pool));
+            }
+
+            if start_time.elapsed().as_secs() >= timeout_secs {
+                bail! {Error::ExtractorError(format!(
+                    "no primary found in failover urls within {} secs",
+                    timeout_secs
+                ))}
+            }
+            TimeUtil::sleep_millis(1000).await;
+        }
+    }
+
+    /// (read_only, whether committed gtids are executed, whether binlogs after them are kept)
+    async fn get_failover_status(
+        conn_pool: &Pool<MySql>,
+        committed_gtid_set: &str,
+    ) -> anyhow::Result<(i64, i64, i64)> {
+        let sql = "SELECT CAST(@@GLOBAL.read_only AS SIGNED),
+            CAST(GTID_SUBSET(?, @@GLOBAL.gtid_executed) AS SIGNED),
+            CAST(GTID_SUBSET(@@GLOBAL.gtid_purged, ?) AS SIGNED)";
+        let row = sqlx::query(sql)
+            .bind(committed_gtid_set)
+            .bind(committed_gtid_set)
+            .fetch_one(conn_pool)
+            .await?;
+        Ok((row.try_get(0)?, row.try_get(1)?, row.try_get(2)?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Seek;
+
+    use super::*;
+
+    const BINLOG_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/mysql_binlogs");
+
+    #[test]
+    fn test_get_local_binlogs() {
+        // sorted by numeric suffix instead of name, the index file is ignored
+        let binlogs = BinlogUtil::get_local_binlogs(BINLOG_DIR).unwrap();
+        assert_eq!(binlogs, vec!["mysql-bin.999999", "mysql-bin.1000000"]);
+
+        assert_eq!(BinlogUtil::get_binlog_index("mysql-bin.1000000"), 1000000);
+        assert_eq!(BinlogUtil::get_binlog_index(""), 0);
+    }
+
+    #[test]
+    fn test_open_local_binlog() {
+        // mysql-bin.999999: binlog_checksum=CRC32, mysql-bin.1000000: binlog_checksum=NONE
+        for (binlog, checksum_length, expect_xid) in
+            [("mysql-bin.999999", 4, 100), ("mysql-bin.1000000", 0, 200)]
+        {
+            let (mut parser, mut reader, file_len) =
+                BinlogUtil::open_local_binlog(BINLOG_DIR, binlog).unwrap();
+            assert_eq!(parser.checksum_length, checksum_length);
+
+            let (header, data) = parser.next(&mut reader).unwrap();
+            let EventData::Xid(xid) = data else {
+                panic!("unexpected event: {:?}", data);
+            };
+            assert_eq!(xid.xid, expect_xid);
+            assert_eq!(header.next_event_position as u64, file_len);
+            assert_eq!(reader.stream_position().unwrap(), file_len);
+        }
+    }
+
+    #[test]
+    fn test_find_last_local_binlog_before_timestamp() {
+        // binlog start timestamps: mysql-bin.999999: 1700000000, mysql-bin.1000000: 1700000100
+        for (start_timestamp, expect_binlog) in [
+            (1600000000, "mysql-bin.999999"),
+            (1700000050, "mysql-bin.999999"),
+            (1700000200, "mysql-bin.1000000"),
+        ] {
+            let binlog =
+                BinlogUtil::find_last_local_binlog_before_timestamp(start_timestamp, BINLOG_DIR)
+                    .unwrap();
+            assert_eq!(binlog, expect_binlog);
+        }
+    }
+
+    #[test]
+    fn test_gtid_set_contains() {
+        let gtid_set =
+            "3e11fa47-71ca-11e1-9e33-c80aa9429562:1-5:7,\n4e11fa47-71ca-11e1-9e33-c80aa9429562:1";
+        assert!(BinlogUtil::gtid_set_contains(
+            gtid_set,
+            "3e11fa47-71ca-11e1-9e33-c80aa9429562:1"
+        ));
+        assert!(BinlogUtil::gtid_set_contains(
+            gtid_set,
+            "3E11FA47-71CA-11E1-9E33-C80AA9429562:7"
+        ));
+        assert!(BinlogUtil::gtid_set_contains(
+            gtid_set,
+            "4e11fa47-71ca-11e1-9e33-c80aa9429562:1"
+        ));
+        assert!(!BinlogUtil::gtid_set_contains(
+            gtid_set,
+            "3e11fa47-71ca-11e1-9e33-c80aa9429562:6"
+        ));
+        assert!(!BinlogUtil::gtid_set_contains(
+            gtid_set,
+            "4e11fa47-71ca-11e1-9e33-c80aa9429562:2"
+        ));
+        assert!(!BinlogUtil::gtid_set_contains(
+            "",
+            "4e11fa47-71ca-11e1-9e33-c80aa9429562:1"
+        ));
+    }
 }
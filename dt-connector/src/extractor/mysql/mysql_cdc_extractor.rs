@@ -1,6 +1,7 @@
 use std::{
     cmp,
     collections::HashMap,
+    io::Seek,
     sync::{
         atomic::{AtomicBool, Ordering},
         Arc,
@@ -35,6 +36,7 @@ use dt_common::{
 };
 use mysql_binlog_connector_rust::{
     binlog_client::BinlogClient,
+    binlog_error::BinlogError,
     command::gtid_set::GtidSet,
     event::{
         event_data::EventData, event_header::EventHeader, query_event::QueryEvent,
@@ -50,9 +52,14 @@ pub struct MysqlCdcExtractor {
     pub url: String,
     pub binlog_filename: String,
     pub binlog_position: u32,
+    // read archived binlog files in this dir instead of connecting to url as a replica
+    pub binlog_dir: String,
     pub server_id: u64,
     pub gtid_enabled: bool,
     pub gtid_set: String,
+    // candidate instances to find the new primary when the source is disconnected, gtid only
+    pub failover_urls: Vec<String>,
+    pub failover_timeout_secs: u64,
     pub binlog_heartbeat_interval_secs: u64,
     pub binlog_timeout_secs: u64,
     pub heartbeat_interval_secs: u64,
@@ -65,21 +72,50 @@ struct Context {
     binlog_filename: String,
     table_map_event_map: HashMap<u64, TableMapEvent>,
     gtid_set: Option<GtidSet>,
+    // gtid_set of the last complete transaction, from which to resume after failover
+    committed_gtid_set: String,
+    // rows of the uncommitted transaction, buffered only with failover_urls,
+    // they are dropped if the source fails halfway and received again from the new primary
+    pending_rows: Vec<(RowData, Position)>,
 }
 
 const QUERY_BEGIN: &str = "BEGIN";
 
+impl Context {
+    fn refresh_committed_gtid_set(&mut self) {
+        if let Some(gtid_set) = &self.gtid_set {
+            self.committed_gtid_set = gtid_set.to_string();
+        }
+    }
+
+    /// Drop the progress of the uncommitted transaction before failover
+    fn reset_to_committed(&mut self) -> anyhow::Result<()> {
+        self.binlog_filename = String::new();
+        self.table_map_event_map.clear();
+        self.pending_rows.clear();
+        self.gtid_set = Some(GtidSet::new(&self.committed_gtid_set)?);
+        Ok(())
+    }
+}
+
 #[async_trait]
 impl Extractor for MysqlCdcExtractor {
     async fn extract(&mut self) -> anyhow::Result<()> {
         if self.base_extractor.time_filter.start_timestamp > 0 {
-            self.binlog_filename = BinlogUtil::find_last_binlog_before_timestamp(
-                self.base_extractor.time_filter.start_timestamp,
-                &self.url,
-                self.server_id,
-                &self.conn_pool,
-            )
-            .await?;
+            self.binlog_filename = if self.binlog_dir.is_empty() {
+                BinlogUtil::find_last_binlog_before_timestamp(
+                    self.base_extractor.time_filter.start_timestamp,
+                    &self.url,
+                    self.server_id,
+                    &self.conn_pool,
+                )
+                .await?
+            } else {
+                BinlogUtil::find_last_local_binlog_before_timestamp(
+                    self.base_extractor.time_filter.start_timestamp,
+                    &self.binlog_dir,
+                )?
+            };
         }
 
         if let Position::MysqlCdc {
@@ -102,15 +138,20 @@ impl Extractor for MysqlCdcExtractor {
         }
 
         log_info!(
-            "MysqlCdcExtractor starts, binlog_filename: {}, binlog_position: {}, gtid_enabled: {}, gtid_set: {}, heartbeat_interval_secs: {}, heartbeat_tb: {}",
+            "MysqlCdcExtractor starts, binlog_filename: {}, binlog_position: {}, binlog_dir: {}, gtid_enabled: {}, gtid_set: {}, heartbeat_interval_secs: {}, heartbeat_tb: {}",
             self.binlog_filename,
             self.binlog_position,
+            self.binlog_dir,
             self.gtid_enabled,
             self.gtid_set,
             self.heartbeat_interval_secs,
             self.heartbeat_tb
         );
-        self.extract_internal().await?;
+        if self.binlog_dir.is_empty() {
+            self.extract_internal().await?;
+        } else {
+            self.extract_local_binlogs().await?;
+        }
         self.base_extractor.wait_task_finish().await
     }
 
@@ -132,19 +173,33 @@ impl MysqlCdcExtractor {
             heartbeat_interval_secs: self.binlog_heartbeat_interval_secs,
             timeout_secs: self.binlog_timeout_secs,
         };
+        if !self.failover_urls.is_empty() && !self.gtid_enabled {
+            bail! {Error::ConfigError(
+                "failover_urls only works when gtid_enabled=true".into()
+            )}
+        }
+        // committed gtids are tracked from gtid_set, an empty one can't be resumed from
+        if !self.failover_urls.is_empty() && self.gtid_set.is_empty() {
+            bail! {Error::ConfigError(
+                "failover_urls requires gtid_set to start from, such as @@GLOBAL.gtid_executed of the source".into()
+            )}
+        }
+
         let mut stream = client.connect().await?;
 
         let mut ctx = Context {
             binlog_filename: self.binlog_filename.clone(),
             table_map_event_map: HashMap::new(),
             gtid_set: None,
+            committed_gtid_set: client.gtid_set.clone(),
+            pending_rows: Vec::new(),
         };
         if self.gtid_enabled {
             ctx.gtid_set = Some(GtidSet::new(&client.gtid_set)?);
         }
 
         // start heartbeat
-        self.start_heartbeat(self.base_extractor.shut_down.clone())?;
+        let mut heartbeat_stopped = self.start_heartbeat()?;
 
         loop {
             if self.base_extractor.time_filter.ended {
@@ -152,7 +207,18 @@ impl MysqlCdcExtractor {
                 return Ok(());
             }
 
-            let (header, data) = stream.read().await?;
+            let (header, data) = match stream.read().await {
+                Ok(event) => event,
+                Err(err) if !self.failover_urls.is_empty() && Self::is_failover_error(&err) => {
+                    log_warn!("binlog stream disconnected, try failover, error: {}", err);
+                    heartbeat_stopped.store(true, Ordering::Release);
+                    client = self.failover(&mut ctx).await?;
+                    stream = client.connect().await?;
+                    heartbeat_stopped = self.start_heartbeat()?;
+                    continue;
+                }
+                Err(err) => return Err(err.into()),
+            };
             match data {
                 EventData::Rotate(r) => {
                     ctx.binlog_filename = r.binlog_filename;
@@ -163,6 +229,124 @@ impl MysqlCdcExtractor {
         }
     }
 
+    async fn extract_local_binlogs(&mut self) -> anyhow::Result<()> {
+        let start_index = BinlogUtil::get_binlog_index(&self.binlog_filename);
+        let binlogs: Vec<String> = BinlogUtil::get_local_binlogs(&self.binlog_dir)?
+            .into_iter()
+            .filter(|i| BinlogUtil::get_binlog_index(i) >= start_index)
+            .collect();
+        if binlogs.is_empty() {
+            bail! {Error::ConfigError(format!(
+                "no binlog files found in binlog_dir: {}, binlog_filename: {}",
+                self.binlog_dir, self.binlog_filename
+            ))}
+        }
+
+        let mut ctx = Context {
+            binlog_filename: String::new(),
+            table_map_event_map: HashMap::new(),
+            gtid_set: None,
+            committed_gtid_set: String::new(),
+            pending_rows: Vec::new(),
+        };
+        if self.gtid_enabled {
+            ctx.gtid_set = Some(GtidSet::new(&self.gtid_set)?);
+        }
+
+        // in gtid mode, transactions in gtid_set were executed and are skipped
+        let mut skip_tx = false;
+        // no heartbeat since the source may not exist anymore
+        for binlog in binlogs {
+            log_info!("reading local binlog: {}", binlog);
+            // binlog_position only applies to the first binlog
+            let start_position = if binlog == self.binlog_filename {
+                self.binlog_position as u64
+            } else {
+                0
+            };
+            ctx.binlog_filename = binlog.clone();
+
+            let (mut parser, mut reader, file_len) =
+                BinlogUtil::open_local_binlog(&self.binlog_dir, &binlog)?;
+            while reader.stream_position()? < file_len {
+                if self.base_extractor.time_filter.ended {
+                    return Ok(());
+                }
+
+                let (header, data) = parser.next(&mut reader)?;
+                if let EventData::Gtid(g) = &data {
+                    skip_tx =
+                        self.gtid_enabled && BinlogUtil::gtid_set_contains(&self.gtid_set, &g.gtid);
+                }
+                if skip_tx {
+                    continue;
+                }
+
+                match data {
+                    EventData::Rotate(_) => {}
+
+                    // table map events are needed by row events after start_position
+                    EventData::TableMap(_) => self.parse_events(header, data, &mut ctx).await?,
+
+                    _ => {
+                        if header.next_event_position as u64 > start_position {
+                            self.parse_events(header, data, &mut ctx).await?
+                        }
+                    }
+                }
+            }
+        }
+        log_info!("all local binlogs read");
+        Ok(())
+    }
+
+    async fn failover(&mut self, ctx: &mut Context) -> anyhow::Result<BinlogClient> {
+        // the old source comes first in case it recovers from a transient disconnect
+        let mut urls = vec![self.url.clone()];
+        urls.extend(self.failover_urls.iter().cloned());
+        let (url, conn_pool) = BinlogUtil::find_failover_primary(
+            &urls,
+            &ctx.committed_gtid_set,
+            self.failover_timeout_secs,
+        )
+        .await?;
+        log_info!(
+            "switch source to new primary: {}, resume from committed gtid_set: {}",
+            url,
+            ctx.committed_gtid_set
+        );
+
+        // events of the uncommitted transaction before disconnected will be received again
+        // and its pending rows are dropped
+        self.conn_pool.close().await;
+        self.conn_pool = conn_pool.clone();
+        let meta_center = self.meta_manager.meta_center.take();
+        self.meta_manager.close().await?;
+        self.meta_manager = MysqlMetaManager::new(conn_pool).await?;
+        self.meta_manager.meta_center = meta_center;
+        self.url = url;
+        self.gtid_set = ctx.committed_gtid_set.clone();
+
+        ctx.reset_to_committed()?;
+
+        Ok(BinlogClient {
+            url: self.url.clone(),
+            binlog_filename: String::new(),
+            binlog_position: 0,
+            server_id: self.server_id,
+            gtid_enabled: true,
+            gtid_set: self.gtid_set.clone(),
+            heartbeat_interval_secs: self.binlog_heartbeat_interval_secs,
+            timeout_secs: self.binlog_timeout_secs,
+        })
+    }
+
+    /// Only a broken connection to the source is recovered by failover, other errors,
+    /// such as unexpected binlog data, would happen again on the new primary
+    fn is_failover_error(err: &BinlogError) -> bool {
+        matches!(err, BinlogError::IoError(_) | BinlogError::ConnectError(_))
+    }
+
     #[async_recursion]
     async fn parse_events(
         &mut self,
@@ -221,14 +405,16 @@ impl MysqlCdcExtractor {
                     let col_values = self
                         .parse_row_data(table_map_event, &w.included_columns, event)
                         .await?;
-                    let row_data = RowData::new(
+                    let mut row_data = RowData::new(
                         table_map_event.database_name.clone(),
                         table_map_event.table_name.clone(),
                         RowType::Insert,
                         None,
                         Some(col_values),
                     );
-                    self.push_row_to_buf(row_data, position.clone()).await?;
+                    row_data.partial = w.included_columns.contains(&false);
+                    self.push_row_to_buf(row_data, position.clone(), ctx)
+                        .await?;
                 }
             }
 
@@ -245,14 +431,18 @@ impl MysqlCdcExtractor {
                     let col_values_after = self
                         .parse_row_data(table_map_event, &u.included_columns_after, &mut event.1)
                         .await?;
-                    let row_data = RowData::new(
+                    let partial = u.included_columns_before.contains(&false)
+                        || u.included_columns_after.contains(&false);
+                    let mut row_data = RowData::new(
                         table_map_event.database_name.clone(),
                         table_map_event.table_name.clone(),
                         RowType::Update,
                         Some(col_values_before),
                         Some(col_values_after),
                     );
-                    self.push_row_to_buf(row_data, position.clone()).await?;
+                    row_data.partial = partial;
+                    self.push_row_to_buf(row_data, position.clone(), ctx)
+                        .await?;
                 }
             }
 
@@ -266,14 +456,16 @@ impl MysqlCdcExtractor {
                     let col_values = self
                         .parse_row_data(table_map_event, &d.included_columns, event)
                         .await?;
-                    let row_data = RowData::new(
+                    let mut row_data = RowData::new(
                         table_map_event.database_name.clone(),
                         table_map_event.table_name.clone(),
                         RowType::Delete,
                         Some(col_values),
                         None,
                     );
-                    self.push_row_to_buf(row_data, position.clone()).await?;
+                    row_data.partial = d.included_columns.contains(&false);
+                    self.push_row_to_buf(row_data, position.clone(), ctx)
+                        .await?;
                 }
             }
 
@@ -286,10 +478,20 @@ impl MysqlCdcExtractor {
                     );
                 }
 
+                let is_begin = query.query == QUERY_BEGIN;
+                if !is_begin {
+                    self.flush_pending_rows(ctx).await?;
+                }
                 self.handle_query_event(query, position.clone()).await?;
+                // ddl is committed implicitly
+                if !is_begin {
+                    ctx.refresh_committed_gtid_set();
+                }
             }
 
             EventData::Xid(xid) => {
+                self.flush_pending_rows(ctx).await?;
+                ctx.refresh_committed_gtid_set();
                 let commit = DtData::Commit {
                     xid: xid.xid.to_string(),
                 };
@@ -308,10 +510,22 @@ impl MysqlCdcExtractor {
         &mut self,
         row_data: RowData,
         position: Position,
+        ctx: &mut Context,
     ) -> anyhow::Result<()> {
+        if !self.failover_urls.is_empty() {
+            ctx.pending_rows.push((row_data, position));
+            return Ok(());
+        }
         self.base_extractor.push_row(row_data, position).await
     }
 
+    async fn flush_pending_rows(&mut self, ctx: &mut Context) -> anyhow::Result<()> {
+        for (row_data, position) in ctx.pending_rows.drain(..) {
+            self.base_extractor.push_row(row_data, position).await?;
+        }
+        Ok(())
+    }
+
     async fn parse_row_data(
         &mut self,
         table_map_event: &TableMapEvent,
@@ -341,8 +555,8 @@ impl MysqlCdcExtractor {
                 continue;
             }
 
+            // binlog_row_image=minimal/noblob, absent cols are unchanged
             if let Some(false) = included_columns.get(i) {
-                data.insert(col.clone(), ColValue::None);
                 continue;
             }
 
@@ -427,28 +641,32 @@ impl MysqlCdcExtractor {
         filtered
     }
 
-    fn start_heartbeat(&mut self, shut_down: Arc<AtomicBool>) -> anyhow::Result<()> {
+    /// Returns the flag to stop this heartbeat, which is needed when the source is switched
+    fn start_heartbeat(&mut self) -> anyhow::Result<Arc<AtomicBool>> {
+        let stopped = Arc::new(AtomicBool::new(false));
         let db_tb = self.base_extractor.precheck_heartbeat(
             self.heartbeat_interval_secs,
             &self.heartbeat_tb,
             DbType::Pg,
         );
         if db_tb.len() != 2 {
-            return Ok(());
+            return Ok(stopped);
         }
 
         self.filter.add_ignore_tb(&db_tb[0], &db_tb[1]);
 
-        let (server_id, heartbeat_interval_secs, syncer, conn_pool) = (
+        let (server_id, heartbeat_interval_secs, syncer, conn_pool, shut_down) = (
             self.server_id,
             self.heartbeat_interval_secs,
             self.syncer.clone(),
             self.conn_pool.clone(),
+            self.base_extractor.shut_down.clone(),
         );
 
+        let heartbeat_stopped = stopped.clone();
         tokio::spawn(async move {
             let mut start_time = Instant::now();
-            while !shut_down.load(Ordering::Acquire) {
+            while !shut_down.load(Ordering::Acquire) && !heartbeat_stopped.load(Ordering::Acquire) {
                 if start_time.elapsed().as_secs() >= heartbeat_interval_secs {
                     Self::heartbeat(server_id, &db_tb[0], &db_tb[1], &syncer, &conn_pool)
                         .await
@@ -459,7 +677,7 @@ impl MysqlCdcExtractor {
             }
         });
         log_info!("heartbeat started");
-        Ok(())
+        Ok(stopped)
     }
 
     async fn heartbeat(
@@ -537,3 +755,58 @@ impl MysqlCdcExtractor {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::io;
+
+    use super::*;
+
+    #[test]
+    fn test_is_failover_error() {
+        let err = BinlogError::IoError(io::Error::from(io::ErrorKind::ConnectionReset));
+        assert!(MysqlCdcExtractor::is_failover_error(&err));
+        let err = BinlogError::ConnectError("connection refused".into());
+        assert!(MysqlCdcExtractor::is_failover_error(&err));
+        let err = BinlogError::UnexpectedData("unknown event".into());
+        assert!(!MysqlCdcExtractor::is_failover_error(&err));
+    }
+
+    #[test]
+    fn test_reset_to_committed() {
+        let uuid = "3e11fa47-71ca-11e1-9e33-c80aa9429562";
+        let committed_gtid_set = format!("{}:1-5", uuid);
+        let mut ctx = Context {
+            binlog_filename: "mysql-bin.000001".into(),
+            table_map_event_map: HashMap::new(),
+            gtid_set: Some(GtidSet::new(&committed_gtid_set).unwrap()),
+            committed_gtid_set: String::new(),
+            pending_rows: Vec::new(),
+        };
+        ctx.refresh_committed_gtid_set();
+
+        // the source fails in the middle of transaction 6
+        ctx.gtid_set
+            .as_mut()
+            .unwrap()
+            .add(&format!("{}:6", uuid))
+            .unwrap();
+        let row_data = RowData::new(
+            "test_db".into(),
+            "tb_1".into(),
+            RowType::Insert,
+            None,
+            Some(HashMap::from([("id".to_string(), ColValue::Long(1))])),
+        );
+        ctx.pending_rows.push((row_data, Position::None));
+
+        ctx.reset_to_committed().unwrap();
+        assert!(ctx.pending_rows.is_empty());
+        assert!(ctx.binlog_filename.is_empty());
+        assert_eq!(
+            ctx.gtid_set.as_ref().unwrap().to_string(),
+            GtidSet::new(&committed_gtid_set).unwrap().to_string()
+        );
+        assert_eq!(ctx.committed_gtid_set, ctx.gtid_set.unwrap().to_string());
+    }
+}
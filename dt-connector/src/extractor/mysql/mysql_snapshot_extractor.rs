@@ -379,6 +379,7 @@ impl MysqlSnapshotExtractor {
             dt_data,
             position,
             data_origin_node: String::new(),
+            data_path: String::new(),
         };
         log_debug!("extracted item: {}", json!(item));
         buffer.push(item).await
@@ -2,4 +2,5 @@ pub mod pg_cdc_client;
 pub mod pg_cdc_extractor;
 pub mod pg_check_extractor;
 pub mod pg_snapshot_extractor;
+pub mod pg_stream_buffer;
 pub mod pg_struct_extractor;
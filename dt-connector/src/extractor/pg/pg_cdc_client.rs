@@ -13,6 +13,11 @@ pub struct PgCdcClient {
     pub pub_name: String,
     pub start_lsn: String,
     pub recreate_slot_if_exists: bool,
+    // decode messages emitted by pg_logical_emit_message, supported since postgres 14
+    pub messages: bool,
+    pub proto_version: u8,
+    // stream large in-progress transactions, requires proto_version >= 2
+    pub streaming: bool,
 }
 
 impl PgCdcClient {
@@ -153,10 +158,29 @@ impl PgCdcClient {
         client.simple_query("SET TIME ZONE 'UTC'").await?;
 
         // start replication slot
-        let options = format!(
-            r#"("proto_version" '{}', "publication_names" '{}')"#,
-            "1", pub_name
+        if !(1..=4).contains(&self.proto_version) {
+            bail! {Error::ConfigError(format!(
+                "unsupported proto_version: {}, should be 1 ~ 4",
+                self.proto_version
+            ))}
+        }
+        if self.streaming && self.proto_version < 2 {
+            bail! {Error::ConfigError(
+                "streaming requires proto_version >= 2".into()
+            )}
+        }
+
+        let mut options = format!(
+            r#""proto_version" '{}', "publication_names" '{}'"#,
+            self.proto_version, pub_name
         );
+        if self.streaming {
+            options.push_str(r#", "streaming" 'on'"#);
+        }
+        if self.messages {
+            options.push_str(r#", "messages" 'true'"#);
+        }
+        let options = format!("({})", options);
         let query = format!(
             "START_REPLICATION SLOT {} LOGICAL {} {}",
             self.slot_name, start_lsn, options
@@ -1,5 +1,5 @@
 use std::{
-    collections::HashMap,
+    collections::{HashMap, HashSet},
     pin::Pin,
     sync::{
         atomic::{AtomicBool, Ordering},
@@ -14,11 +14,12 @@ use futures::StreamExt;
 use postgres_protocol::message::backend::{
     DeleteBody, InsertBody,
     LogicalReplicationMessage::{
-        Begin, Commit, Delete, Insert, Origin, Relation, Truncate, Type, Update,
+        Begin, Commit, Delete, Insert, Message, Origin, Relation, StreamAbort, StreamCommit,
+        StreamStart, StreamStop, Truncate, Type, Update,
     },
-    RelationBody,
+    MessageBody, RelationBody,
     ReplicationMessage::*,
-    TupleData, UpdateBody,
+    TruncateBody, TupleData, UpdateBody,
 };
 use postgres_types::PgLsn;
 use sqlx::{postgres::PgArguments, query::Query, Pool, Postgres};
@@ -28,7 +29,8 @@ use tokio_postgres::replication::LogicalReplicationStream;
 use crate::{
     close_conn_pool,
     extractor::{
-        base_extractor::BaseExtractor, pg::pg_cdc_client::PgCdcClient,
+        base_extractor::BaseExtractor,
+        pg::{pg_cdc_client::PgCdcClient, pg_stream_buffer::PgStreamBuffer},
         resumer::cdc_resumer::CdcResumer,
     },
     Extractor,
@@ -39,7 +41,13 @@ use dt_common::{
     log_error, log_info,
     meta::adaptor::pg_col_value_convertor::PgColValueConvertor,
     meta::col_value::ColValue,
+    meta::ddl_meta::{
+        ddl_data::DdlData,
+        ddl_statement::{DdlStatement, PgTruncateMultiTableStatement, PgTruncateTableStatement},
+        ddl_type::DdlType,
+    },
     meta::dt_data::DtData,
+    meta::message_data::MessageData,
     meta::pg::{pg_meta_manager::PgMetaManager, pg_tb_meta::PgTbMeta},
     meta::position::Position,
     meta::rdb_tb_meta::RdbTbMeta,
@@ -64,11 +72,22 @@ pub struct PgCdcExtractor {
     pub heartbeat_interval_secs: u64,
     pub heartbeat_tb: String,
     pub ddl_meta_tb: String,
+    pub capture_ddl_by_message: bool,
+    pub message_prefixes: HashSet<String>,
+    pub proto_version: u8,
+    pub streaming: bool,
+    pub stream_buffer: PgStreamBuffer,
     pub syncer: Arc<Mutex<Syncer>>,
     pub resumer: CdcResumer,
 }
 
 const SECS_FROM_1970_TO_2000: i64 = 946_684_800;
+// refer: https://www.postgresql.org/docs/current/protocol-logicalrep-message-formats.html
+const TRUNCATE_CASCADE: i8 = 1;
+const TRUNCATE_RESTART_IDENTITY: i8 = 2;
+// prefix of messages emitted by the ddl capture trigger
+const DDL_MESSAGE_PREFIX: &str = "ape_dts_ddl";
+const DDL_CAPTURE_FUNCTION: &str = "ape_dts_emit_ddl";
 
 #[async_trait]
 impl Extractor for PgCdcExtractor {
@@ -111,6 +130,9 @@ impl PgCdcExtractor {
             slot_name: self.slot_name.clone(),
             start_lsn: self.start_lsn.clone(),
             recreate_slot_if_exists: self.recreate_slot_if_exists,
+            messages: self.capture_ddl_by_message || !self.message_prefixes.is_empty(),
+            proto_version: self.proto_version,
+            streaming: self.streaming,
         };
         let (stream, actual_start_lsn) = cdc_client.connect().await?;
         tokio::pin!(stream);
@@ -120,6 +142,9 @@ impl PgCdcExtractor {
         if ddl_meta.len() == 2 {
             self.filter.add_do_tb(&ddl_meta[0], &ddl_meta[1]);
         }
+        if self.capture_ddl_by_message && !self.filter.filter_all_ddl() {
+            self.install_ddl_capture_trigger().await?;
+        }
 
         // start heartbeat
         self.start_heartbeat(self.base_extractor.shut_down.clone())?;
@@ -180,26 +205,102 @@ impl PgCdcExtractor {
                                 .await?;
                         }
 
-                        Origin(_origin) => {}
+                        // changes of a large in-progress transaction are sent in several
+                        // stream blocks and buffered until Stream Commit / Stream Abort
+                        StreamStart(start) => {
+                            self.stream_buffer.start_stream(&start.xid().to_string());
+                        }
+
+                        StreamStop(_stop) => {
+                            self.stream_buffer.stop_stream();
+                        }
+
+                        StreamCommit(commit) => {
+                            // same as Begin
+                            position = get_position(&last_tx_end_lsn, commit.timestamp());
+                            xid = commit.xid().to_string();
+                            let timestamp = commit.timestamp() / 1_000_000 + SECS_FROM_1970_TO_2000;
+                            BaseExtractor::update_time_filter(
+                                &mut self.base_extractor.time_filter,
+                                timestamp as u32,
+                                &position,
+                            );
+                            // the task ends at the beginning of the next loop
+                            if self.base_extractor.time_filter.ended {
+                                self.stream_buffer.abort(&xid, &xid)?;
+                                continue;
+                            }
+                            self.replay_streamed_tx(&xid, &position).await?;
+
+                            // same as Commit
+                            last_tx_end_lsn = PgLsn::from(commit.end_lsn()).to_string();
+                            position = get_position(&last_tx_end_lsn, commit.timestamp());
+                            let commit = DtData::Commit { xid: xid.clone() };
+                            self.base_extractor
+                                .push_dt_data(commit, position.clone())
+                                .await?;
+                        }
+
+                        StreamAbort(abort) => {
+                            self.stream_buffer
+                                .abort(&abort.xid().to_string(), &abort.subxid().to_string())?;
+                        }
+
+                        // sent after Begin if the transaction was written by a session
+                        // with replication origin set up, e.g. by PgSinker of another task
+                        Origin(origin) => {
+                            if self.stream_buffer.in_stream() {
+                                self.stream_buffer.set_origin(origin.name()?);
+                            } else if let Some(data_marker) = &mut self.base_extractor.data_marker {
+                                if data_marker.use_replication_origin() {
+                                    data_marker.refresh_by_origin(origin.name()?);
+                                }
+                            }
+                        }
 
-                        Truncate(_truncate) => {}
+                        Message(message) => {
+                            self.stream_buffer.set_subxid(None);
+                            if self.base_extractor.time_filter.started
+                                || self.stream_buffer.in_stream()
+                            {
+                                self.decode_message(&message, &position).await?;
+                            }
+                        }
+
+                        Truncate(truncate) => {
+                            self.stream_buffer.set_subxid(truncate.xid());
+                            if self.base_extractor.time_filter.started
+                                || self.stream_buffer.in_stream()
+                            {
+                                self.decode_truncate(&truncate, &position).await?;
+                            }
+                        }
 
                         Type(_typee) => {}
 
                         Insert(insert) => {
-                            if self.base_extractor.time_filter.started {
+                            self.stream_buffer.set_subxid(insert.xid());
+                            if self.base_extractor.time_filter.started
+                                || self.stream_buffer.in_stream()
+                            {
                                 self.decode_insert(&insert, &position, &ddl_meta).await?;
                             }
                         }
 
                         Update(update) => {
-                            if self.base_extractor.time_filter.started {
+                            self.stream_buffer.set_subxid(update.xid());
+                            if self.base_extractor.time_filter.started
+                                || self.stream_buffer.in_stream()
+                            {
                                 self.decode_update(&update, &position).await?;
                             }
                         }
 
                         Delete(delete) => {
-                            if self.base_extractor.time_filter.started {
+                            self.stream_buffer.set_subxid(delete.xid());
+                            if self.base_extractor.time_filter.started
+                                || self.stream_buffer.in_stream()
+                            {
                                 self.decode_delete(&delete, &position).await?;
                             }
                         }
@@ -433,10 +534,18 @@ impl PgCdcExtractor {
         let ddl_text = get_string(row_data, "ddl_text");
         let _tag = get_string(row_data, "tag");
         let schema = get_string(row_data, "schema");
+        self.decode_ddl_text(&schema, &ddl_text, position).await
+    }
 
+    async fn decode_ddl_text(
+        &mut self,
+        schema: &str,
+        ddl_text: &str,
+        position: &Position,
+    ) -> anyhow::Result<()> {
         if let Ok(ddl_data) = self
             .base_extractor
-            .parse_ddl(&DbType::Pg, &schema, &ddl_text)
+            .parse_ddl(&DbType::Pg, schema, ddl_text)
             .await
         {
             for ddl_data in ddl_data.split_to_multi() {
@@ -445,8 +554,7 @@ impl PgCdcExtractor {
                 let (schema, tb) = ddl_data.get_schema_tb();
 
                 if !self.filter.filter_ddl(&schema, &tb, &ddl_data.ddl_type) {
-                    self.base_extractor
-                        .push_ddl(ddl_data, position.clone())
+                    self.push_to_buf(DtData::Ddl { ddl_data }, position.clone())
                         .await?;
                 }
             }
@@ -454,6 +562,124 @@ impl PgCdcExtractor {
         Ok(())
     }
 
+    async fn decode_message(
+        &mut self,
+        message: &MessageBody,
+        position: &Position,
+    ) -> anyhow::Result<()> {
+        let prefix = message.prefix()?;
+        let content = String::from_utf8_lossy(message.content()).to_string();
+
+        if prefix == DDL_MESSAGE_PREFIX {
+            if !self.capture_ddl_by_message || self.filter.filter_all_ddl() {
+                return Ok(());
+            }
+            // {"schema": "public", "tag": "CREATE TABLE", "ddl_text": "..."}
+            let ddl_info: serde_json::Value = serde_json::from_str(&content)?;
+            let get_string =
+                |key: &str| -> String { ddl_info[key].as_str().unwrap_or_default().to_string() };
+            return self
+                .decode_ddl_text(&get_string("schema"), &get_string("ddl_text"), position)
+                .await;
+        }
+
+        if self.message_prefixes.contains(prefix) {
+            let message_data = MessageData {
+                prefix: prefix.to_string(),
+                content,
+                transactional: message.transactional() != 0,
+            };
+            self.push_to_buf(DtData::Message { message_data }, position.clone())
+                .await?;
+        }
+        Ok(())
+    }
+
+    /// Install an event trigger which emits executed ddls as logical messages,
+    /// so no ddl meta table is needed. Requires superuser.
+    async fn install_ddl_capture_trigger(&self) -> anyhow::Result<()> {
+        let function_sql = format!(
+            r#"CREATE OR REPLACE FUNCTION public.{}() RETURNS event_trigger
+            LANGUAGE plpgsql AS $$
+            BEGIN
+                PERFORM pg_logical_emit_message(true, '{}',
+                    json_build_object('schema', current_schema, 'tag', TG_TAG, 'ddl_text', current_query())::text);
+            END
+            $$"#,
+            DDL_CAPTURE_FUNCTION, DDL_MESSAGE_PREFIX
+        );
+        sqlx::query(&function_sql).execute(&self.conn_pool).await?;
+
+        let trigger = sqlx::query("SELECT evtname FROM pg_event_trigger WHERE evtname = $1")
+            .bind(DDL_CAPTURE_FUNCTION)
+            .fetch_optional(&self.conn_pool)
+            .await?;
+        if trigger.is_none() {
+            let trigger_sql = format!(
+                "CREATE EVENT TRIGGER {} ON ddl_command_end EXECUTE PROCEDURE public.{}()",
+                DDL_CAPTURE_FUNCTION, DDL_CAPTURE_FUNCTION
+            );
+            log_info!("install ddl capture trigger: {}", trigger_sql);
+            sqlx::query(&trigger_sql).execute(&self.conn_pool).await?;
+        }
+        Ok(())
+    }
+
+    async fn decode_truncate(
+        &mut self,
+        event: &TruncateBody,
+        position: &Position,
+    ) -> anyhow::Result<()> {
+        if self.filter.filter_all_ddl() {
+            return Ok(());
+        }
+
+        // a truncate may contain multiple relations: TRUNCATE a, b; or tables truncated by CASCADE
+        let mut schema_tbs = Vec::new();
+        for rel_id in event.rel_ids() {
+            let tb_meta = self.meta_manager.get_tb_meta_by_oid(*rel_id as i32)?;
+            let (schema, tb) = (tb_meta.basic.schema, tb_meta.basic.tb);
+            if !self
+                .filter
+                .filter_ddl(&schema, &tb, &DdlType::TruncateTable)
+            {
+                schema_tbs.push((schema, tb));
+            }
+        }
+
+        let cascade = event.options() & TRUNCATE_CASCADE != 0;
+        let restart_identity = event.options() & TRUNCATE_RESTART_IDENTITY != 0;
+        let statement = match schema_tbs.len() {
+            0 => return Ok(()),
+            1 => {
+                let (schema, tb) = schema_tbs.remove(0);
+                DdlStatement::PgTruncateTable(PgTruncateTableStatement {
+                    schema,
+                    tb,
+                    cascade,
+                    restart_identity,
+                    ..Default::default()
+                })
+            }
+            _ => DdlStatement::PgTruncateMultiTable(PgTruncateMultiTableStatement {
+                schema_tbs,
+                cascade,
+                restart_identity,
+            }),
+        };
+
+        let query = statement.to_sql(&DbType::Pg);
+        let ddl_data = DdlData {
+            query,
+            ddl_type: DdlType::TruncateTable,
+            db_type: DbType::Pg,
+            statement,
+            ..Default::default()
+        };
+        self.push_to_buf(DtData::Ddl { ddl_data }, position.clone())
+            .await
+    }
+
     fn parse_row_data(
         &mut self,
         tb_meta: &PgTbMeta,
@@ -497,7 +723,42 @@ impl PgCdcExtractor {
         row_data: RowData,
         position: Position,
     ) -> anyhow::Result<()> {
-        self.base_extractor.push_row(row_data, position).await
+        self.push_to_buf(DtData::Dml { row_data }, position).await
+    }
+
+    async fn push_to_buf(&mut self, dt_data: DtData, position: Position) -> anyhow::Result<()> {
+        // changes of streamed transactions are pushed after Stream Commit
+        if self.stream_buffer.in_stream() {
+            return self.stream_buffer.push(dt_data);
+        }
+
+        match dt_data {
+            DtData::Dml { row_data } => self.base_extractor.push_row(row_data, position).await,
+            DtData::Ddl { ddl_data } => self.base_extractor.push_ddl(ddl_data, position).await,
+            _ => self.base_extractor.push_dt_data(dt_data, position).await,
+        }
+    }
+
+    async fn replay_streamed_tx(&mut self, xid: &str, position: &Position) -> anyhow::Result<()> {
+        // changes of a transaction committed before start_time_utc are discarded
+        let Some(mut reader) = self
+            .stream_buffer
+            .commit_in_time(xid, &self.base_extractor.time_filter)?
+        else {
+            return Ok(());
+        };
+        if let (Some(origin), Some(data_marker)) =
+            (&reader.origin, &mut self.base_extractor.data_marker)
+        {
+            if data_marker.use_replication_origin() {
+                data_marker.refresh_by_origin(origin);
+            }
+        }
+
+        while let Some(dt_data) = reader.next_data()? {
+            self.push_to_buf(dt_data, position.clone()).await?;
+        }
+        Ok(())
     }
 
     fn filter_event(&mut self, tb_meta: &PgTbMeta, row_type: RowType) -> bool {
@@ -0,0 +1,588 @@
+use std::{
+    collections::HashMap,
+    fs::{self, File, OpenOptions},
+    io::{BufRead, BufReader, BufWriter, Lines, Write},
+    path::PathBuf,
+};
+
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use dt_common::{
+    log_info,
+    meta::{col_value::ColValue, dt_data::DtData, row_data::RowData, row_type::RowType},
+    time_filter::TimeFilter,
+};
+
+const SPILL_FILE_EXTENSION: &str = "spill";
+
+/// Buffers changes of in-progress transactions streamed by pgoutput (proto_version >= 2,
+/// streaming on) until Stream Commit / Stream Abort arrives. Changes are spilled to disk
+/// once the memory limits of DtQueue are reached.
+#[derive(Default)]
+pub struct PgStreamBuffer {
+    pub spill_dir: String,
+    pub max_bytes: usize,
+    pub max_count: usize,
+    cur_bytes: usize,
+    cur_count: usize,
+    // top level xid of the current stream block, between Stream Start and Stream Stop
+    stream_xid: Option<String>,
+    // xid of the (sub) transaction which the current change belongs to
+    subxid: Option<String>,
+    txs: HashMap<String, StreamedTx>,
+}
+
+#[derive(Default)]
+struct StreamedTx {
+    // (subxid, data)
+    items: Vec<(String, DtData)>,
+    bytes: usize,
+    spilled: bool,
+    // replication origin of the transaction, sent in Stream Start
+    origin: Option<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct SpilledItem {
+    subxid: String,
+    data: SpilledData,
+}
+
+// ColValue is serialized without type info, so row values are spilled with their variant names
+#[derive(Serialize, Deserialize)]
+enum SpilledData {
+    Dml {
+        schema: String,
+        tb: String,
+        row_type: RowType,
+        before: Option<HashMap<String, serde_json::Value>>,
+        after: Option<HashMap<String, serde_json::Value>>,
+        data_size: usize,
+    },
+    Other {
+        dt_data: DtData,
+    },
+}
+
+/// Changes of a committed streamed transaction, spilled ones first, then those in memory
+pub struct StreamedTxReader {
+    spill_file: Option<PathBuf>,
+    lines: Option<Lines<BufReader<File>>>,
+    items: std::vec::IntoIter<(String, DtData)>,
+    pub origin: Option<String>,
+}
+
+impl StreamedTxReader {
+    pub fn next_data(&mut self) -> anyhow::Result<Option<DtData>> {
+        if let Some(lines) = &mut self.lines {
+            if let Some(line) = lines.next() {
+                let item: SpilledItem = serde_json::from_str(&line?)?;
+                return Ok(Some(PgStreamBuffer::from_spilled(item.data)?));
+            }
+            self.lines = None;
+        }
+        Ok(self.items.next().map(|(_, dt_data)| dt_data))
+    }
+}
+
+impl Drop for StreamedTxReader {
+    fn drop(&mut self) {
+        if let Some(spill_file) = &self.spill_file {
+            let _ = fs::remove_file(spill_file);
+        }
+    }
+}
+
+impl PgStreamBuffer {
+    pub fn new(spill_dir: &str, max_bytes: usize, max_count: usize) -> anyhow::Result<Self> {
+        // streamed transactions are resent from the beginning after restart
+        if let Ok(entries) = fs::read_dir(spill_dir) {
+            for entry in entries {
+                let path = entry?.path();
+                if path.extension().is_some_and(|i| i == SPILL_FILE_EXTENSION) {
+                    fs::remove_file(path)?;
+                }
+            }
+        }
+
+        Ok(Self {
+            spill_dir: spill_dir.to_string(),
+            max_bytes,
+            max_count,
+            ..Default::default()
+        })
+    }
+
+    pub fn start_stream(&mut self, xid: &str) {
+        self.stream_xid = Some(xid.to_string());
+        self.subxid = None;
+    }
+
+    pub fn stop_stream(&mut self) {
+        self.stream_xid = None;
+        self.subxid = None;
+    }
+
+    pub fn in_stream(&self) -> bool {
+        self.stream_xid.is_some()
+    }
+
+    /// Changes of streamed transactions carry the xid of the (sub) transaction they belong to
+    pub fn set_subxid(&mut self, subxid: Option<u32>) {
+        self.subxid = subxid.map(|i| i.to_string());
+    }
+
+    pub fn set_origin(&mut self, origin: &str) {
+        if let Some(xid) = self.stream_xid.clone() {
+            self.txs.entry(xid).or_default().origin = Some(origin.to_string());
+        }
+    }
+
+    pub fn push(&mut self, dt_data: DtData) -> anyhow::Result<()> {
+        let xid = self.stream_xid.clone().unwrap_or_default();
+        let subxid = self.subxid.clone().unwrap_or_else(|| xid.clone());
+        let data_size = dt_data.get_data_size();
+
+        let tx = self.txs.entry(xid).or_default();
+        tx.items.push((subxid, dt_data));
+        tx.bytes += data_size;
+        self.cur_bytes += data_size;
+        self.cur_count += 1;
+
+        if (self.max_bytes > 0 && self.cur_bytes > self.max_bytes)
+            || (self.max_count > 0 && self.cur_count > self.max_count)
+        {
+            self.spill_all()?;
+        }
+        Ok(())
+    }
+
+    pub fn commit(&mut self, xid: &str) -> anyhow::Result<StreamedTxReader> {
+        let tx = self.txs.remove(xid).unwrap_or_default();
+        self.cur_bytes -= tx.bytes;
+        self.cur_count -= tx.items.len();
+
+        let (spill_file, lines) = if tx.spilled {
+            let spill_file = self.get_spill_file(xid);
+            let lines = BufReader::new(File::open(&spill_file)?).lines();
+            (Some(spill_file), Some(lines))
+        } else {
+            (None, None)
+        };
+
+        Ok(StreamedTxReader {
+            spill_file,
+            lines,
+            items: tx.items.into_iter(),
+            origin: tx.origin,
+        })
+    }
+
+    /// Same as commit, but changes of a transaction committed out of the time filter are
+    /// discarded and None is returned
+    pub fn commit_in_time(
+        &mut self,
+        xid: &str,
+        time_filter: &TimeFilter,
+    ) -> anyhow::Result<Option<StreamedTxReader>> {
+        if time_filter.started && !time_filter.ended {
+            return Ok(Some(self.commit(xid)?));
+        }
+        self.abort(xid, xid)?;
+        Ok(None)
+    }
+
+    /// If subxid equals xid, the whole transaction is aborted, otherwise only the
+    /// sub transaction (ROLLBACK TO SAVEPOINT) is
+    pub fn abort(&mut self, xid: &str, subxid: &str) -> anyhow::Result<()> {
+        if xid == subxid {
+            let tx = self.txs.remove(xid).unwrap_or_default();
+            self.cur_bytes -= tx.bytes;
+            self.cur_count -= tx.items.len();
+            if tx.spilled {
+                fs::remove_file(self.get_spill_file(xid))?;
+            }
+            return Ok(());
+        }
+
+        let spill_file = self.get_spill_file(xid);
+        let Some(tx) = self.txs.get_mut(xid) else {
+            return Ok(());
+        };
+
+        let (mut removed_bytes, mut removed_count) = (0, 0);
+        tx.items.retain(|(i, dt_data)| {
+            if i == subxid {
+                removed_bytes += dt_data.get_data_size();
+                removed_count += 1;
+                return false;
+            }
+            true
+        });
+        tx.bytes -= removed_bytes;
+        self.cur_bytes -= removed_bytes;
+        self.cur_count -= removed_count;
+
+        if tx.spilled {
+            let tmp_file = spill_file.with_extension("tmp");
+            {
+                let reader = BufReader::new(File::open(&spill_file)?);
+                let mut writer = BufWriter::new(File::create(&tmp_file)?);
+                for line in reader.lines() {
+                    let line = line?;
+                    let item: SpilledItem = serde_json::from_str(&line)?;
+                    if item.subxid != subxid {
+                        writeln!(writer, "{}", line)?;
+                    }
+                }
+                writer.flush()?;
+            }
+            fs::rename(tmp_file, spill_file)?;
+        }
+        Ok(())
+    }
+
+    fn spill_all(&mut self) -> anyhow::Result<()> {
+        fs::create_dir_all(&self.spill_dir)?;
+        let xids: Vec<String> = self
+            .txs
+            .iter()
+            .filter(|(_, tx)| !tx.items.is_empty())
+            .map(|(xid, _)| xid.clone())
+            .collect();
+
+        for xid in xids {
+            let spill_file = self.get_spill_file(&xid);
+            let tx = self.txs.get_mut(&xid).unwrap();
+            log_info!(
+                "spill streamed transaction, xid: {}, count: {}, file: {}",
+                xid,
+                tx.items.len(),
+                spill_file.display()
+            );
+
+            let file = OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(&spill_file)?;
+            let mut writer = BufWriter::new(file);
+            for (subxid, dt_data) in tx.items.drain(..) {
+                let item = SpilledItem {
+                    subxid,
+                    data: Self::to_spilled(dt_data),
+                };
+                writeln!(writer, "{}", serde_json::to_string(&item)?)?;
+            }
+            writer.flush()?;
+
+            tx.spilled = true;
+            self.cur_bytes -= tx.bytes;
+            tx.bytes = 0;
+        }
+        self.cur_count = 0;
+        Ok(())
+    }
+
+    fn get_spill_file(&self, xid: &str) -> PathBuf {
+        PathBuf::from(&self.spill_dir).join(format!("{}.{}", xid, SPILL_FILE_EXTENSION))
+    }
+
+    fn to_spilled(dt_data: DtData) -> SpilledData {
+        match dt_data {
+            DtData::Dml { row_data } => {
+                let to_tagged = |col_values: Option<HashMap<String, ColValue>>| {
+                    col_values.map(|col_values| {
+                        col_values
+                            .iter()
+                            .map(|(col, value)| (col.clone(), Self::col_value_to_tagged(value)))
+                            .collect()
+                    })
+                };
+                SpilledData::Dml {
+                    schema: row_data.schema,
+                    tb: row_data.tb,
+                    row_type: row_data.row_type,
+                    before: to_tagged(row_data.before),
+                    after: to_tagged(row_data.after),
+                    data_size: row_data.data_size,
+                }
+            }
+            dt_data => SpilledData::Other { dt_data },
+        }
+    }
+
+    fn from_spilled(data: SpilledData) -> anyhow::Result<DtData> {
+        match data {
+            SpilledData::Dml {
+                schema,
+                tb,
+                row_type,
+                before,
+                after,
+                data_size,
+            } => {
+                let from_tagged = |col_values: Option<HashMap<String, serde_json::Value>>| {
+                    col_values
+                        .map(|col_values| {
+                            col_values
+                                .into_iter()
+                                .map(|(col, value)| Ok((col, serde_json::from_value(value)?)))
+                                .collect::<anyhow::Result<HashMap<String, ColValue>>>()
+                        })
+                        .transpose()
+                };
+                let row_data = RowData {
+                    schema,
+                    tb,
+                    row_type,
+                    before: from_tagged(before)?,
+                    after: from_tagged(after)?,
+                    data_size,
+                    partial: false,
+                    delete_all: false,
+                };
+                Ok(DtData::Dml { row_data })
+            }
+            SpilledData::Other { dt_data } => Ok(dt_data),
+        }
+    }
+
+    // the externally tagged format of the derived Deserialize of ColValue
+    fn col_value_to_tagged(value: &ColValue) -> serde_json::Value {
+        match value {
+            ColValue::None => json!("None"),
+            ColValue::Bool(v) => json!({ "Bool": v }),
+            ColValue::Tiny(v) => json!({ "Tiny": v }),
+            ColValue::UnsignedTiny(v) => json!({ "UnsignedTiny": v }),
+            ColValue::Short(v) => json!({ "Short": v }),
+            ColValue::UnsignedShort(v) => json!({ "UnsignedShort": v }),
+            ColValue::Long(v) => json!({ "Long": v }),
+            ColValue::UnsignedLong(v) => json!({ "UnsignedLong": v }),
+            ColValue::LongLong(v) => json!({ "LongLong": v }),
+            ColValue::UnsignedLongLong(v) => json!({ "UnsignedLongLong": v }),
+            ColValue::Float(v) => json!({ "Float": v }),
+            ColValue::Double(v) => json!({ "Double": v }),
+            ColValue::Decimal(v) => json!({ "Decimal": v }),
+            ColValue::Time(v) => json!({ "Time": v }),
+            ColValue::Date(v) => json!({ "Date": v }),
+            ColValue::DateTime(v) => json!({ "DateTime": v }),
+            ColValue::Timestamp(v) => json!({ "Timestamp": v }),
+            ColValue::Year(v) => json!({ "Year": v }),
+            ColValue::String(v) => json!({ "String": v }),
+            ColValue::RawString(v) => json!({ "RawString": v }),
+            ColValue::Blob(v) => json!({ "Blob": v }),
+            ColValue::Bit(v) => json!({ "Bit": v }),
+            ColValue::Set(v) => json!({ "Set": v }),
+            ColValue::Enum(v) => json!({ "Enum": v }),
+            ColValue::Set2(v) => json!({ "Set2": v }),
+            ColValue::Enum2(v) => json!({ "Enum2": v }),
+            ColValue::Json(v) => json!({ "Json": v }),
+            ColValue::Json2(v) => json!({ "Json2": v }),
+            ColValue::Json3(v) => json!({ "Json3": v }),
+            ColValue::MongoDoc(v) => json!({ "MongoDoc": v }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn new_buffer(name: &str, max_count: usize) -> PgStreamBuffer {
+        let spill_dir = std::env::temp_dir().join(format!("ape_dts_pg_stream_buffer_{}", name));
+        let _ = fs::remove_dir_all(&spill_dir);
+        PgStreamBuffer::new(spill_dir.to_str().unwrap(), 0, max_count).unwrap()
+    }
+
+    fn new_row(id: i32, value: &str) -> RowData {
+        let after = HashMap::from([
+            ("id".to_string(), ColValue::Long(id)),
+            ("value".to_string(), ColValue::String(value.to_string())),
+        ]);
+        RowData::new(
+            "test_db".into(),
+            "tb_1".into(),
+            RowType::Insert,
+            None,
+            Some(after),
+        )
+    }
+
+    fn push_row(buffer: &mut PgStreamBuffer, id: i32) {
+        let row_data = new_row(id, &format!("value_{}", id));
+        buffer.push(DtData::Dml { row_data }).unwrap();
+    }
+
+    fn read_ids(mut reader: StreamedTxReader) -> Vec<i32> {
+        let mut ids = Vec::new();
+        while let Some(dt_data) = reader.next_data().unwrap() {
+            let DtData::Dml { row_data } = dt_data else {
+                panic!("unexpected data: {:?}", dt_data);
+            };
+            let Some(ColValue::Long(id)) = row_data.after.as_ref().unwrap().get("id") else {
+                panic!("unexpected row: {}", row_data);
+            };
+            ids.push(*id);
+        }
+        ids
+    }
+
+    #[test]
+    fn test_stream_commit() {
+        let mut buffer = new_buffer("commit", 0);
+
+        // stream blocks of two transactions interleave
+        buffer.start_stream("1");
+        buffer.set_origin("origin_1");
+        push_row(&mut buffer, 1);
+        buffer.stop_stream();
+        buffer.start_stream("2");
+        push_row(&mut buffer, 2);
+        buffer.stop_stream();
+        buffer.start_stream("1");
+        push_row(&mut buffer, 3);
+        buffer.stop_stream();
+        assert!(!buffer.in_stream());
+
+        let reader = buffer.commit("1").unwrap();
+        assert_eq!(reader.origin, Some("origin_1".to_string()));
+        assert_eq!(read_ids(reader), vec![1, 3]);
+
+        let reader = buffer.commit("2").unwrap();
+        assert_eq!(reader.origin, None);
+        assert_eq!(read_ids(reader), vec![2]);
+        assert_eq!(buffer.cur_count, 0);
+        assert_eq!(buffer.cur_bytes, 0);
+    }
+
+    #[test]
+    fn test_stream_commit_out_of_time() {
+        let mut buffer = new_buffer("commit_out_of_time", 1);
+        let spill_file = buffer.get_spill_file("1");
+        let mut time_filter = TimeFilter {
+            start_timestamp: 100,
+            end_timestamp: 200,
+            started: false,
+            ended: false,
+        };
+
+        // committed before start_time_utc
+        buffer.start_stream("1");
+        push_row(&mut buffer, 1);
+        push_row(&mut buffer, 2);
+        buffer.stop_stream();
+        assert!(spill_file.exists());
+        assert!(buffer.commit_in_time("1", &time_filter).unwrap().is_none());
+        assert!(!spill_file.exists());
+        assert_eq!(buffer.cur_count, 0);
+
+        // committed in range
+        time_filter.started = true;
+        buffer.start_stream("2");
+        push_row(&mut buffer, 3);
+        buffer.stop_stream();
+        let reader = buffer.commit_in_time("2", &time_filter).unwrap().unwrap();
+        assert_eq!(read_ids(reader), vec![3]);
+
+        // committed after end_time_utc
+        time_filter.ended = true;
+        buffer.start_stream("3");
+        push_row(&mut buffer, 4);
+        buffer.stop_stream();
+        assert!(buffer.commit_in_time("3", &time_filter).unwrap().is_none());
+        assert_eq!(buffer.cur_count, 0);
+        assert!(buffer.txs.is_empty());
+    }
+
+    #[test]
+    fn test_spill_to_disk() {
+        let mut buffer = new_buffer("spill", 2);
+        let spill_file = buffer.get_spill_file("1");
+
+        buffer.start_stream("1");
+        push_row(&mut buffer, 1);
+        push_row(&mut buffer, 2);
+        assert!(!spill_file.exists());
+
+        // the third change exceeds max_count
+        push_row(&mut buffer, 3);
+        assert!(spill_file.exists());
+        assert_eq!(buffer.cur_count, 0);
+        assert_eq!(buffer.cur_bytes, 0);
+
+        push_row(&mut buffer, 4);
+        buffer.stop_stream();
+
+        // spilled changes first, then those in memory, values are kept
+        let mut reader = buffer.commit("1").unwrap();
+        let mut rows = Vec::new();
+        while let Some(DtData::Dml { row_data }) = reader.next_data().unwrap() {
+            rows.push(row_data);
+        }
+        let expect_rows: Vec<RowData> = (1..=4)
+            .map(|id| new_row(id, &format!("value_{}", id)))
+            .collect();
+        assert_eq!(rows, expect_rows);
+
+        // spill file is removed once the transaction is consumed
+        drop(reader);
+        assert!(!spill_file.exists());
+    }
+
+    #[test]
+    fn test_sub_transaction_abort() {
+        let mut buffer = new_buffer("sub_abort", 0);
+
+        buffer.start_stream("1");
+        buffer.set_subxid(Some(1));
+        push_row(&mut buffer, 1);
+        buffer.set_subxid(Some(2));
+        push_row(&mut buffer, 2);
+        buffer.set_subxid(Some(1));
+        push_row(&mut buffer, 3);
+        buffer.stop_stream();
+
+        // ROLLBACK TO SAVEPOINT
+        buffer.abort("1", "2").unwrap();
+        assert_eq!(buffer.cur_count, 2);
+        assert_eq!(read_ids(buffer.commit("1").unwrap()), vec![1, 3]);
+    }
+
+    #[test]
+    fn test_sub_transaction_abort_spilled() {
+        let mut buffer = new_buffer("sub_abort_spilled", 1);
+        let spill_file = buffer.get_spill_file("1");
+
+        buffer.start_stream("1");
+        buffer.set_subxid(Some(1));
+        push_row(&mut buffer, 1);
+        buffer.set_subxid(Some(2));
+        push_row(&mut buffer, 2);
+        push_row(&mut buffer, 3);
+        buffer.set_subxid(Some(1));
+        push_row(&mut buffer, 4);
+        buffer.stop_stream();
+        assert!(spill_file.exists());
+
+        buffer.abort("1", "2").unwrap();
+        assert_eq!(read_ids(buffer.commit("1").unwrap()), vec![1, 4]);
+    }
+
+    #[test]
+    fn test_transaction_abort() {
+        let mut buffer = new_buffer("abort", 1);
+        let spill_file = buffer.get_spill_file("1");
+
+        buffer.start_stream("1");
+        push_row(&mut buffer, 1);
+        push_row(&mut buffer, 2);
+        buffer.stop_stream();
+        assert!(spill_file.exists());
+
+        buffer.abort("1", "1").unwrap();
+        assert!(!spill_file.exists());
+        assert_eq!(buffer.cur_count, 0);
+        assert!(read_ids(buffer.commit("1").unwrap()).is_empty());
+    }
+}
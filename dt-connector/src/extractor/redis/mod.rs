@@ -2,6 +2,7 @@ use async_trait::async_trait;
 use std::io::{Cursor, Read};
 
 pub mod rdb;
+pub mod redis_check_extractor;
 pub mod redis_client;
 pub mod redis_psync_extractor;
 pub mod redis_reshard_extractor;
@@ -0,0 +1,188 @@
+use anyhow::bail;
+use dt_common::error::Error;
+
+// bits of delta of delta for each timestamp level, level 0 means delta of delta is 0,
+// level n is prefixed by n bits of 1 and a bit of 0, except the last level
+const TIMESTAMP_LEVEL_BITS: [u32; 7] = [0, 5, 8, 11, 14, 32, 64];
+const DOUBLE_LEADING: u32 = 5;
+const DOUBLE_BLOCK_SIZE: u32 = 6;
+const DOUBLE_BLOCK_ADJUST: u32 = 1;
+
+/// Decodes compressed chunks of RedisTimeSeries, in which timestamps are encoded by
+/// delta of delta and values are encoded by xor with the previous value (Gorilla).
+/// Bits are stored from the least significant bit of little endian u64 words.
+pub struct GorillaDecoder<'a> {
+    data: &'a [u8],
+    pub bit: usize,
+    pub prev_timestamp: u64,
+    pub prev_timestamp_delta: i64,
+    pub prev_value: u64,
+    prev_leading: u32,
+    prev_trailing: u32,
+}
+
+impl<'a> GorillaDecoder<'a> {
+    pub fn new(data: &'a [u8], base_timestamp: u64, base_value: u64) -> Self {
+        Self {
+            data,
+            bit: 0,
+            prev_timestamp: base_timestamp,
+            prev_timestamp_delta: 0,
+            prev_value: base_value,
+            prev_leading: 0,
+            prev_trailing: 0,
+        }
+    }
+
+    /// Decodes count samples, the first sample is the base one which takes no bits.
+    pub fn decode(&mut self, count: u64) -> anyhow::Result<Vec<(u64, f64)>> {
+        let mut samples = Vec::with_capacity(count as usize);
+        if count == 0 {
+            return Ok(samples);
+        }
+        samples.push((self.prev_timestamp, f64::from_bits(self.prev_value)));
+
+        for _ in 1..count {
+            let delta = self.prev_timestamp_delta + self.read_timestamp_dod()?;
+            self.prev_timestamp = self.prev_timestamp.wrapping_add(delta as u64);
+            self.prev_timestamp_delta = delta;
+            self.prev_value ^= self.read_value_xor()?;
+            samples.push((self.prev_timestamp, f64::from_bits(self.prev_value)));
+        }
+        Ok(samples)
+    }
+
+    fn read_timestamp_dod(&mut self) -> anyhow::Result<i64> {
+        let last_level = TIMESTAMP_LEVEL_BITS.len() - 1;
+        let mut level = 0;
+        while level < last_level && self.read_bit()? {
+            level += 1;
+        }
+
+        let bits = TIMESTAMP_LEVEL_BITS[level];
+        let dod = match bits {
+            0 => 0,
+            64 => self.read_bits(64)? as i64,
+            _ => {
+                // sign extend
+                let v = self.read_bits(bits)? as i64;
+                if v & (1 << (bits - 1)) != 0 {
+                    v - (1 << bits)
+                } else {
+                    v
+                }
+            }
+        };
+        Ok(dod)
+    }
+
+    fn read_value_xor(&mut self) -> anyhow::Result<u64> {
+        if !self.read_bit()? {
+            return Ok(0);
+        }
+
+        if self.read_bit()? {
+            self.prev_leading = self.read_bits(DOUBLE_LEADING)? as u32;
+            let block_size = self.read_bits(DOUBLE_BLOCK_SIZE)? as u32 + DOUBLE_BLOCK_ADJUST;
+            if self.prev_leading + block_size > 64 {
+                bail! {Error::RedisRdbError(format!(
+                    "invalid gorilla block, leading: {}, block size: {}",
+                    self.prev_leading, block_size
+                ))}
+            }
+            self.prev_trailing = 64 - self.prev_leading - block_size;
+        }
+
+        let block_size = 64 - self.prev_leading - self.prev_trailing;
+        Ok(self
+            .read_bits(block_size)?
+            .checked_shl(self.prev_trailing)
+            .unwrap_or_default())
+    }
+
+    fn read_bit(&mut self) -> anyhow::Result<bool> {
+        let Some(byte) = self.data.get(self.bit / 8) else {
+            bail! {Error::RedisRdbError(
+                "gorilla data exhausted".into()
+            )}
+        };
+        let bit = (byte >> (self.bit % 8)) & 1 == 1;
+        self.bit += 1;
+        Ok(bit)
+    }
+
+    fn read_bits(&mut self, len: u32) -> anyhow::Result<u64> {
+        let mut v = 0u64;
+        for i in 0..len {
+            if self.read_bit()? {
+                v |= 1 << i;
+            }
+        }
+        Ok(v)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct BitWriter {
+        data: Vec<u8>,
+        bit: usize,
+    }
+
+    impl BitWriter {
+        fn append(&mut self, v: u64, len: u32) {
+            for i in 0..len {
+                if self.bit / 8 >= self.data.len() {
+                    self.data.push(0);
+                }
+                if (v >> i) & 1 == 1 {
+                    self.data[self.bit / 8] |= 1 << (self.bit % 8);
+                }
+                self.bit += 1;
+            }
+        }
+    }
+
+    #[test]
+    fn test_decode() {
+        let mut writer = BitWriter {
+            data: Vec::new(),
+            bit: 0,
+        };
+        // sample 2: delta 1000 (dod 1000, level 3: '1110' + 11 bits), value unchanged
+        writer.append(0b0111, 4);
+        writer.append(1000, 11);
+        writer.append(0, 1);
+        // sample 3: delta 1000 (dod 0), value 2.0 xor 1.0, new block
+        writer.append(0, 1);
+        let xor = 2.0f64.to_bits() ^ 1.0f64.to_bits();
+        let (leading, trailing) = (xor.leading_zeros(), xor.trailing_zeros());
+        let block_size = 64 - leading - trailing;
+        writer.append(0b11, 2);
+        writer.append(leading as u64, 5);
+        writer.append((block_size - 1) as u64, 6);
+        writer.append(xor >> trailing, block_size);
+        // sample 4: delta 990 (dod -10, level 2: '110' + 8 bits), value 1.0 reuses the block
+        writer.append(0b011, 3);
+        writer.append((-10i64 as u64) & 0xff, 8);
+        writer.append(0b01, 2);
+        writer.append(xor >> trailing, block_size);
+
+        let mut decoder = GorillaDecoder::new(&writer.data, 1000, 1.0f64.to_bits());
+        let samples = decoder.decode(4).unwrap();
+        assert_eq!(
+            samples,
+            vec![(1000, 1.0), (2000, 1.0), (3000, 2.0), (3990, 1.0)]
+        );
+        assert_eq!(decoder.bit, writer.bit);
+        assert_eq!(decoder.prev_timestamp, 3990);
+        assert_eq!(decoder.prev_timestamp_delta, 990);
+        assert_eq!(decoder.prev_value, 1.0f64.to_bits());
+
+        // data exhausted
+        let mut decoder = GorillaDecoder::new(&writer.data[..4], 1000, 1.0f64.to_bits());
+        assert!(decoder.decode(4).is_err());
+    }
+}
@@ -1,15 +1,68 @@
 use anyhow::bail;
+use byteorder::{ByteOrder, LittleEndian};
 use dt_common::error::Error;
-use dt_common::log_info;
-use dt_common::meta::redis::redis_object::{ModuleObject, RedisString};
+use dt_common::meta::redis::redis_object::{
+    BloomFilter, BloomObject, ModuleObject, ModuleValue, RedisString, TimeSeriesObject,
+};
+use dt_common::{log_info, log_warn};
+use serde_json::{Map, Number, Value};
 
+use super::gorilla_decoder::GorillaDecoder;
 use crate::extractor::redis::rdb::reader::rdb_reader::RdbReader;
+use crate::extractor::redis::StreamReader;
 
 pub struct ModuleParser {}
 
 const MODULE_TYPE_NAME_CHAR_SET: &str =
     "ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
 
+pub const MODULE_JSON: &str = "ReJSON-RL";
+pub const MODULE_BLOOM: &str = "MBbloom--";
+pub const MODULE_TIME_SERIES: &str = "TSDB-TYPE";
+
+const RDB_MODULE_OPCODE_EOF: u64 = 0;
+const RDB_MODULE_OPCODE_SINT: u64 = 1;
+const RDB_MODULE_OPCODE_UINT: u64 = 2;
+const RDB_MODULE_OPCODE_FLOAT: u64 = 3;
+const RDB_MODULE_OPCODE_DOUBLE: u64 = 4;
+const RDB_MODULE_OPCODE_STRING: u64 = 5;
+
+// node types of RedisJSON 1.x (encver 0)
+const JSON_NODE_NULL: u64 = 1;
+const JSON_NODE_BOOLEAN: u64 = 2;
+const JSON_NODE_INTEGER: u64 = 4;
+const JSON_NODE_NUMBER: u64 = 8;
+const JSON_NODE_STRING: u64 = 16;
+const JSON_NODE_DICT: u64 = 32;
+const JSON_NODE_ARRAY: u64 = 64;
+const JSON_NODE_KEYVAL: u64 = 128;
+
+// encoding versions of RedisBloom
+const BF_MIN_OPTIONS_ENC: u64 = 2;
+const BF_MIN_GROWTH_ENC: u64 = 4;
+const BF_ENCODING_VERSION: u64 = 4;
+
+// series options of RedisTimeSeries
+const SERIES_OPT_UNCOMPRESSED: u64 = 0x1;
+// tokens saved by each chunk of RedisTimeSeries
+const TS_UNCOMPRESSED_CHUNK_TOKENS: usize = 4;
+const TS_COMPRESSED_CHUNK_TOKENS: usize = 11;
+
+/// Values saved by RedisModule_Save* apis, each is prefixed by an opcode in module2 encoding
+#[derive(Debug, Clone)]
+enum ModuleToken {
+    Signed(i64),
+    Unsigned(u64),
+    Float(f32),
+    Double(f64),
+    String(RedisString),
+}
+
+struct ModuleTokens {
+    tokens: Vec<ModuleToken>,
+    pos: usize,
+}
+
 impl ModuleParser {
     pub async fn load_from_buffer(
         reader: &mut RdbReader<'_>,
@@ -25,11 +78,37 @@ impl ModuleParser {
 
         let module_id = reader.read_length().await?;
         let module_name = Self::module_type_name_by_id(module_id);
+        let encver = module_id & 1023;
+        let tokens = Self::read_module_tokens(reader).await?;
+
+        let mut tokens = ModuleTokens { tokens, pos: 0 };
+        let value = match module_name.as_str() {
+            MODULE_JSON => Self::decode_json(encver, &mut tokens),
+            MODULE_BLOOM => Self::decode_bloom(encver, &mut tokens),
+            MODULE_TIME_SERIES => Self::decode_time_series(&mut tokens),
+            _ => {
+                log_info!("load module2 type: [{}] with raw", module_name);
+                Ok(ModuleValue::Unknown)
+            }
+        };
 
-        log_info!("load module2 type: [{}] with raw", module_name);
-        Self::skip_module_data(reader).await?;
+        // restore by raw bytes is still available even if failed to decode
+        let value = value.unwrap_or_else(|err| {
+            log_warn!(
+                "failed to decode module2 type: [{}], encver: {}, key: [{}], error: {}",
+                module_name,
+                encver,
+                key,
+                err
+            );
+            ModuleValue::Unknown
+        });
 
-        Ok(ModuleObject::new())
+        Ok(ModuleObject {
+            key,
+            module_name,
+            value,
+        })
     }
 
     pub fn module_type_name_by_id(module_id: u64) -> String {
@@ -44,30 +123,500 @@ impl ModuleParser {
         String::from_utf8(name_list).unwrap()
     }
 
-    async fn skip_module_data(reader: &mut RdbReader<'_>) -> anyhow::Result<()> {
+    async fn read_module_tokens(reader: &mut RdbReader<'_>) -> anyhow::Result<Vec<ModuleToken>> {
+        let mut tokens = Vec::new();
         let mut opcode = reader.read_length().await?;
-        while opcode != 0 {
-            match opcode {
-                1 | 2 => {
-                    reader.read_length().await?;
-                }
-                3 => {
-                    reader.read_float().await?;
-                }
-                4 => {
-                    reader.read_double().await?;
-                }
-                5 => {
-                    reader.read_string().await?;
+        while opcode != RDB_MODULE_OPCODE_EOF {
+            let token = match opcode {
+                RDB_MODULE_OPCODE_SINT => ModuleToken::Signed(reader.read_length().await? as i64),
+                RDB_MODULE_OPCODE_UINT => ModuleToken::Unsigned(reader.read_length().await?),
+                RDB_MODULE_OPCODE_FLOAT => {
+                    let buf = reader.read_bytes(4).await?;
+                    ModuleToken::Float(LittleEndian::read_f32(&buf))
                 }
+                RDB_MODULE_OPCODE_DOUBLE => ModuleToken::Double(reader.read_double().await?),
+                RDB_MODULE_OPCODE_STRING => ModuleToken::String(reader.read_string().await?),
                 _ => {
                     bail! {Error::RedisRdbError(format!(
                         "unknown module opcode: {}", opcode
                     ))}
                 }
-            }
+            };
+            tokens.push(token);
             opcode = reader.read_length().await?;
         }
+        Ok(tokens)
+    }
+
+    fn decode_json(encver: u64, tokens: &mut ModuleTokens) -> anyhow::Result<ModuleValue> {
+        let json = match encver {
+            0 => Self::decode_json_node(tokens)?.to_string(),
+            // RedisJSON 2.x saves the serialized json
+            2 | 3 => String::from_utf8(tokens.string()?.bytes)?,
+            _ => bail! {Error::RedisRdbError(format!(
+                "unsupported encver of {}: {}", MODULE_JSON, encver
+            ))},
+        };
+        tokens.finish()?;
+        Ok(ModuleValue::Json(json))
+    }
+
+    fn decode_json_node(tokens: &mut ModuleTokens) -> anyhow::Result<Value> {
+        let value = match tokens.unsigned()? {
+            JSON_NODE_NULL => Value::Null,
+            JSON_NODE_BOOLEAN => Value::Bool(tokens.string()?.bytes.first() == Some(&b'1')),
+            JSON_NODE_INTEGER => Value::Number(tokens.signed()?.into()),
+            JSON_NODE_NUMBER => Number::from_f64(tokens.double()?)
+                .map(Value::Number)
+                .unwrap_or(Value::Null),
+            JSON_NODE_STRING => Value::String(tokens.string()?.to_string()),
+            JSON_NODE_DICT => {
+                let len = tokens.unsigned()?;
+                let mut map = Map::new();
+                for _ in 0..len {
+                    let node_type = tokens.unsigned()?;
+                    if node_type != JSON_NODE_KEYVAL {
+                        bail! {Error::RedisRdbError(format!(
+                            "expect json keyval node, got: {}", node_type
+                        ))}
+                    }
+                    let key = tokens.string()?.to_string();
+                    map.insert(key, Self::decode_json_node(tokens)?);
+                }
+                Value::Object(map)
+            }
+            JSON_NODE_ARRAY => {
+                let len = tokens.unsigned()?;
+                let mut array = Vec::new();
+                for _ in 0..len {
+                    array.push(Self::decode_json_node(tokens)?);
+                }
+                Value::Array(array)
+            }
+            node_type => bail! {Error::RedisRdbError(format!(
+                "unknown json node type: {}", node_type
+            ))},
+        };
+        Ok(value)
+    }
+
+    fn decode_bloom(encver: u64, tokens: &mut ModuleTokens) -> anyhow::Result<ModuleValue> {
+        if !(BF_MIN_OPTIONS_ENC..=BF_ENCODING_VERSION).contains(&encver) {
+            bail! {Error::RedisRdbError(format!(
+                "unsupported encver of {}: {}", MODULE_BLOOM, encver
+            ))}
+        }
+
+        let mut bloom = BloomObject {
+            size: tokens.unsigned()?,
+            ..Default::default()
+        };
+        let nfilters = tokens.unsigned()?;
+        bloom.options = tokens.unsigned()?;
+        bloom.growth = if encver >= BF_MIN_GROWTH_ENC {
+            tokens.unsigned()?
+        } else {
+            2
+        };
+
+        for _ in 0..nfilters {
+            let filter = BloomFilter {
+                entries: tokens.unsigned()?,
+                error: tokens.double()?,
+                hashes: tokens.unsigned()?,
+                bpe: tokens.double()?,
+                bits: tokens.unsigned()?,
+                n2: tokens.unsigned()?,
+                data: tokens.string()?.bytes,
+                size: tokens.unsigned()?,
+            };
+            bloom.filters.push(filter);
+        }
+        tokens.finish()?;
+        Ok(ModuleValue::Bloom(bloom))
+    }
+
+    fn decode_time_series(tokens: &mut ModuleTokens) -> anyhow::Result<ModuleValue> {
+        let mut ts = TimeSeriesObject::default();
+        // key name of the series
+        tokens.string()?;
+        ts.retention_ms = tokens.unsigned()?;
+        ts.chunk_size = tokens.unsigned()?;
+        ts.uncompressed = tokens.unsigned()? & SERIES_OPT_UNCOMPRESSED != 0;
+        // last timestamp, last value
+        tokens.unsigned()?;
+        tokens.double()?;
+        let total_samples = tokens.unsigned()?;
+        ts.duplicate_policy = match tokens.unsigned()? {
+            1 => Some("BLOCK"),
+            2 => Some("LAST"),
+            3 => Some("FIRST"),
+            4 => Some("MIN"),
+            5 => Some("MAX"),
+            6 => Some("SUM"),
+            // use the default policy of target
+            _ => None,
+        }
+        .map(String::from);
+
+        // source key if the series is a compaction of another series
+        if tokens.unsigned()? != 0 {
+            tokens.string()?;
+        }
+
+        // ignore max time diff and ignore max value diff, saved by newer versions
+        if let (Some(ModuleToken::Unsigned(_)), Some(ModuleToken::Double(_))) =
+            (tokens.peek(0), tokens.peek(1))
+        {
+            tokens.unsigned()?;
+            tokens.double()?;
+        }
+
+        let labels_count = tokens.unsigned()?;
+        for _ in 0..labels_count {
+            ts.labels.push((tokens.string()?, tokens.string()?));
+        }
+
+        let chunk_tokens = if ts.uncompressed {
+            TS_UNCOMPRESSED_CHUNK_TOKENS
+        } else {
+            TS_COMPRESSED_CHUNK_TOKENS
+        };
+        let rules_count = tokens.unsigned()?;
+        if rules_count > 0 {
+            // aggregation contexts of compaction rules differ by aggregators and versions,
+            // locate chunks from the tail instead, rules are not rewritten
+            log_warn!(
+                "{} compaction rules of time series are ignored",
+                rules_count
+            );
+            tokens.pos = tokens.find_chunks(chunk_tokens)?;
+        }
+
+        let chunks_count = tokens.unsigned()?;
+        for _ in 0..chunks_count {
+            let samples = if ts.uncompressed {
+                Self::decode_uncompressed_chunk(tokens)?
+            } else {
+                Self::decode_compressed_chunk(tokens)?
+            };
+            ts.samples.extend(samples);
+        }
+        tokens.finish()?;
+
+        if ts.samples.len() as u64 != total_samples {
+            bail! {Error::RedisRdbError(format!(
+                "time series samples mismatch, expect: {}, decoded: {}",
+                total_samples,
+                ts.samples.len()
+            ))}
+        }
+        Ok(ModuleValue::TimeSeries(ts))
+    }
+
+    fn decode_uncompressed_chunk(tokens: &mut ModuleTokens) -> anyhow::Result<Vec<(u64, f64)>> {
+        // base timestamp
+        tokens.unsigned()?;
+        let num_samples = tokens.unsigned()? as usize;
+        // allocated size
+        tokens.unsigned()?;
+        let data = tokens.string()?.bytes;
+        if data.len() < num_samples * 16 {
+            bail! {Error::RedisRdbError(format!(
+                "time series chunk too short, samples: {}, bytes: {}",
+                num_samples,
+                data.len()
+            ))}
+        }
+
+        let mut samples = Vec::with_capacity(num_samples);
+        for sample in data.chunks_exact(16).take(num_samples) {
+            samples.push((
+                LittleEndian::read_u64(&sample[..8]),
+                LittleEndian::read_f64(&sample[8..]),
+            ));
+        }
+        Ok(samples)
+    }
+
+    fn decode_compressed_chunk(tokens: &mut ModuleTokens) -> anyhow::Result<Vec<(u64, f64)>> {
+        // allocated size
+        tokens.unsigned()?;
+        let count = tokens.unsigned()?;
+        let idx = tokens.unsigned()?;
+        let base_value = tokens.unsigned()?;
+        let base_timestamp = tokens.unsigned()?;
+        let prev_timestamp = tokens.unsigned()?;
+        let prev_timestamp_delta = tokens.signed()?;
+        let prev_value = tokens.unsigned()?;
+        // prev leading, prev trailing
+        tokens.unsigned()?;
+        tokens.unsigned()?;
+        let data = tokens.string()?.bytes;
+
+        let mut decoder = GorillaDecoder::new(&data, base_timestamp, base_value);
+        let samples = decoder.decode(count)?;
+
+        // the chunk header keeps the state after the last sample, which verifies the decoding
+        if decoder.bit as u64 != idx
+            || decoder.prev_timestamp != prev_timestamp
+            || decoder.prev_timestamp_delta != prev_timestamp_delta
+            || decoder.prev_value != prev_value
+        {
+            bail! {Error::RedisRdbError(
+                "time series compressed chunk verification failed".into()
+            )}
+        }
+        Ok(samples)
+    }
+}
+
+impl ModuleTokens {
+    fn next(&mut self, expect: &str) -> anyhow::Result<ModuleToken> {
+        if let Some(token) = self.tokens.get(self.pos) {
+            self.pos += 1;
+            Ok(token.clone())
+        } else {
+            bail! {Error::RedisRdbError(format!(
+                "expect module {} at {}, got end", expect, self.pos
+            ))}
+        }
+    }
+
+    fn peek(&self, offset: usize) -> Option<&ModuleToken> {
+        self.tokens.get(self.pos + offset)
+    }
+
+    fn unexpected<T>(&self, expect: &str, token: ModuleToken) -> anyhow::Result<T> {
+        bail! {Error::RedisRdbError(format!(
+            "expect module {} at {}, got {:?}", expect, self.pos - 1, token
+        ))}
+    }
+
+    fn unsigned(&mut self) -> anyhow::Result<u64> {
+        match self.next("unsigned")? {
+            ModuleToken::Unsigned(v) => Ok(v),
+            token => self.unexpected("unsigned", token),
+        }
+    }
+
+    fn signed(&mut self) -> anyhow::Result<i64> {
+        match self.next("signed")? {
+            ModuleToken::Signed(v) => Ok(v),
+            token => self.unexpected("signed", token),
+        }
+    }
+
+    fn double(&mut self) -> anyhow::Result<f64> {
+        match self.next("double")? {
+            ModuleToken::Double(v) => Ok(v),
+            ModuleToken::Float(v) => Ok(v as f64),
+            token => self.unexpected("double", token),
+        }
+    }
+
+    fn string(&mut self) -> anyhow::Result<RedisString> {
+        match self.next("string")? {
+            ModuleToken::String(v) => Ok(v),
+            token => self.unexpected("string", token),
+        }
+    }
+
+    fn finish(&self) -> anyhow::Result<()> {
+        if self.pos != self.tokens.len() {
+            bail! {Error::RedisRdbError(format!(
+                "{} module tokens left undecoded",
+                self.tokens.len() - self.pos
+            ))}
+        }
         Ok(())
     }
+
+    /// Finds the position of chunks count, chunks are the last part and each chunk has
+    /// a fixed number of tokens ending with the chunk data.
+    fn find_chunks(&self, chunk_tokens: usize) -> anyhow::Result<usize> {
+        for pos in self.pos..self.tokens.len() {
+            let ModuleToken::Unsigned(count) = self.tokens[pos] else {
+                continue;
+            };
+            let left = self.tokens.len() - pos - 1;
+            if count as usize * chunk_tokens != left {
+                continue;
+            }
+
+            let matched =
+                self.tokens[pos + 1..]
+                    .chunks(chunk_tokens)
+                    .all(|chunk| match chunk.split_last() {
+                        Some((ModuleToken::String(_), rest)) => rest.iter().all(|i| {
+                            matches!(i, ModuleToken::Unsigned(_) | ModuleToken::Signed(_))
+                        }),
+                        _ => false,
+                    });
+            if matched {
+                return Ok(pos);
+            }
+        }
+        bail! {Error::RedisRdbError(
+            "failed to locate time series chunks".into()
+        )}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn to_tokens(tokens: Vec<ModuleToken>) -> ModuleTokens {
+        ModuleTokens { tokens, pos: 0 }
+    }
+
+    fn str_token(s: &str) -> ModuleToken {
+        ModuleToken::String(s.to_string().into())
+    }
+
+    #[test]
+    fn test_module_type_name() {
+        // module ids are generated from names and encvers
+        let encode = |name: &str, encver: u64| {
+            let mut id = 0u64;
+            for c in name.chars() {
+                id = (id << 6) | MODULE_TYPE_NAME_CHAR_SET.find(c).unwrap() as u64;
+            }
+            (id << 10) | encver
+        };
+        for name in [MODULE_JSON, MODULE_BLOOM, MODULE_TIME_SERIES] {
+            let module_id = encode(name, 3);
+            assert_eq!(ModuleParser::module_type_name_by_id(module_id), name);
+            assert_eq!(module_id & 1023, 3);
+        }
+    }
+
+    #[test]
+    fn test_decode_json() {
+        let mut tokens = to_tokens(vec![str_token(r#"{"a":[1,"b"]}"#)]);
+        match ModuleParser::decode_json(3, &mut tokens).unwrap() {
+            ModuleValue::Json(json) => assert_eq!(json, r#"{"a":[1,"b"]}"#),
+            _ => panic!(),
+        }
+
+        // {"a":[1,true],"b":null} in RedisJSON 1.x
+        let mut tokens = to_tokens(vec![
+            ModuleToken::Unsigned(JSON_NODE_DICT),
+            ModuleToken::Unsigned(2),
+            ModuleToken::Unsigned(JSON_NODE_KEYVAL),
+            str_token("a"),
+            ModuleToken::Unsigned(JSON_NODE_ARRAY),
+            ModuleToken::Unsigned(2),
+            ModuleToken::Unsigned(JSON_NODE_INTEGER),
+            ModuleToken::Signed(1),
+            ModuleToken::Unsigned(JSON_NODE_BOOLEAN),
+            str_token("1"),
+            ModuleToken::Unsigned(JSON_NODE_KEYVAL),
+            str_token("b"),
+            ModuleToken::Unsigned(JSON_NODE_NULL),
+        ]);
+        match ModuleParser::decode_json(0, &mut tokens).unwrap() {
+            ModuleValue::Json(json) => assert_eq!(json, r#"{"a":[1,true],"b":null}"#),
+            _ => panic!(),
+        }
+
+        // tokens left
+        let mut tokens = to_tokens(vec![str_token("{}"), ModuleToken::Unsigned(1)]);
+        assert!(ModuleParser::decode_json(3, &mut tokens).is_err());
+    }
+
+    #[test]
+    fn test_decode_bloom() {
+        let mut tokens = to_tokens(vec![
+            ModuleToken::Unsigned(3),
+            ModuleToken::Unsigned(1),
+            ModuleToken::Unsigned(0),
+            ModuleToken::Unsigned(2),
+            ModuleToken::Unsigned(100),
+            ModuleToken::Double(0.01),
+            ModuleToken::Unsigned(7),
+            ModuleToken::Double(9.5),
+            ModuleToken::Unsigned(1024),
+            ModuleToken::Unsigned(10),
+            ModuleToken::String(vec![1u8; 128].into()),
+            ModuleToken::Unsigned(3),
+        ]);
+        match ModuleParser::decode_bloom(4, &mut tokens).unwrap() {
+            ModuleValue::Bloom(bloom) => {
+                assert_eq!(bloom.size, 3);
+                assert_eq!(bloom.growth, 2);
+                assert_eq!(bloom.filters.len(), 1);
+                assert_eq!(bloom.filters[0].hashes, 7);
+                assert_eq!(bloom.filters[0].n2, 10);
+                assert_eq!(bloom.filters[0].data.len(), 128);
+            }
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn test_decode_uncompressed_time_series() {
+        let mut data = Vec::new();
+        for (ts, value) in [(1000u64, 1.5f64), (2000, -2.0)] {
+            data.extend_from_slice(&ts.to_le_bytes());
+            data.extend_from_slice(&value.to_le_bytes());
+        }
+        let header = vec![
+            str_token("ts:1"),
+            ModuleToken::Unsigned(3600000),
+            ModuleToken::Unsigned(4096),
+            ModuleToken::Unsigned(SERIES_OPT_UNCOMPRESSED),
+            ModuleToken::Unsigned(2000),
+            ModuleToken::Double(-2.0),
+            ModuleToken::Unsigned(2),
+            ModuleToken::Unsigned(2),
+            ModuleToken::Unsigned(0),
+            ModuleToken::Unsigned(1),
+            str_token("sensor"),
+            str_token("a"),
+        ];
+        let chunks = vec![
+            ModuleToken::Unsigned(1),
+            ModuleToken::Unsigned(1000),
+            ModuleToken::Unsigned(2),
+            ModuleToken::Unsigned(data.len() as u64),
+            ModuleToken::String(data.into()),
+        ];
+
+        // without compaction rules
+        let mut tokens = header.clone();
+        tokens.push(ModuleToken::Unsigned(0));
+        tokens.extend(chunks.clone());
+        let ModuleValue::TimeSeries(ts) =
+            ModuleParser::decode_time_series(&mut to_tokens(tokens)).unwrap()
+        else {
+            panic!()
+        };
+        assert_eq!(ts.retention_ms, 3600000);
+        assert!(ts.uncompressed);
+        assert_eq!(ts.duplicate_policy, Some("LAST".to_string()));
+        assert_eq!(ts.labels.len(), 1);
+        assert_eq!(ts.samples, vec![(1000, 1.5), (2000, -2.0)]);
+
+        // with a compaction rule, whose context is skipped
+        let mut tokens = header;
+        tokens.extend(vec![
+            ModuleToken::Unsigned(1),
+            str_token("ts:1:avg"),
+            ModuleToken::Unsigned(60000),
+            ModuleToken::Unsigned(0),
+            ModuleToken::Unsigned(1),
+            ModuleToken::Unsigned(0),
+            ModuleToken::Double(3.0),
+            ModuleToken::Double(2.0),
+        ]);
+        tokens.extend(chunks);
+        let ModuleValue::TimeSeries(ts) =
+            ModuleParser::decode_time_series(&mut to_tokens(tokens)).unwrap()
+        else {
+            panic!()
+        };
+        assert_eq!(ts.samples.len(), 2);
+    }
 }
@@ -7,17 +7,17 @@ use dt_common::meta::redis::{redis_entry::RedisEntry, redis_object::RedisCmd};
 use dt_common::{error::Error, log_debug, log_info};
 
 const K_FLAG_SLOT_INFO: u8 = 0xf4; // (244) (Redis 7.4+) RDB_OPCODE_SLOT_INFO: slot info
-const _K_FLAG_FUNCTION2: u8 = 0xf5; // (245) function library data
-const _K_FLAG_FUNCTION: u8 = 0xf6; // (246) old function library data for 7.0 rc1 and rc2
+pub const K_FLAG_FUNCTION2: u8 = 0xf5; // (245) function library data
+const K_FLAG_FUNCTION: u8 = 0xf6; // (246) old function library data for 7.0 rc1 and rc2
 const K_FLAG_MODULE_AUX: u8 = 0xf7; // (247) Module auxiliary data.
 const K_FLAG_IDLE: u8 = 0xf8; // (248) LRU idle time.
 const K_FLAG_FREQ: u8 = 0xf9; // (249) LFU frequency.
-const K_FLAG_AUX: u8 = 0xfa; // (250) RDB aux field.
+pub const K_FLAG_AUX: u8 = 0xfa; // (250) RDB aux field.
 const K_FLAG_RESIZE_DB: u8 = 0xfb; // (251) Hash table resize hint.
-const K_FLAG_EXPIRE_MS: u8 = 0xfc; // (252) Expire time in milliseconds.
+pub const K_FLAG_EXPIRE_MS: u8 = 0xfc; // (252) Expire time in milliseconds.
 const K_FLAG_EXPIRE: u8 = 0xfd; // (253) Old expire time in seconds.
-const K_FLAG_SELECT: u8 = 0xfe; // (254) DB number of the following keys.
-const K_EOF: u8 = 0xff; // (255) End of the RDB file.
+pub const K_FLAG_SELECT: u8 = 0xfe; // (254) DB number of the following keys.
+pub const K_EOF: u8 = 0xff; // (255) End of the RDB file.
 
 const RDB_MODULE_OPCODE_EOF: u64 = 0; // End of module value.
 const RDB_MODULE_OPCODE_SINT: u64 = 1; // Signed integer.
@@ -62,6 +62,32 @@ impl RdbParser<'_> {
                 self.reader.read_length().await?; // slot size
                 self.reader.read_length().await?; // expires slot size
             }
+            K_FLAG_FUNCTION2 => {
+                // the source code of a library, which starts with: #!<engine> name=<library>
+                let code = self.reader.read_string().await?;
+                let mut cmd = RedisCmd::new();
+                cmd.add_str_arg("function");
+                cmd.add_str_arg("load");
+                cmd.add_str_arg("replace");
+                cmd.add_redis_arg(&code);
+                log_info!(
+                    "RDB function library: {}",
+                    code.to_string().lines().next().unwrap_or_default()
+                );
+
+                let mut entry = RedisEntry::new();
+                entry.is_base = true;
+                entry.db_id = self.now_db_id;
+                entry.cmd = cmd;
+                return Ok(Some(entry));
+            }
+
+            K_FLAG_FUNCTION => {
+                bail! {Error::RedisRdbError(
+                    "function library of redis 7.0 rc1/rc2 is not supported".into()
+                )}
+            }
+
             K_FLAG_MODULE_AUX => {
                 let module_id = self.reader.read_length().await?; // module id
                 let module_name = ModuleParser::module_type_name_by_id(module_id);
@@ -211,3 +237,71 @@ impl RdbParser<'_> {
         Ok(None)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::*;
+
+    fn push_string(buf: &mut Vec<u8>, s: &str) {
+        // 6 bit length
+        buf.push(s.len() as u8);
+        buf.extend_from_slice(s.as_bytes());
+    }
+
+    #[tokio::test]
+    async fn test_load_function_library() {
+        let code = "#!lua name=lib\nreturn 1";
+
+        let mut data = b"REDIS0010".to_vec();
+        data.push(K_FLAG_FUNCTION2);
+        push_string(&mut data, code);
+        // string key
+        data.push(0);
+        push_string(&mut data, "k1");
+        push_string(&mut data, "v1");
+        data.push(K_EOF);
+        data.extend_from_slice(&[0; 8]);
+
+        let mut cursor = Cursor::new(data.as_slice());
+        let mut stream_reader: Box<&mut (dyn StreamReader + Send)> = Box::new(&mut cursor);
+        let reader = RdbReader {
+            conn: &mut stream_reader,
+            rdb_length: data.len(),
+            position: 0,
+            copy_raw: false,
+            raw_bytes: Vec::new(),
+        };
+        let mut parser = RdbParser {
+            reader,
+            repl_stream_db_id: 0,
+            now_db_id: 0,
+            expire_ms: 0,
+            idle: 0,
+            freq: 0,
+            is_end: false,
+        };
+
+        assert_eq!(parser.load_meta().await.unwrap(), "0010");
+        let mut entries = Vec::new();
+        while !parser.is_end {
+            if let Some(entry) = parser.load_entry().await.unwrap() {
+                entries.push(entry);
+            }
+        }
+        assert_eq!(entries.len(), 2);
+
+        // the library is loaded by FUNCTION LOAD REPLACE, not as a key
+        assert!(entries[0].is_base);
+        assert!(!entries[0].is_base_key());
+        assert!(entries[0].is_global_cmd());
+        assert_eq!(
+            entries[0].cmd.args,
+            RedisCmd::from_str_args(&["function", "load", "replace", code]).args
+        );
+
+        assert!(entries[1].is_base_key());
+        assert_eq!(entries[1].key.as_bytes(), b"k1");
+    }
+}
@@ -0,0 +1,75 @@
+use anyhow::bail;
+use async_trait::async_trait;
+use redis::{Connection, Value};
+
+use dt_common::{
+    error::Error,
+    log_info,
+    meta::{dt_data::DtData, position::Position, redis::redis_entry::RedisEntry},
+    utils::redis_util::RedisUtil,
+};
+
+use crate::{
+    check_log::check_log::CheckLog,
+    extractor::{base_check_extractor::BaseCheckExtractor, base_extractor::BaseExtractor},
+    sinker::redis::redis_checker::RedisChecker,
+    BatchCheckExtractor, Extractor,
+};
+
+pub struct RedisCheckExtractor {
+    pub base_extractor: BaseExtractor,
+    pub conn: Connection,
+    pub check_log_dir: String,
+    pub batch_size: usize,
+}
+
+#[async_trait]
+impl Extractor for RedisCheckExtractor {
+    async fn extract(&mut self) -> anyhow::Result<()> {
+        log_info!("RedisCheckExtractor starts");
+        let base_check_extractor = BaseCheckExtractor {
+            check_log_dir: self.check_log_dir.clone(),
+            batch_size: self.batch_size,
+        };
+        base_check_extractor.extract(self).await?;
+        self.base_extractor.wait_task_finish().await
+    }
+}
+
+#[async_trait]
+impl BatchCheckExtractor for RedisCheckExtractor {
+    async fn batch_extract(&mut self, check_logs: &[CheckLog]) -> anyhow::Result<()> {
+        // logs in the same batch share the same schema, which is the db id
+        let db = &check_logs[0].schema;
+        let cmd = ["SELECT", db];
+        if Value::Okay != RedisUtil::send_cmd(&mut self.conn, &cmd)? {
+            bail! {Error::RedisResultError(format!("\"SELECT {}\" failed", db))}
+        }
+        let db_id: i64 = db.parse()?;
+
+        for check_log in check_logs.iter() {
+            let Some(key) = RedisChecker::parse_key_col_values(&check_log.id_col_values)? else {
+                continue;
+            };
+
+            // the key may have been deleted or expired in source since the last check
+            let value = match RedisUtil::get_object(&mut self.conn, &key)? {
+                Some(value) => value,
+                None => continue,
+            };
+            let pttl = RedisUtil::get_pttl(&mut self.conn, &key)?;
+
+            let mut entry = RedisEntry::new();
+            entry.is_base = true;
+            entry.db_id = db_id;
+            entry.key = key;
+            entry.value = value;
+            entry.expire_ms = pttl.max(0);
+
+            self.base_extractor
+                .push_dt_data(DtData::Redis { entry }, Position::None)
+                .await?;
+        }
+        Ok(())
+    }
+}
@@ -18,6 +18,7 @@ use dt_common::config::config_enums::{DbType, ExtractType};
 use dt_common::config::config_token_parser::ConfigTokenParser;
 use dt_common::meta::dt_data::DtData;
 use dt_common::meta::position::Position;
+use dt_common::meta::redis::command::key_parser::KeyParser;
 use dt_common::meta::redis::redis_entry::RedisEntry;
 use dt_common::meta::redis::redis_object::RedisCmd;
 use dt_common::meta::syncer::Syncer;
@@ -41,6 +42,9 @@ pub struct RedisPsyncExtractor {
     pub filter: RdbFilter,
     pub resumer: CdcResumer,
     pub extract_type: ExtractType,
+    // address of the cluster node, empty if the source is not a cluster
+    pub node_address: String,
+    pub key_parser: KeyParser,
 }
 
 #[async_trait]
@@ -112,10 +116,19 @@ impl RedisPsyncExtractor {
                 let tokens: Vec<&str> = s.split_whitespace().collect();
                 self.repl_id = tokens[1].to_string();
                 self.repl_offset = tokens[2].parse::<u64>()?;
-            } else if s != "CONTINUE" {
-                bail! {Error::ExtractorError(
-                    "PSYNC command response is NOT CONTINUE".into(),
-                )}
+            } else {
+                // +CONTINUE [new_repl_id], the new repl_id is returned if the master has changed,
+                // e.g. a replica promoted to master after failover continues with the old repl_id
+                let tokens: Vec<&str> = s.split_whitespace().collect();
+                if tokens.first() != Some(&"CONTINUE") {
+                    bail! {Error::ExtractorError(
+                        "PSYNC command response is NOT CONTINUE".into(),
+                    )}
+                }
+                if let Some(new_repl_id) = tokens.get(1) {
+                    log_info!("repl_id changed from {} to {}", self.repl_id, new_repl_id);
+                    self.repl_id = new_repl_id.to_string();
+                }
             }
         } else {
             bail! {Error::ExtractorError(
@@ -176,10 +189,13 @@ impl RedisPsyncExtractor {
         loop {
             if let Some(entry) = parser.load_entry().await? {
                 self.now_db_id = entry.db_id;
+                // function libraries are synced even if rdb is discarded,
+                // otherwise FCALL in aof fails in target
                 if matches!(
                     self.extract_type,
                     ExtractType::Snapshot | ExtractType::SnapshotAndCdc
-                ) {
+                ) || entry.is_global_cmd()
+                {
                     if let Some(data_marker) = &self.base_extractor.data_marker {
                         if data_marker.is_redis_marker_info(&entry) {
                             continue;
@@ -212,6 +228,7 @@ impl RedisPsyncExtractor {
             repl_offset: self.repl_offset + 1,
             now_db_id: parser.now_db_id,
             timestamp: String::new(),
+            address: self.node_address.clone(),
         };
         log_position!("start_aof_position | {}", position.to_string());
         Ok(())
@@ -257,7 +274,7 @@ impl RedisPsyncExtractor {
             }
 
             self.repl_offset += n as u64;
-            let cmd = self.handle_redis_value(value).await?;
+            let mut cmd = self.handle_redis_value(value).await?;
             log_debug!("received cmd: [{}]", cmd);
 
             if !cmd.args.is_empty() {
@@ -285,6 +302,7 @@ impl RedisPsyncExtractor {
                     repl_offset: self.repl_offset,
                     now_db_id: self.now_db_id,
                     timestamp: heartbeat_timestamp.clone(),
+                    address: self.node_address.clone(),
                 };
 
                 // transaction begin
@@ -323,11 +341,31 @@ impl RedisPsyncExtractor {
                     continue;
                 }
 
+                if cmd_name == "function" {
+                    Self::add_function_load_replace(&mut cmd);
+                }
+
                 // build entry and push it to buffer
                 let mut entry = RedisEntry::new();
                 entry.cmd = cmd;
                 entry.db_id = self.now_db_id;
 
+                // keys are needed by key filter and key router
+                if !Self::is_data_marker(&self.base_extractor, &entry)
+                    && (self.filter.has_key_filter()
+                        || !self.base_extractor.router.key_map.is_empty())
+                {
+                    if let Err(err) = entry.cmd.parse_keys(&self.key_parser) {
+                        log_warn!(
+                            "failed to parse keys, cmd: [{}], error: {:?}",
+                            entry.cmd,
+                            err
+                        );
+                    } else if self.filter_cmd_keys(&mut entry.cmd)? {
+                        continue;
+                    }
+                }
+
                 Self::push_to_buf(&mut self.base_extractor, &mut self.filter, entry, position)
                     .await?;
             }
@@ -438,20 +476,127 @@ impl RedisPsyncExtractor {
         Ok(())
     }
 
+    fn is_data_marker(base_extractor: &BaseExtractor, entry: &RedisEntry) -> bool {
+        base_extractor
+            .data_marker
+            .as_ref()
+            .is_some_and(|i| i.is_redis_marker_info(entry))
+    }
+
+    /// return true if the whole command is filtered, keys of the command should have been parsed
+    fn filter_cmd_keys(&self, cmd: &mut RedisCmd) -> anyhow::Result<bool> {
+        let filtered: Vec<bool> = cmd
+            .key_indexes
+            .iter()
+            // key_indexes are 1-based
+            .map(|i| self.filter.filter_key(&cmd.args[i - 1]))
+            .collect();
+        if !filtered.contains(&true) {
+            return Ok(false);
+        }
+        if !filtered.contains(&false) {
+            return Ok(true);
+        }
+
+        // only part of the keys are filtered, remove them if the command allows
+        let key_step = match cmd.name.as_str() {
+            "DEL" | "UNLINK" => 1,
+            // MSET key value [key value ...]
+            "MSET" | "MSETNX" => 2,
+            _ => {
+                log_warn!(
+                    "only part of the keys are filtered, the command is kept, cmd: [{}]",
+                    cmd
+                );
+                return Ok(false);
+            }
+        };
+
+        let mut removed_indexes = Vec::new();
+        for (i, key_index) in cmd.key_indexes.iter().enumerate() {
+            if filtered[i] {
+                removed_indexes.extend(key_index - 1..key_index - 1 + key_step);
+            }
+        }
+        let args = std::mem::take(&mut cmd.args);
+        cmd.args = args
+            .into_iter()
+            .enumerate()
+            .filter(|(i, _)| !removed_indexes.contains(i))
+            .map(|(_, arg)| arg)
+            .collect();
+        cmd.parse_keys(&self.key_parser)?;
+        Ok(false)
+    }
+
+    /// Libraries may exist in target if the task is resumed, FUNCTION LOAD fails then
+    fn add_function_load_replace(cmd: &mut RedisCmd) {
+        if cmd.get_str_arg(1).eq_ignore_ascii_case("load")
+            && !cmd.get_str_arg(2).eq_ignore_ascii_case("replace")
+        {
+            cmd.args.insert(2, b"REPLACE".to_vec());
+        }
+    }
+
     pub async fn push_to_buf(
         base_extractor: &mut BaseExtractor,
         filter: &mut RdbFilter,
-        mut entry: RedisEntry,
+        entry: RedisEntry,
         position: Position,
     ) -> anyhow::Result<()> {
-        // currently only support db filter
-        if filter.filter_schema(&entry.db_id.to_string()) {
+        if !entry.is_global_cmd() && filter.filter_schema(&entry.db_id.to_string()) {
             return Ok(());
         }
 
+        // data marker is discarded later, it should be neither filtered nor routed by keys
+        let is_data_marker = Self::is_data_marker(base_extractor, &entry);
+
+        // keys of aof commands are filtered before
+        if entry.is_base_key() && !is_data_marker && filter.filter_key(entry.key.as_bytes()) {
+            return Ok(());
+        }
+
+        let mut entry = if is_data_marker {
+            entry
+        } else {
+            base_extractor.router.route_redis_entry(entry)
+        };
         entry.data_size = entry.get_data_malloc_size();
         base_extractor
             .push_dt_data(DtData::Redis { entry }, position)
             .await
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_add_function_load_replace() {
+        let code = "#!lua name=mylib\nredis.register_function('f', function() return 1 end)";
+
+        let mut cmd = RedisCmd::from_str_args(&["FUNCTION", "LOAD", code]);
+        RedisPsyncExtractor::add_function_load_replace(&mut cmd);
+        assert_eq!(
+            cmd.args,
+            RedisCmd::from_str_args(&["FUNCTION", "LOAD", "REPLACE", code]).args
+        );
+
+        // REPLACE is not added twice
+        let mut cmd = RedisCmd::from_str_args(&["function", "load", "replace", code]);
+        RedisPsyncExtractor::add_function_load_replace(&mut cmd);
+        assert_eq!(
+            cmd.args,
+            RedisCmd::from_str_args(&["function", "load", "replace", code]).args
+        );
+
+        // other FUNCTION subcommands are kept
+        let mut cmd = RedisCmd::from_str_args(&["FUNCTION", "DELETE", "mylib"]);
+        RedisPsyncExtractor::add_function_load_replace(&mut cmd);
+        assert_eq!(
+            cmd.args,
+            RedisCmd::from_str_args(&["FUNCTION", "DELETE", "mylib"]).args
+        );
+    }
+}
@@ -1,36 +1,85 @@
-use anyhow::bail;
+use std::{
+    collections::HashSet,
+    io::Cursor,
+    sync::atomic::{AtomicBool, Ordering},
+    sync::Arc,
+    time::Duration,
+};
+
+use anyhow::{bail, Context};
 use async_trait::async_trait;
-use dt_common::{error::Error, log_info, rdb_filter::RdbFilter};
+use dt_common::{error::Error, log_error, log_info, log_warn, rdb_filter::RdbFilter};
 use dt_common::{
     meta::{
         dt_data::DtData,
         position::Position,
         redis::{
+            command::key_parser::KeyParser,
             redis_entry::RedisEntry,
-            redis_object::{RedisObject, RedisString},
+            redis_object::{RedisCmd, RedisObject, RedisString},
             redis_statistic_type::RedisStatisticType,
+            redis_write_method::RedisWriteMethod,
         },
     },
     utils::redis_util::RedisUtil,
 };
 use redis::{Connection, Value};
+use tokio::sync::{mpsc, oneshot};
+
+use crate::{
+    extractor::{
+        base_extractor::BaseExtractor,
+        redis::{
+            rdb::{entry_parser::entry_parser::EntryParser, reader::rdb_reader::RdbReader},
+            redis_psync_extractor::RedisPsyncExtractor,
+            StreamReader,
+        },
+    },
+    Extractor,
+};
 
-use crate::{extractor::base_extractor::BaseExtractor, Extractor};
+const KEYEVENT_CHANNEL_PATTERN: &str = "__keyevent@*__:*";
+// max number of key events buffered while scanning, the task fails if exceeded
+const KEY_EVENT_BUFFER_SIZE: usize = 100_000;
 
 pub struct RedisScanExtractor {
     pub base_extractor: BaseExtractor,
-    pub statistic_type: RedisStatisticType,
+    // None to migrate keys, otherwise to analyze keys
+    pub statistic_type: Option<RedisStatisticType>,
+    // restore: read keys by DUMP, rewrite: read keys by type-specific commands
+    pub method: RedisWriteMethod,
     pub scan_count: u64,
+    // sync changes by keyspace notifications after scan, only used to migrate keys
+    pub keyspace_notify: bool,
+    pub url: String,
     pub conn: Connection,
+    pub now_db_id: i64,
+    pub key_parser: KeyParser,
     pub filter: RdbFilter,
 }
 
+// (db_id, event, key)
+type KeyEvent = (i64, String, Vec<u8>);
+
+struct KeyEventReceiver {
+    receiver: mpsc::Receiver<KeyEvent>,
+    // set if events were dropped since the buffer was full
+    overflowed: Arc<AtomicBool>,
+}
+
 #[async_trait]
 impl Extractor for RedisScanExtractor {
     async fn extract(&mut self) -> anyhow::Result<()> {
         log_info!("RedisScanExtractor starts");
 
-        if let RedisStatisticType::HotKey = self.statistic_type {
+        let statistic_type = self.statistic_type.clone();
+        if let Some(RedisStatisticType::Continuous) = statistic_type {
+            bail! {Error::ConfigError(
+                "statistic_type continuous is driven by psync, extract_type should not be scan".into()
+            )}
+        }
+
+        if let Some(RedisStatisticType::HotKey) = statistic_type {
             let maxmemory_policy = self.get_maxmemory_policy().await?;
             if maxmemory_policy != "allkeys-lfu" {
                 bail! {Error::MetadataError(format!(
@@ -40,30 +89,53 @@ impl Extractor for RedisScanExtractor {
             }
         }
 
+        // subscribe before scan, so that changes during scan are not missed
+        let key_events = if statistic_type.is_none() && self.keyspace_notify {
+            Some(self.subscribe_key_events().await?)
+        } else {
+            None
+        };
+
         let count = &self.scan_count.to_string();
         for db in self.get_dbs().await? {
             if self.filter.filter_schema(&db) {
                 continue;
             }
 
-            // select db
-            let cmd = ["SELECT", &db];
-            if Value::Okay != RedisUtil::send_cmd(&mut self.conn, &cmd)? {
-                bail! {Error::RedisResultError(format!("\"SELECT {}\" failed", db))}
-            }
+            let db_id: i64 = db.parse()?;
+            self.select_db(db_id)?;
 
             // scan
-            let db_id: i64 = db.parse()?;
             let mut cursor = 0;
             loop {
                 let cmd = ["SCAN", &cursor.to_string(), "COUNT", count];
-                let result = self.query(&cmd).await?;
+                let mut result =
+                    RedisUtil::parse_result_as_bytes(RedisUtil::send_cmd(&mut self.conn, &cmd)?)?;
+                if result.is_empty() {
+                    bail! {Error::RedisResultError(format!("\"SCAN {}\" failed", cursor))}
+                }
 
-                cursor = result[0].parse()?;
-                for key in result.iter().skip(1) {
-                    match self.statistic_type {
-                        RedisStatisticType::HotKey => self.analyze_hot_key(db_id, key).await?,
-                        RedisStatisticType::BigKey => self.analyze_big_key(db_id, key).await?,
+                let keys = result.split_off(1);
+                cursor = String::from_utf8_lossy(&result[0]).parse()?;
+                for key in keys {
+                    if self.filter.filter_key(&key) {
+                        continue;
+                    }
+                    match statistic_type {
+                        Some(RedisStatisticType::HotKey) => {
+                            let key = String::from_utf8_lossy(&key);
+                            self.analyze_hot_key(db_id, &key).await?
+                        }
+                        Some(RedisStatisticType::BigKey) => {
+                            let key = String::from_utf8_lossy(&key);
+                            self.analyze_big_key(db_id, &key).await?
+                        }
+                        Some(RedisStatisticType::Continuous) => {}
+                        None => {
+                            if let Some(entry) = self.read_entry(db_id, key.into()).await? {
+                                self.push_entry(entry, Position::None).await?;
+                            }
+                        }
                     }
                 }
 
@@ -73,6 +145,11 @@ impl Extractor for RedisScanExtractor {
             }
         }
 
+        if let Some(key_events) = key_events {
+            log_info!("end scanning keys, start syncing changes by keyspace notifications");
+            self.sync_key_events(key_events).await?;
+        }
+
         self.base_extractor.wait_task_finish().await
     }
 }
@@ -90,6 +167,20 @@ impl RedisScanExtractor {
         Ok(dbs)
     }
 
+    fn select_db(&mut self, db_id: i64) -> anyhow::Result<()> {
+        if db_id == self.now_db_id {
+            return Ok(());
+        }
+
+        let db = db_id.to_string();
+        let cmd = ["SELECT", &db];
+        if Value::Okay != RedisUtil::send_cmd(&mut self.conn, &cmd)? {
+            bail! {Error::RedisResultError(format!("\"SELECT {}\" failed", db))}
+        }
+        self.now_db_id = db_id;
+        Ok(())
+    }
+
     async fn get_maxmemory_policy(&mut self) -> anyhow::Result<String> {
         let cmd = ["CONFIG", "GET", "maxmemory-policy"];
         let result = self.query(&cmd).await?;
@@ -134,8 +225,305 @@ impl RedisScanExtractor {
             .await
     }
 
+    /// read the key in the selected db as a snapshot entry, None if the key does not exist.
+    /// the value and the ttl are read by separate commands, which is not atomic
+    async fn read_entry(
+        &mut self,
+        db_id: i64,
+        key: RedisString,
+    ) -> anyhow::Result<Option<RedisEntry>> {
+        let mut entry = RedisEntry::new();
+        entry.is_base = true;
+        entry.db_id = db_id;
+        entry.key = key;
+
+        if let RedisWriteMethod::Rewrite = self.method {
+            match RedisUtil::get_object(&mut self.conn, &entry.key)? {
+                None => return Ok(None),
+                // values of module types are not read by commands, fall back to DUMP
+                Some(RedisObject::Module(_)) => {}
+                Some(value) => entry.value = value,
+            }
+        }
+
+        if let RedisObject::Unknown = entry.value {
+            let Some(payload) = RedisUtil::get_dump(&mut self.conn, &entry.key)? else {
+                return Ok(None);
+            };
+            Self::parse_dump(&mut entry, payload).await?;
+        }
+
+        match RedisUtil::get_pttl(&mut self.conn, &entry.key)? {
+            -2 => return Ok(None),
+            -1 => {}
+            // the key expires right now
+            pttl => entry.expire_ms = pttl.max(1),
+        }
+        Ok(Some(entry))
+    }
+
+    /// DUMP payload: type byte, value in rdb encoding, rdb version (2 bytes), crc64 (8 bytes)
+    async fn parse_dump(entry: &mut RedisEntry, payload: Vec<u8>) -> anyhow::Result<()> {
+        if payload.len() < 11 {
+            bail! {Error::RedisRdbError(format!(
+                "invalid DUMP payload of key: {}, length: {}",
+                entry.key,
+                payload.len()
+            ))}
+        }
+
+        let type_byte = payload[0];
+        let raw_bytes = &payload[1..payload.len() - 10];
+        let mut cursor = Cursor::new(raw_bytes);
+        let mut stream_reader: Box<&mut (dyn StreamReader + Send)> = Box::new(&mut cursor);
+        let mut reader = RdbReader {
+            conn: &mut stream_reader,
+            rdb_length: raw_bytes.len(),
+            position: 0,
+            copy_raw: false,
+            raw_bytes: Vec::new(),
+        };
+
+        entry.value = EntryParser::parse_object(&mut reader, type_byte, entry.key.clone())
+            .await
+            .with_context(|| {
+                format!(
+                    "failed to parse DUMP payload of key: {}, type_byte: {}",
+                    entry.key, type_byte
+                )
+            })?;
+        entry.value_type_byte = type_byte;
+        entry.raw_bytes = raw_bytes.to_vec();
+        Ok(())
+    }
+
+    async fn push_entry(&mut self, entry: RedisEntry, position: Position) -> anyhow::Result<()> {
+        RedisPsyncExtractor::push_to_buf(
+            &mut self.base_extractor,
+            &mut self.filter,
+            entry,
+            position,
+        )
+        .await
+    }
+
+    async fn subscribe_key_events(&mut self) -> anyhow::Result<KeyEventReceiver> {
+        // managed services may forbid CONFIG, then the config is not checked
+        match self
+            .query(&["CONFIG", "GET", "notify-keyspace-events"])
+            .await
+        {
+            Ok(result) if result.len() > 1 => {
+                if !result[1].contains('E') {
+                    bail! {Error::ConfigError(format!(
+                        "notify-keyspace-events is [{}], should contain E and the event classes to sync, E.g. EA",
+                        result[1]
+                    ))}
+                }
+            }
+            _ => log_warn!(
+                "can not get notify-keyspace-events, make sure keyevent notifications are enabled"
+            ),
+        }
+
+        let conn = redis::Client::open(self.url.as_str())?
+            .get_connection()
+            .with_context(|| format!("can not connect redis: [{}]", self.url))?;
+        // to check shut_down periodically
+        conn.set_read_timeout(Some(Duration::from_secs(1)))?;
+
+        let (sender, receiver) = mpsc::channel(KEY_EVENT_BUFFER_SIZE);
+        let (ready_sender, ready_receiver) = oneshot::channel();
+        let shut_down = self.base_extractor.shut_down.clone();
+        let overflowed = Arc::new(AtomicBool::new(false));
+        let overflowed_clone = overflowed.clone();
+        // the blocking connection runs in its own thread
+        std::thread::spawn(move || {
+            Self::receive_key_events(conn, sender, ready_sender, shut_down, overflowed_clone)
+        });
+
+        ready_receiver
+            .await?
+            .with_context(|| format!("failed to psubscribe {}", KEYEVENT_CHANNEL_PATTERN))?;
+        log_info!("psubscribed {}", KEYEVENT_CHANNEL_PATTERN);
+        Ok(KeyEventReceiver {
+            receiver,
+            overflowed,
+        })
+    }
+
+    fn receive_key_events(
+        mut conn: Connection,
+        sender: mpsc::Sender<KeyEvent>,
+        ready_sender: oneshot::Sender<redis::RedisResult<()>>,
+        shut_down: Arc<AtomicBool>,
+        overflowed: Arc<AtomicBool>,
+    ) {
+        let mut pubsub = conn.as_pubsub();
+        let subscribed = pubsub.psubscribe(KEYEVENT_CHANNEL_PATTERN);
+        let failed = subscribed.is_err();
+        if ready_sender.send(subscribed).is_err() || failed {
+            return;
+        }
+
+        while !shut_down.load(Ordering::Acquire) {
+            match pubsub.get_message() {
+                Ok(msg) => {
+                    let Some(event) =
+                        Self::parse_key_event(msg.get_channel_name(), msg.get_payload_bytes())
+                    else {
+                        continue;
+                    };
+                    match sender.try_send(event) {
+                        Ok(_) => {}
+                        Err(mpsc::error::TrySendError::Full(_)) => {
+                            overflowed.store(true, Ordering::Release);
+                            break;
+                        }
+                        Err(mpsc::error::TrySendError::Closed(_)) => break,
+                    }
+                }
+                Err(err) if err.is_timeout() => {}
+                Err(err) => {
+                    log_error!("keyspace notification connection broken: {}", err);
+                    break;
+                }
+            }
+        }
+    }
+
+    /// channel: __keyevent@<db>__:<event>, payload: key
+    fn parse_key_event(channel: &str, payload: &[u8]) -> Option<KeyEvent> {
+        let (db, event) = channel.strip_prefix("__keyevent@")?.split_once("__:")?;
+        Some((db.parse().ok()?, event.to_string(), payload.to_vec()))
+    }
+
+    async fn sync_key_events(&mut self, mut key_events: KeyEventReceiver) -> anyhow::Result<()> {
+        let batch_size = self.scan_count as usize;
+        loop {
+            // fail as soon as events are dropped, instead of after the buffered ones are synced
+            if key_events.overflowed.load(Ordering::Acquire) {
+                bail! {Error::ExtractorError(format!(
+                    "more than {} key events are buffered, changes are lost, the source changes faster than they are synced",
+                    KEY_EVENT_BUFFER_SIZE
+                ))}
+            }
+
+            let first = match tokio::time::timeout(
+                Duration::from_secs(1),
+                key_events.receiver.recv(),
+            )
+            .await
+            {
+                Ok(Some(event)) => event,
+                Ok(None) | Err(_) if self.base_extractor.shut_down.load(Ordering::Acquire) => {
+                    return Ok(())
+                }
+                Ok(None) => {
+                    bail! {Error::ExtractorError(
+                        "keyspace notifications stopped, changes may be lost".into()
+                    )}
+                }
+                Err(_) => continue,
+            };
+
+            // a key changed many times in a batch is read only once, since the latest value is read anyway
+            let mut keys = HashSet::from([(first.0, first.2)]);
+            while keys.len() < batch_size {
+                match key_events.receiver.try_recv() {
+                    Ok((db_id, _, key)) => {
+                        keys.insert((db_id, key));
+                    }
+                    Err(_) => break,
+                }
+            }
+
+            for (db_id, key) in keys {
+                if self.filter.filter_key(&key) {
+                    continue;
+                }
+                self.sync_key(db_id, key.into()).await?;
+            }
+        }
+    }
+
+    async fn sync_key(&mut self, db_id: i64, key: RedisString) -> anyhow::Result<()> {
+        self.select_db(db_id)?;
+        let position = Position::Redis {
+            repl_id: String::new(),
+            repl_port: 0,
+            repl_offset: 0,
+            now_db_id: db_id,
+            timestamp: Position::format_timestamp_millis(chrono::Utc::now().timestamp_millis()),
+            address: String::new(),
+        };
+
+        let entry = self.read_entry(db_id, key.clone()).await?;
+        // restore replaces the key, while rewrite appends to it
+        if entry.is_none() || matches!(self.method, RedisWriteMethod::Rewrite) {
+            let mut del_entry = RedisEntry::new();
+            del_entry.db_id = db_id;
+            del_entry.key = key.clone();
+            del_entry.cmd = RedisCmd::from_str_args(&["del"]);
+            del_entry.cmd.add_redis_arg(&key);
+            del_entry.cmd.parse_keys(&self.key_parser)?;
+            self.push_entry(del_entry, position.clone()).await?;
+        }
+
+        if let Some(entry) = entry {
+            self.push_entry(entry, position).await?;
+        }
+        Ok(())
+    }
+
     async fn query(&mut self, cmd: &[&str]) -> anyhow::Result<Vec<String>> {
         let result = RedisUtil::send_cmd(&mut self.conn, cmd)?;
         RedisUtil::parse_result_as_string(result)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_key_event() {
+        assert_eq!(
+            RedisScanExtractor::parse_key_event("__keyevent@3__:hset", b"user:1"),
+            Some((3, "hset".to_string(), b"user:1".to_vec()))
+        );
+        assert_eq!(
+            RedisScanExtractor::parse_key_event("__keyspace@3__:user:1", b"hset"),
+            None
+        );
+        assert_eq!(
+            RedisScanExtractor::parse_key_event("__keyevent@x__:hset", b"user:1"),
+            None
+        );
+    }
+
+    #[tokio::test]
+    async fn test_parse_dump() {
+        // DUMP of a string "v1", rdb version 11
+        let mut payload = vec![0u8, 2, b'v', b'1'];
+        payload.extend_from_slice(&11u16.to_le_bytes());
+        payload.extend_from_slice(&[0; 8]);
+
+        let mut entry = RedisEntry::new();
+        entry.key = RedisString::from("k1".to_string());
+        RedisScanExtractor::parse_dump(&mut entry, payload)
+            .await
+            .unwrap();
+        assert_eq!(entry.value_type_byte, 0);
+        assert_eq!(entry.raw_bytes, vec![2, b'v', b'1']);
+        match entry.value {
+            RedisObject::String(obj) => assert_eq!(obj.value.to_string(), "v1"),
+            _ => panic!(),
+        }
+
+        let mut entry = RedisEntry::new();
+        assert!(RedisScanExtractor::parse_dump(&mut entry, vec![0, 1])
+            .await
+            .is_err());
+    }
+}
@@ -1,4 +1,5 @@
 use std::{
+    collections::HashMap,
     fs::File,
     io::{BufRead, BufReader},
     path::Path,
@@ -19,6 +20,8 @@ use super::{CURRENT_POSITION_LOG_FLAG, TAIL_POSITION_COUNT};
 pub struct CdcResumer {
     pub current_position: Position,
     pub checkpoint_position: Position,
+    // checkpoint positions of each node if the source is a redis cluster, key: node address
+    pub redis_node_positions: HashMap<String, Position>,
 }
 
 impl CdcResumer {
@@ -83,6 +86,12 @@ impl CdcResumer {
         if line.contains(CURRENT_POSITION_LOG_FLAG) {
             self.current_position = position;
         } else {
+            if let Position::Redis { address, .. } = &position {
+                if !address.is_empty() {
+                    self.redis_node_positions
+                        .insert(address.clone(), position.clone());
+                }
+            }
             self.checkpoint_position = position;
         }
     }
@@ -14,8 +14,8 @@ pub mod sinker;
 use async_trait::async_trait;
 use check_log::check_log::CheckLog;
 use dt_common::meta::{
-    dcl_meta::dcl_data::DclData, ddl_meta::ddl_data::DdlData, dt_data::DtItem, row_data::RowData,
-    struct_meta::struct_data::StructData,
+    dcl_meta::dcl_data::DclData, ddl_meta::ddl_data::DdlData, dt_data::DtItem,
+    message_data::MessageData, row_data::RowData, struct_meta::struct_data::StructData,
 };
 
 #[async_trait]
@@ -44,6 +44,10 @@ pub trait Sinker {
         Ok(())
     }
 
+    async fn sink_message(&mut self, mut _data: Vec<MessageData>) -> anyhow::Result<()> {
+        Ok(())
+    }
+
     async fn refresh_meta(&mut self, _data: Vec<DdlData>) -> anyhow::Result<()> {
         Ok(())
     }
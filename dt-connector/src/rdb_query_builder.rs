@@ -219,7 +219,7 @@ impl RdbQueryBuilder<'_> {
             let mut index = query_info.cols.len() + 1;
             let after = row_data.after.as_ref().unwrap();
             let mut set_pairs = Vec::new();
-            for col in self.rdb_tb_meta.cols.iter() {
+            for col in self.get_insert_cols(row_data) {
                 if self.rdb_tb_meta.id_cols.contains(col) {
                     continue;
                 }
@@ -252,15 +252,14 @@ impl RdbQueryBuilder<'_> {
         let mut cols = Vec::new();
         let mut binds = Vec::new();
         let after = row_data.after.as_ref().unwrap();
-        for col_name in self.rdb_tb_meta.cols.iter() {
+        for col_name in self.get_insert_cols(row_data) {
             cols.push(col_name.clone());
             binds.push(after.get(col_name));
         }
 
         let mut col_values = Vec::new();
-        for i in 0..self.rdb_tb_meta.cols.len() {
-            let sql_value =
-                self.get_sql_value(i + 1, &self.rdb_tb_meta.cols[i], &binds[i], placeholder)?;
+        for i in 0..cols.len() {
+            let sql_value = self.get_sql_value(i + 1, &cols[i], &binds[i], placeholder)?;
             col_values.push(sql_value);
         }
 
@@ -268,7 +267,7 @@ impl RdbQueryBuilder<'_> {
             "INSERT INTO {}.{}({}) VALUES({})",
             self.escape(&self.rdb_tb_meta.schema),
             self.escape(&self.rdb_tb_meta.tb),
-            self.escape_cols(&self.rdb_tb_meta.cols).join(","),
+            self.escape_cols(&cols).join(","),
             col_values.join(",")
         );
 
@@ -281,14 +280,16 @@ impl RdbQueryBuilder<'_> {
         placeholder: bool,
     ) -> anyhow::Result<RdbQueryInfo<'a>> {
         let before = row_data.before.as_ref().unwrap();
-        let (where_sql, not_null_cols) = self.get_where_info(1, before, placeholder)?;
+        // delete all rows matching the key cols present in before
+        let (where_sql, not_null_cols) =
+            self.get_where_info(1, before, placeholder, row_data.delete_all)?;
         let mut sql = format!(
             "DELETE FROM {}.{} WHERE {}",
             self.escape(&self.rdb_tb_meta.schema),
             self.escape(&self.rdb_tb_meta.tb),
             where_sql
         );
-        if self.rdb_tb_meta.key_map.is_empty() {
+        if self.rdb_tb_meta.key_map.is_empty() && !row_data.delete_all {
             sql += " LIMIT 1";
         }
 
@@ -312,6 +313,7 @@ impl RdbQueryBuilder<'_> {
         let mut index = 1;
         let mut set_cols = Vec::new();
         let mut set_pairs = Vec::new();
+        // for partial row images, after only contains the changed cols
         for (col, _) in after.iter() {
             set_cols.push(col.clone());
             let sql_value = self.get_sql_value(index, col, &after.get(col), placeholder)?;
@@ -326,7 +328,7 @@ impl RdbQueryBuilder<'_> {
             ))}
         }
 
-        let (where_sql, not_null_cols) = self.get_where_info(index, before, placeholder)?;
+        let (where_sql, not_null_cols) = self.get_where_info(index, before, placeholder, false)?;
         let mut sql = format!(
             "UPDATE {}.{} SET {} WHERE {}",
             self.escape(&self.rdb_tb_meta.schema),
@@ -350,9 +352,19 @@ impl RdbQueryBuilder<'_> {
         Ok(RdbQueryInfo { sql, cols, binds })
     }
 
+    fn get_insert_cols<'b>(&'b self, row_data: &'b RowData) -> Vec<&'b String> {
+        let after = row_data.after.as_ref().unwrap();
+        self.rdb_tb_meta
+            .cols
+            .iter()
+            // for partial row images, cols absent in after are left to their defaults
+            .filter(|col| !row_data.partial || after.contains_key(*col))
+            .collect()
+    }
+
     pub fn get_select_query<'a>(&self, row_data: &'a RowData) -> anyhow::Result<RdbQueryInfo<'a>> {
         let after = row_data.after.as_ref().unwrap();
-        let (where_sql, not_null_cols) = self.get_where_info(1, after, true)?;
+        let (where_sql, not_null_cols) = self.get_where_info(1, after, true, false)?;
         let mut sql = format!(
             "SELECT {} FROM {}.{} WHERE {}",
             self.build_extract_cols_str()?,
@@ -436,11 +448,16 @@ impl RdbQueryBuilder<'_> {
         mut index: usize,
         col_value_map: &HashMap<String, ColValue>,
         placeholder: bool,
+        skip_absent_cols: bool,
     ) -> anyhow::Result<(String, Vec<String>)> {
         let mut where_sql = String::new();
         let mut not_null_cols = Vec::new();
 
         for col in self.rdb_tb_meta.id_cols.iter() {
+            if skip_absent_cols && !col_value_map.contains_key(col) {
+                continue;
+            }
+
             if !where_sql.is_empty() {
                 where_sql += " AND";
             }
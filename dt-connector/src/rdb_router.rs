@@ -1,10 +1,15 @@
-use anyhow::Ok;
+use anyhow::{bail, Ok};
 use dt_common::{
     config::{
         config_enums::DbType, config_token_parser::ConfigTokenParser, router_config::RouterConfig,
     },
+    error::Error,
     meta::{
         ddl_meta::{ddl_data::DdlData, ddl_statement::DdlStatement},
+        redis::{
+            redis_entry::RedisEntry,
+            redis_object::{RedisObject, RedisString},
+        },
         struct_meta::{statement::struct_statement::StructStatement, struct_data::StructData},
     },
     utils::sql_util::SqlUtil,
@@ -30,6 +35,17 @@ pub struct RdbRouter {
     pub col_map: TbColMap,
     // HashMap<(src_schema, src_tb), String>
     pub topic_map: HashMap<(String, String), String>,
+    // prefix rewriting rules of redis keys, the first matched rule is used
+    pub key_map: Vec<KeyMap>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KeyMap {
+    // source db id, empty or "*" matches all dbs
+    #[serde(default)]
+    pub db: String,
+    pub src_prefix: String,
+    pub dst_prefix: String,
 }
 
 impl RdbRouter {
@@ -40,16 +56,32 @@ impl RdbRouter {
                 tb_map,
                 col_map,
                 topic_map,
+                key_map,
             } => {
                 let schema_map = Self::parse_schema_map(schema_map, db_type)?;
                 let tb_map = Self::parse_tb_map(tb_map, db_type)?;
                 let col_map = Self::parse_col_map(col_map)?;
                 let topic_map = Self::parse_topic_map(topic_map, db_type)?;
+                let key_map = Self::parse_key_map(key_map)?;
+
+                if *db_type == DbType::Redis {
+                    // db_map=0:1,2:3
+                    for (src_db, dst_db) in schema_map.iter() {
+                        if src_db.parse::<i64>().is_err() || dst_db.parse::<i64>().is_err() {
+                            bail! {Error::ConfigError(format!(
+                                "invalid redis db_map: {}:{}, db should be a number",
+                                src_db, dst_db
+                            ))}
+                        }
+                    }
+                }
+
                 Ok(Self {
                     schema_map,
                     tb_map,
                     col_map,
                     topic_map,
+                    key_map,
                 })
             }
         }
@@ -110,12 +142,27 @@ impl RdbRouter {
             reverse_schema_map.insert(dst_db.to_owned(), src_schema.to_owned());
         }
 
+        let reverse_key_map = self
+            .key_map
+            .iter()
+            .map(|i| KeyMap {
+                db: if Self::match_all_dbs(&i.db) {
+                    i.db.clone()
+                } else {
+                    self.get_schema_map(&i.db).to_string()
+                },
+                src_prefix: i.dst_prefix.clone(),
+                dst_prefix: i.src_prefix.clone(),
+            })
+            .collect();
+
         Self {
             schema_map: reverse_schema_map,
             tb_map: reverse_tb_map,
             col_map: reverse_tb_col_map,
             // topic_map should not be reversed
             topic_map: self.topic_map.clone(),
+            key_map: reverse_key_map,
         }
     }
 
@@ -174,6 +221,20 @@ impl RdbRouter {
                 );
             }
 
+            DdlStatement::MongoCommand(s) if !s.new_coll.is_empty() => {
+                let (dst_db, dst_coll) = self.get_tb_map(&s.db, &s.coll);
+                let (dst_new_db, dst_new_coll) = self.get_tb_map(&s.new_db, &s.new_coll);
+                (s.db, s.coll) = (dst_db.to_string(), dst_coll.to_string());
+                (s.new_db, s.new_coll) = (dst_new_db.to_string(), dst_new_coll.to_string());
+            }
+
+            DdlStatement::PgTruncateMultiTable(s) => {
+                for (schema, tb) in s.schema_tbs.iter_mut() {
+                    let (dst_schema, dst_tb) = self.get_tb_map(schema, tb);
+                    (*schema, *tb) = (dst_schema.to_string(), dst_tb.to_string());
+                }
+            }
+
             _ => {
                 let (src_schema, src_tb) = ddl_data.get_schema_tb();
                 let (dst_schema, dst_tb) = self.get_tb_map(&src_schema, &src_tb);
@@ -217,6 +278,83 @@ impl RdbRouter {
         struct_data
     }
 
+    pub fn route_redis_entry(&self, mut entry: RedisEntry) -> RedisEntry {
+        let src_db_id = entry.db_id;
+        if let Some(dst_db) = self.schema_map.get(&src_db_id.to_string()) {
+            // validated in from_config
+            entry.db_id = dst_db.parse().unwrap();
+        }
+
+        if self.key_map.is_empty() {
+            return entry;
+        }
+
+        if entry.is_base_key() {
+            if let Some(dst_key) = self.get_key_map(src_db_id, entry.key.as_bytes()) {
+                let src_key = std::mem::replace(&mut entry.key, RedisString::from(dst_key));
+                Self::route_redis_object(&mut entry.value, &src_key, &entry.key);
+            }
+        } else {
+            // keys of aof commands should have been parsed by KeyParser
+            let cmd = &mut entry.cmd;
+            for (i, key_index) in cmd.key_indexes.iter().enumerate() {
+                // key_indexes are 1-based
+                let arg = &mut cmd.args[key_index - 1];
+                if let Some(dst_key) = self.get_key_map(src_db_id, arg) {
+                    cmd.keys[i] = String::from_utf8_lossy(&dst_key).to_string();
+                    *arg = dst_key;
+                }
+            }
+        }
+        entry
+    }
+
+    pub fn get_key_map(&self, db_id: i64, key: &[u8]) -> Option<Vec<u8>> {
+        let db = db_id.to_string();
+        for i in self.key_map.iter() {
+            if !Self::match_all_dbs(&i.db) && i.db != db {
+                continue;
+            }
+
+            if let Some(suffix) = key.strip_prefix(i.src_prefix.as_bytes()) {
+                let mut dst_key = i.dst_prefix.as_bytes().to_vec();
+                dst_key.extend_from_slice(suffix);
+                return Some(dst_key);
+            }
+        }
+        None
+    }
+
+    fn route_redis_object(value: &mut RedisObject, src_key: &RedisString, dst_key: &RedisString) {
+        match value {
+            RedisObject::String(obj) => obj.key = dst_key.clone(),
+            RedisObject::List(obj) => obj.key = dst_key.clone(),
+            RedisObject::Hash(obj) => obj.key = dst_key.clone(),
+            RedisObject::Set(obj) => obj.key = dst_key.clone(),
+            RedisObject::Zset(obj) => obj.key = dst_key.clone(),
+            RedisObject::Module(obj) => obj.key = dst_key.clone(),
+            RedisObject::Stream(obj) => {
+                obj.key = dst_key.clone();
+                // XADD key ..., XSETID key ..., XCLAIM key ..., XGROUP CREATE key ...
+                for cmd in obj.cmds.iter_mut() {
+                    let index = if cmd.get_name().eq_ignore_ascii_case("xgroup") {
+                        2
+                    } else {
+                        1
+                    };
+                    if cmd.args.len() > index && cmd.args[index] == src_key.bytes {
+                        cmd.args[index] = dst_key.bytes.clone();
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn match_all_dbs(db: &str) -> bool {
+        db.is_empty() || db == "*"
+    }
+
     fn parse_schema_map(config_str: &str, db_type: &DbType) -> anyhow::Result<SchemaMap> {
         // db_map=src_db_1:dst_db_1,src_db_2:dst_db_2
         let mut schema_map = HashMap::new();
@@ -283,6 +421,17 @@ impl RdbRouter {
         Ok(results)
     }
 
+    fn parse_key_map(config_str: &str) -> anyhow::Result<Vec<KeyMap>> {
+        if config_str.trim().is_empty() {
+            return Ok(Vec::new());
+        }
+
+        // key_map=json:[{"db":"0","src_prefix":"user:","dst_prefix":"member:"},{"src_prefix":"order:","dst_prefix":"o:"}]
+        let key_map: Vec<KeyMap> =
+            serde_json::from_str(config_str.trim_start_matches(JSON_PREFIX))?;
+        Ok(key_map)
+    }
+
     fn parse_config(config_str: &str, db_type: &DbType) -> anyhow::Result<Vec<String>> {
         let delimiters = vec![',', '.', ':'];
         let tokens = ConfigTokenParser::parse_config(config_str, db_type, &delimiters)?;
@@ -303,7 +452,14 @@ impl RdbRouter {
 mod tests {
     use std::collections::HashMap;
 
-    use dt_common::config::{config_enums::DbType, router_config::RouterConfig};
+    use dt_common::{
+        config::{config_enums::DbType, router_config::RouterConfig},
+        meta::redis::{
+            command::key_parser::KeyParser,
+            redis_entry::RedisEntry,
+            redis_object::{RedisCmd, RedisObject, RedisString, StringObject},
+        },
+    };
 
     use super::{RdbRouter, TbColMap, TbMap};
 
@@ -424,6 +580,7 @@ mod tests {
             tb_map: tb_map_str.into(),
             col_map: col_map_str.into(),
             topic_map: topic_map.into(),
+            key_map: String::new(),
         };
         let router = RdbRouter::from_config(&config, &DbType::Mysql).unwrap();
 
@@ -450,4 +607,60 @@ mod tests {
         assert_eq!(router.get_topic("db:1", "tb:2"), "test2");
         assert_eq!(router.get_topic("db:2", "tb:1"), "test");
     }
+
+    #[test]
+    fn test_route_redis_entry() {
+        let config = RouterConfig::Rdb {
+            schema_map: "0:1".into(),
+            tb_map: String::new(),
+            col_map: String::new(),
+            topic_map: String::new(),
+            key_map: r#"json:[{"db":"2","src_prefix":"user:","dst_prefix":"db2:user:"},{"src_prefix":"user:","dst_prefix":"member:"}]"#.into(),
+        };
+        let router = RdbRouter::from_config(&config, &DbType::Redis).unwrap();
+
+        // rdb entry
+        let mut entry = RedisEntry::new();
+        entry.is_base = true;
+        entry.key = RedisString::from("user:1".to_string());
+        let mut obj = StringObject::new();
+        obj.key = entry.key.clone();
+        entry.value = RedisObject::String(obj);
+        let entry = router.route_redis_entry(entry);
+        assert_eq!(entry.db_id, 1);
+        assert_eq!(entry.key.to_string(), "member:1");
+        match entry.value {
+            RedisObject::String(obj) => assert_eq!(obj.key.to_string(), "member:1"),
+            _ => panic!(),
+        }
+
+        // aof command
+        let key_parser = KeyParser::new();
+        let mut entry = RedisEntry::new();
+        entry.db_id = 2;
+        entry.cmd = RedisCmd::from_str_args(&["MSET", "user:1", "v1", "order:1", "v2"]);
+        entry.cmd.parse_keys(&key_parser).unwrap();
+        let entry = router.route_redis_entry(entry);
+        assert_eq!(entry.db_id, 2);
+        assert_eq!(entry.cmd.to_string(), "MSET db2:user:1 v1 order:1 v2");
+        assert_eq!(entry.cmd.keys, vec!["db2:user:1", "order:1"]);
+
+        // reverse
+        let reverse_router = router.reverse();
+        assert_eq!(
+            reverse_router.get_key_map(1, b"member:1"),
+            Some(b"user:1".to_vec())
+        );
+        assert_eq!(reverse_router.get_key_map(1, b"user:1"), None);
+
+        // db should be a number
+        let config = RouterConfig::Rdb {
+            schema_map: "a:1".into(),
+            tb_map: String::new(),
+            col_map: String::new(),
+            topic_map: String::new(),
+            key_map: String::new(),
+        };
+        assert!(RdbRouter::from_config(&config, &DbType::Redis).is_err());
+    }
 }
@@ -1,8 +1,10 @@
 use std::collections::HashMap;
 
 use dt_common::meta::{
-    rdb_meta_manager::RdbMetaManager, rdb_tb_meta::RdbTbMeta, row_data::RowData,
-    struct_meta::statement::struct_statement::StructStatement,
+    rdb_meta_manager::RdbMetaManager,
+    rdb_tb_meta::RdbTbMeta,
+    row_data::RowData,
+    struct_meta::{statement::struct_statement::StructStatement, struct_diff::StructDiff},
 };
 use dt_common::{log_diff, log_extra, log_miss, rdb_filter::RdbFilter};
 
@@ -97,11 +99,51 @@ impl BaseChecker {
         dst_statement: &mut StructStatement,
         filter: &RdbFilter,
     ) -> anyhow::Result<()> {
-        if matches!(dst_statement, StructStatement::Unknown) {
-            log_miss!("{:?}", src_statement.to_sqls(filter)?);
-            return Ok(());
+        match (&mut *src_statement, &*dst_statement) {
+            (StructStatement::MysqlCreateTable(s), StructStatement::Unknown) => {
+                let sqls = s.to_sqls(filter)?;
+                let miss = StructDiff::for_missing_table(
+                    &s.table.database_name,
+                    &s.table.table_name,
+                    sqls,
+                );
+                log_miss!("{}", miss);
+                return Ok(());
+            }
+
+            (StructStatement::PgCreateTable(s), StructStatement::Unknown) => {
+                let sqls = s.to_sqls(filter)?;
+                let miss =
+                    StructDiff::for_missing_table(&s.table.schema_name, &s.table.table_name, sqls);
+                log_miss!("{}", miss);
+                return Ok(());
+            }
+
+            (s, StructStatement::Unknown) => {
+                log_miss!("{:?}", s.to_sqls(filter)?);
+                return Ok(());
+            }
+
+            (StructStatement::MysqlCreateTable(s), StructStatement::MysqlCreateTable(d)) => {
+                let diff = StructDiff::from_mysql_table(s, d, filter);
+                if !diff.is_empty() {
+                    log_diff!("{}", diff);
+                }
+                return Ok(());
+            }
+
+            (StructStatement::PgCreateTable(s), StructStatement::PgCreateTable(d)) => {
+                let diff = StructDiff::from_pg_table(s, d, filter);
+                if !diff.is_empty() {
+                    log_diff!("{}", diff);
+                }
+                return Ok(());
+            }
+
+            _ => {}
         }
 
+        // databases, schemas and rbac are compared by their sqls
         let mut src_sqls = HashMap::new();
         for (key, sql) in src_statement.to_sqls(filter)? {
             src_sqls.insert(key, sql);
@@ -2,11 +2,39 @@ use std::sync::Arc;
 
 use tokio::{sync::Mutex, time::Instant};
 
-use dt_common::monitor::{counter_type::CounterType, monitor::Monitor};
+use dt_common::{
+    meta::{
+        dt_data::{DtData, DtItem},
+        row_data::RowData,
+    },
+    monitor::{counter_type::CounterType, monitor::Monitor},
+};
 
 pub struct BaseSinker {}
 
 impl BaseSinker {
+    /// Split items into transactions delimited by DtData::Begin / DtData::Commit,
+    /// transactions without dmls are dropped
+    pub fn split_transactions(data: Vec<DtItem>) -> Vec<Vec<RowData>> {
+        let mut txs = Vec::new();
+        let mut tx = Vec::new();
+        for item in data {
+            match item.dt_data {
+                DtData::Begin {} | DtData::Commit { .. } => {
+                    if !tx.is_empty() {
+                        txs.push(std::mem::take(&mut tx));
+                    }
+                }
+                DtData::Dml { row_data } => tx.push(row_data),
+                _ => {}
+            }
+        }
+        if !tx.is_empty() {
+            txs.push(tx);
+        }
+        txs
+    }
+
     pub async fn update_batch_monitor(
         monitor: &mut Arc<Mutex<Monitor>>,
         batch_size: usize,
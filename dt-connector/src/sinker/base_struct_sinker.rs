@@ -1,13 +1,29 @@
+use std::sync::Arc;
+
 use anyhow::bail;
 use dt_common::log_error;
+use dt_common::meta::struct_meta::statement::struct_statement::SqlPhase;
 use dt_common::meta::struct_meta::struct_data::StructData;
 use dt_common::{
-    config::config_enums::ConflictPolicyEnum, error::Error, log_info, rdb_filter::RdbFilter,
+    config::config_enums::{ConflictPolicyEnum, StructPhase},
+    error::Error,
+    log_info,
+    rdb_filter::RdbFilter,
 };
 use sqlx::{query, MySql, Pool, Postgres};
+use tokio::{sync::Semaphore, task::JoinSet, time::Instant};
 
 pub struct BaseStructSinker {}
 
+// (key, sql) of each phase
+#[derive(Default)]
+struct PhaseSqls {
+    pre_data: Vec<(String, String)>,
+    post_data: Vec<(String, String)>,
+    foreign_key: Vec<(String, String)>,
+}
+
+#[derive(Clone)]
 pub enum DBConnPool {
     MySQL(Pool<MySql>),
     PostgreSQL(Pool<Postgres>),
@@ -41,6 +57,159 @@ impl BaseStructSinker {
         Ok(())
     }
 
+    /// pre_data: create databases/schemas/tables with primary keys only, before snapshot.
+    /// post_data: create secondary indexes and constraints in parallel, after snapshot so the
+    /// bulk load does not pay for index maintenance. Foreign keys may reference unique keys of
+    /// tables in later batches, so they are appended to foreign_key_sqls and created by sink_foreign_keys
+    /// once all batches are sinked.
+    pub async fn sink_structs_in_phase(
+        conn_pool: &DBConnPool,
+        conflict_policy: &ConflictPolicyEnum,
+        data: Vec<StructData>,
+        filter: &RdbFilter,
+        phase: &StructPhase,
+        parallel_size: usize,
+        foreign_key_sqls: &mut Vec<(String, String)>,
+    ) -> anyhow::Result<()> {
+        if *phase == StructPhase::All {
+            return Self::sink_structs(conn_pool, conflict_policy, data, filter).await;
+        }
+
+        let mut phase_sqls = Self::split_sqls_by_phase(data, filter)?;
+        match phase {
+            StructPhase::PreData => {
+                Self::execute_serial(conn_pool, conflict_policy, phase_sqls.pre_data).await
+            }
+
+            StructPhase::PostData => {
+                foreign_key_sqls.append(&mut phase_sqls.foreign_key);
+                Self::execute_parallel(
+                    conn_pool,
+                    conflict_policy,
+                    phase_sqls.post_data,
+                    parallel_size,
+                )
+                .await
+            }
+
+            StructPhase::All => Ok(()),
+        }
+    }
+
+    pub async fn sink_foreign_keys(
+        conn_pool: &DBConnPool,
+        conflict_policy: &ConflictPolicyEnum,
+        foreign_key_sqls: Vec<(String, String)>,
+        parallel_size: usize,
+    ) -> anyhow::Result<()> {
+        Self::execute_parallel(conn_pool, conflict_policy, foreign_key_sqls, parallel_size).await
+    }
+
+    fn split_sqls_by_phase(data: Vec<StructData>, filter: &RdbFilter) -> anyhow::Result<PhaseSqls> {
+        let mut phase_sqls = PhaseSqls::default();
+        for mut struct_data in data {
+            for (key, sql) in struct_data.statement.to_sqls(filter)? {
+                match struct_data.statement.get_sql_phase(&key) {
+                    SqlPhase::PreData => phase_sqls.pre_data.push((key, sql)),
+                    SqlPhase::PostData => phase_sqls.post_data.push((key, sql)),
+                    SqlPhase::ForeignKey => phase_sqls.foreign_key.push((key, sql)),
+                }
+            }
+        }
+        Ok(phase_sqls)
+    }
+
+    async fn execute_serial(
+        conn_pool: &DBConnPool,
+        conflict_policy: &ConflictPolicyEnum,
+        sqls: Vec<(String, String)>,
+    ) -> anyhow::Result<()> {
+        for (key, sql) in sqls {
+            let result = Self::execute_with_log(conn_pool.clone(), key, sql).await;
+            Self::handle_result(conflict_policy, result)?;
+        }
+        Ok(())
+    }
+
+    async fn execute_parallel(
+        conn_pool: &DBConnPool,
+        conflict_policy: &ConflictPolicyEnum,
+        sqls: Vec<(String, String)>,
+        parallel_size: usize,
+    ) -> anyhow::Result<()> {
+        if sqls.is_empty() {
+            return Ok(());
+        }
+
+        let start_time = Instant::now();
+        let count = sqls.len();
+        let semaphore = Arc::new(Semaphore::new(parallel_size.max(1)));
+        let mut join_set: JoinSet<anyhow::Result<()>> = JoinSet::new();
+        for (key, sql) in sqls {
+            let permit = semaphore.clone().acquire_owned().await?;
+            let conn_pool = conn_pool.clone();
+            join_set.spawn(async move {
+                let result = Self::execute_with_log(conn_pool, key, sql).await;
+                drop(permit);
+                result
+            });
+
+            // fail fast without waiting for all pending sqls to be spawned
+            while let Some(result) = join_set.try_join_next() {
+                Self::handle_result(conflict_policy, result?)?;
+            }
+        }
+
+        while let Some(result) = join_set.join_next().await {
+            Self::handle_result(conflict_policy, result?)?;
+        }
+
+        log_info!(
+            "ddl batch finished, count: {}, parallel_size: {}, cost: {} ms",
+            count,
+            parallel_size,
+            start_time.elapsed().as_millis()
+        );
+        Ok(())
+    }
+
+    async fn execute_with_log(
+        conn_pool: DBConnPool,
+        key: String,
+        sql: String,
+    ) -> anyhow::Result<()> {
+        let start_time = Instant::now();
+        log_info!("ddl begin: {}", sql);
+        match Self::execute(&conn_pool, &sql).await {
+            Ok(()) => {
+                log_info!(
+                    "ddl succeed, key: {}, cost: {} ms",
+                    key,
+                    start_time.elapsed().as_millis()
+                );
+                Ok(())
+            }
+
+            Err(error) => {
+                log_error!("ddl failed, key: {}, error: {}", key, error);
+                Err(error)
+            }
+        }
+    }
+
+    fn handle_result(
+        conflict_policy: &ConflictPolicyEnum,
+        result: anyhow::Result<()>,
+    ) -> anyhow::Result<()> {
+        match result {
+            Ok(()) => Ok(()),
+            Err(error) => match conflict_policy {
+                ConflictPolicyEnum::Interrupt => bail! {error},
+                ConflictPolicyEnum::Ignore => Ok(()),
+            },
+        }
+    }
+
     async fn execute(pool: &DBConnPool, sql: &str) -> anyhow::Result<()> {
         match pool {
             DBConnPool::MySQL(pool) => match query(sql).execute(pool).await {
@@ -54,3 +223,102 @@ impl BaseStructSinker {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dt_common::{
+        config::{config_enums::DbType, filter_config::FilterConfig},
+        meta::struct_meta::{
+            statement::{
+                mysql_create_table_statement::MysqlCreateTableStatement,
+                struct_statement::StructStatement,
+            },
+            structure::{
+                column::Column,
+                constraint::{Constraint, ConstraintType},
+                index::{Index, IndexColumn, IndexType},
+                table::Table,
+            },
+        },
+    };
+
+    fn build_struct_data(tb: &str, has_pk: bool) -> StructData {
+        let column_key = if has_pk { "PRI" } else { "" };
+        let build_constraint = |name: &str, constraint_type: ConstraintType| Constraint {
+            database_name: "db_1".to_string(),
+            schema_name: String::new(),
+            table_name: tb.to_string(),
+            constraint_name: name.to_string(),
+            constraint_type,
+            definition: "(`f_1`)".to_string(),
+        };
+        let statement = MysqlCreateTableStatement {
+            table: Table {
+                database_name: "db_1".to_string(),
+                table_name: tb.to_string(),
+                columns: vec![Column {
+                    column_name: "id".to_string(),
+                    column_type: "int".to_string(),
+                    column_key: column_key.to_string(),
+                    ..Default::default()
+                }],
+                ..Default::default()
+            },
+            constraints: vec![
+                build_constraint("chk_1", ConstraintType::Check),
+                build_constraint("fk_1", ConstraintType::Foregin),
+            ],
+            indexes: vec![Index {
+                database_name: "db_1".to_string(),
+                table_name: tb.to_string(),
+                index_name: "idx_1".to_string(),
+                index_type: IndexType::Btree,
+                columns: vec![IndexColumn {
+                    column_name: "f_1".to_string(),
+                    seq_in_index: 1,
+                }],
+                ..Default::default()
+            }],
+        };
+        StructData {
+            schema: "db_1".to_string(),
+            statement: StructStatement::MysqlCreateTable(statement),
+        }
+    }
+
+    fn get_keys(sqls: &[(String, String)]) -> Vec<&str> {
+        sqls.iter().map(|(key, _)| key.as_str()).collect()
+    }
+
+    #[test]
+    fn test_split_sqls_by_phase() {
+        let config = FilterConfig {
+            do_structures: "*".to_string(),
+            ..Default::default()
+        };
+        let filter = RdbFilter::from_config(&config, &DbType::Mysql).unwrap();
+        let data = vec![
+            build_struct_data("tb_1", true),
+            build_struct_data("tb_2", false),
+        ];
+
+        let phase_sqls = BaseStructSinker::split_sqls_by_phase(data, &filter).unwrap();
+        assert_eq!(
+            get_keys(&phase_sqls.pre_data),
+            vec!["table.db_1.tb_1", "table.db_1.tb_2", "index.db_1.tb_2"]
+        );
+        assert_eq!(
+            get_keys(&phase_sqls.post_data),
+            vec![
+                "index.db_1.tb_1",
+                "constraint.db_1.tb_1.chk_1",
+                "constraint.db_1.tb_2.chk_1"
+            ]
+        );
+        assert_eq!(
+            get_keys(&phase_sqls.foreign_key),
+            vec!["constraint.db_1.tb_1.fk_1", "constraint.db_1.tb_2.fk_1"]
+        );
+    }
+}
@@ -11,7 +11,13 @@ use crate::{call_batch_fn, sinker::base_sinker::BaseSinker, Sinker};
 use dt_common::{
     config::config_enums::DbType,
     error::Error,
-    meta::{col_value::ColValue, row_data::RowData, row_type::RowType},
+    log_info,
+    meta::{
+        col_value::ColValue,
+        ddl_meta::{ddl_data::DdlData, ddl_type::DdlType},
+        row_data::RowData,
+        row_type::RowType,
+    },
     monitor::monitor::Monitor,
     utils::sql_util::SqlUtil,
 };
@@ -41,6 +47,25 @@ impl Sinker for ClickhouseSinker {
         call_batch_fn!(self, data, Self::batch_sink);
         Ok(())
     }
+
+    async fn sink_ddl(&mut self, data: Vec<DdlData>, _batch: bool) -> anyhow::Result<()> {
+        // only truncate is supported, other ddls are not compatible with clickhouse
+        for ddl_data in data {
+            if ddl_data.ddl_type != DdlType::TruncateTable {
+                continue;
+            }
+
+            for sql in ddl_data.to_truncate_sqls(&DbType::ClickHouse) {
+                log_info!("sink ddl: {}", sql);
+                // curl -X POST -d 'TRUNCATE TABLE test_db.tb_1' 'http://localhost:8123/' --user admin:123456
+                let url = format!("http://{}:{}/", self.host, self.port);
+                let request = self.build_request(&url, &sql)?;
+                let response = self.http_client.execute(request).await?;
+                Self::check_response(response).await?;
+            }
+        }
+        Ok(())
+    }
 }
 
 impl ClickhouseSinker {
@@ -0,0 +1,421 @@
+use std::{cmp::Ordering, collections::HashMap};
+
+use dt_common::{
+    config::{config_enums::ConflictResolveEnum, conflict_resolve_config::ConflictResolveConfig},
+    log_conflict, log_warn,
+    meta::{col_value::ColValue, row_data::RowData, row_type::RowType},
+};
+use mlua::{IntoLua, Lua};
+use serde::Serialize;
+use serde_json::json;
+use strum::{Display, EnumString};
+
+/// Detects conflicts between an incoming row event and the current target row,
+/// which happen in two-way sync when the same row is written on both sides.
+#[derive(Clone)]
+pub struct ConflictResolver {
+    pub config: ConflictResolveConfig,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Display, EnumString)]
+#[serde(rename_all = "snake_case")]
+pub enum ConflictType {
+    // insert, but a different row with the same key exists in target
+    #[strum(serialize = "insert_exists")]
+    InsertExists,
+    // update, but the row does not exist in target
+    #[strum(serialize = "update_missing")]
+    UpdateMissing,
+    // update, but the target row differs from the before image
+    #[strum(serialize = "update_mismatch")]
+    UpdateMismatch,
+    // delete, but the target row differs from the before image
+    #[strum(serialize = "delete_mismatch")]
+    DeleteMismatch,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Resolution {
+    // apply the event as it is
+    Apply,
+    // overwrite target with the after image of the event
+    Replace,
+    // keep target and discard the event
+    Skip,
+}
+
+#[derive(Serialize)]
+struct ConflictLog<'a> {
+    schema: &'a str,
+    tb: &'a str,
+    row_type: String,
+    conflict_type: ConflictType,
+    resolution: Resolution,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    before: Option<&'a HashMap<String, ColValue>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    after: Option<&'a HashMap<String, ColValue>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    target: Option<&'a HashMap<String, ColValue>>,
+}
+
+impl ConflictResolver {
+    pub fn new(config: ConflictResolveConfig) -> Option<Self> {
+        if config.policy == ConflictResolveEnum::None {
+            return None;
+        }
+        Some(Self { config })
+    }
+
+    /// The row used to select the current target row: the before image for update/delete
+    pub fn build_lookup_row(row_data: &RowData) -> RowData {
+        let image = match row_data.row_type {
+            RowType::Insert => row_data.after.clone(),
+            _ => row_data.before.clone(),
+        };
+        RowData::new(
+            row_data.schema.clone(),
+            row_data.tb.clone(),
+            RowType::Insert,
+            None,
+            image,
+        )
+    }
+
+    /// The row to write when the event is resolved as Replace
+    pub fn build_replace_row(row_data: &RowData) -> RowData {
+        RowData::new(
+            row_data.schema.clone(),
+            row_data.tb.clone(),
+            RowType::Insert,
+            None,
+            row_data.after.clone(),
+        )
+    }
+
+    pub fn resolve(
+        &self,
+        row_data: &RowData,
+        target: &Option<RowData>,
+    ) -> anyhow::Result<Resolution> {
+        let target = target.as_ref().and_then(|i| i.after.as_ref());
+        let conflict_type = match Self::detect(row_data, target) {
+            Some(conflict_type) => conflict_type,
+            None => return Ok(Resolution::Apply),
+        };
+
+        let source_wins = match self.config.policy {
+            ConflictResolveEnum::None | ConflictResolveEnum::SourceWins => true,
+            ConflictResolveEnum::TargetWins => false,
+            ConflictResolveEnum::Timestamp => self.source_is_newer(row_data, target),
+            ConflictResolveEnum::Lua => self.lua_source_wins(row_data, target, &conflict_type)?,
+        };
+
+        let resolution = match (source_wins, &conflict_type) {
+            (false, _) => Resolution::Skip,
+            (true, ConflictType::InsertExists) | (true, ConflictType::UpdateMissing) => {
+                Resolution::Replace
+            }
+            (true, _) => Resolution::Apply,
+        };
+
+        let log = ConflictLog {
+            schema: &row_data.schema,
+            tb: &row_data.tb,
+            row_type: row_data.row_type.to_string(),
+            conflict_type,
+            resolution: resolution.clone(),
+            before: row_data.before.as_ref(),
+            after: row_data.after.as_ref(),
+            target,
+        };
+        log_conflict!("{}", json!(log));
+        Ok(resolution)
+    }
+
+    fn detect(
+        row_data: &RowData,
+        target: Option<&HashMap<String, ColValue>>,
+    ) -> Option<ConflictType> {
+        match (&row_data.row_type, target) {
+            (RowType::Insert, Some(target)) => {
+                if Self::image_matches(&row_data.after, target) {
+                    None
+                } else {
+                    Some(ConflictType::InsertExists)
+                }
+            }
+
+            (RowType::Update, None) => Some(ConflictType::UpdateMissing),
+
+            (RowType::Update, Some(target)) => {
+                // target already has the after image, the event was applied before
+                if Self::image_matches(&row_data.before, target)
+                    || Self::image_matches(&row_data.after, target)
+                {
+                    None
+                } else {
+                    Some(ConflictType::UpdateMismatch)
+                }
+            }
+
+            (RowType::Delete, Some(target)) => {
+                if Self::image_matches(&row_data.before, target) {
+                    None
+                } else {
+                    Some(ConflictType::DeleteMismatch)
+                }
+            }
+
+            // insert into an empty slot or delete a missing row, nothing to resolve
+            _ => None,
+        }
+    }
+
+    fn image_matches(
+        image: &Option<HashMap<String, ColValue>>,
+        target: &HashMap<String, ColValue>,
+    ) -> bool {
+        let image = match image {
+            Some(image) => image,
+            None => return true,
+        };
+        // only compare columns in the image since it may be partial
+        // compare by string since source and target may decode the same value into different types
+        for (col, col_value) in image.iter() {
+            if let Some(target_col_value) = target.get(col) {
+                if col_value.to_option_string() != target_col_value.to_option_string() {
+                    return false;
+                }
+            }
+        }
+        true
+    }
+
+    fn source_is_newer(
+        &self,
+        row_data: &RowData,
+        target: Option<&HashMap<String, ColValue>>,
+    ) -> bool {
+        let col = &self.config.timestamp_col;
+        let image = match row_data.row_type {
+            RowType::Delete => &row_data.before,
+            _ => &row_data.after,
+        };
+
+        let src_value = image.as_ref().and_then(|i| i.get(col));
+        let dst_value = target.and_then(|i| i.get(col));
+        match (src_value, dst_value) {
+            (Some(src_value), Some(dst_value)) => {
+                Self::compare_col_values(src_value, dst_value) != Ordering::Less
+            }
+            // target row missing, source wins
+            (_, None) => true,
+            (None, Some(_)) => {
+                log_warn!(
+                    "conflict timestamp column: {} not found in source row, schema: {}, tb: {}",
+                    col,
+                    row_data.schema,
+                    row_data.tb
+                );
+                true
+            }
+        }
+    }
+
+    fn compare_col_values(a: &ColValue, b: &ColValue) -> Ordering {
+        let a = a.to_option_string();
+        let b = b.to_option_string();
+        if let (Some(a_str), Some(b_str)) = (&a, &b) {
+            if let (Ok(a_num), Ok(b_num)) = (a_str.parse::<f64>(), b_str.parse::<f64>()) {
+                return a_num.partial_cmp(&b_num).unwrap_or(Ordering::Equal);
+            }
+        }
+        // NULL < any value, datetime strings compare lexicographically
+        a.cmp(&b)
+    }
+
+    fn lua_source_wins(
+        &self,
+        row_data: &RowData,
+        target: Option<&HashMap<String, ColValue>>,
+        conflict_type: &ConflictType,
+    ) -> anyhow::Result<bool> {
+        let lua = Lua::new();
+        lua.globals().set(
+            "before",
+            Self::col_values_to_lua_table(row_data.before.as_ref(), &lua)?,
+        )?;
+        lua.globals().set(
+            "after",
+            Self::col_values_to_lua_table(row_data.after.as_ref(), &lua)?,
+        )?;
+        lua.globals()
+            .set("target", Self::col_values_to_lua_table(target, &lua)?)?;
+        lua.globals().set("schema", row_data.schema.as_str())?;
+        lua.globals().set("tb", row_data.tb.as_str())?;
+        lua.globals()
+            .set("row_type", row_data.row_type.to_string())?;
+        lua.globals()
+            .set("conflict_type", conflict_type.to_string())?;
+        lua.globals().set("resolution", "source")?;
+
+        lua.load(&self.config.lua_code).exec()?;
+
+        let resolution: String = lua.globals().get("resolution")?;
+        Ok(resolution != "target")
+    }
+
+    fn col_values_to_lua_table<'lua>(
+        col_values: Option<&HashMap<String, ColValue>>,
+        lua: &'lua Lua,
+    ) -> anyhow::Result<mlua::Table<'lua>> {
+        let lua_table = lua.create_table()?;
+        if let Some(map) = col_values {
+            for (col, col_value) in map.iter() {
+                // all values are passed as strings, NULL as nil
+                let lua_value = match col_value.to_option_string() {
+                    Some(v) => v.into_lua(lua)?,
+                    None => mlua::Value::Nil,
+                };
+                lua_table.set(col.as_str(), lua_value)?;
+            }
+        }
+        Ok(lua_table)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use dt_common::{
+        config::{
+            config_enums::ConflictResolveEnum, conflict_resolve_config::ConflictResolveConfig,
+        },
+        meta::{col_value::ColValue, row_data::RowData, row_type::RowType},
+    };
+
+    use super::{ConflictResolver, Resolution};
+
+    fn build_image(id: i32, value: &str, version: i64) -> Option<HashMap<String, ColValue>> {
+        let mut image = HashMap::new();
+        image.insert("id".to_string(), ColValue::Long(id));
+        image.insert("value".to_string(), ColValue::String(value.to_string()));
+        image.insert("version".to_string(), ColValue::LongLong(version));
+        Some(image)
+    }
+
+    fn build_row_data(
+        row_type: RowType,
+        before: Option<HashMap<String, ColValue>>,
+        after: Option<HashMap<String, ColValue>>,
+    ) -> RowData {
+        RowData::new("db_1".into(), "tb_1".into(), row_type, before, after)
+    }
+
+    fn build_resolver(policy: ConflictResolveEnum, lua_code: &str) -> ConflictResolver {
+        ConflictResolver::new(ConflictResolveConfig {
+            policy,
+            timestamp_col: "version".into(),
+            lua_code_file: String::new(),
+            lua_code: lua_code.into(),
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_no_conflict() {
+        let resolver = build_resolver(ConflictResolveEnum::TargetWins, "");
+        let update = build_row_data(
+            RowType::Update,
+            build_image(1, "a", 1),
+            build_image(1, "b", 2),
+        );
+
+        let target = Some(build_row_data(
+            RowType::Insert,
+            None,
+            build_image(1, "a", 1),
+        ));
+        assert_eq!(
+            resolver.resolve(&update, &target).unwrap(),
+            Resolution::Apply
+        );
+
+        // already applied
+        let target = Some(build_row_data(
+            RowType::Insert,
+            None,
+            build_image(1, "b", 2),
+        ));
+        assert_eq!(
+            resolver.resolve(&update, &target).unwrap(),
+            Resolution::Apply
+        );
+
+        let insert = build_row_data(RowType::Insert, None, build_image(1, "a", 1));
+        assert_eq!(resolver.resolve(&insert, &None).unwrap(), Resolution::Apply);
+    }
+
+    #[test]
+    fn test_resolve_policies() {
+        let update = build_row_data(
+            RowType::Update,
+            build_image(1, "a", 1),
+            build_image(1, "b", 2),
+        );
+        let newer_target = Some(build_row_data(
+            RowType::Insert,
+            None,
+            build_image(1, "c", 3),
+        ));
+        let older_target = Some(build_row_data(
+            RowType::Insert,
+            None,
+            build_image(1, "c", 0),
+        ));
+
+        let resolver = build_resolver(ConflictResolveEnum::SourceWins, "");
+        assert_eq!(
+            resolver.resolve(&update, &newer_target).unwrap(),
+            Resolution::Apply
+        );
+        assert_eq!(
+            resolver.resolve(&update, &None).unwrap(),
+            Resolution::Replace
+        );
+
+        let resolver = build_resolver(ConflictResolveEnum::TargetWins, "");
+        assert_eq!(
+            resolver.resolve(&update, &newer_target).unwrap(),
+            Resolution::Skip
+        );
+
+        let resolver = build_resolver(ConflictResolveEnum::Timestamp, "");
+        assert_eq!(
+            resolver.resolve(&update, &newer_target).unwrap(),
+            Resolution::Skip
+        );
+        assert_eq!(
+            resolver.resolve(&update, &older_target).unwrap(),
+            Resolution::Apply
+        );
+
+        let lua_code = r#"
+            if conflict_type == "update_mismatch" and target["value"] == "c" then
+                resolution = "target"
+            end
+        "#;
+        let resolver = build_resolver(ConflictResolveEnum::Lua, lua_code);
+        assert_eq!(
+            resolver.resolve(&update, &newer_target).unwrap(),
+            Resolution::Skip
+        );
+        assert_eq!(
+            resolver.resolve(&update, &None).unwrap(),
+            Resolution::Replace
+        );
+    }
+}
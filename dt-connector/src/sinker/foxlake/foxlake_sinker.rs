@@ -112,6 +112,7 @@ impl FoxlakeSinker {
                 dt_data: DtData::Foxlake { file_meta },
                 position: Position::None,
                 data_origin_node: String::new(),
+                data_path: String::new(),
             };
             dt_items.push(dt_item);
         }
@@ -1,13 +1,32 @@
 use std::sync::Arc;
 
+use anyhow::{bail, Context};
 use async_trait::async_trait;
+use base64::{prelude::BASE64_STANDARD, Engine};
 use kafka::producer::{Producer, Record};
 use tokio::{sync::Mutex, time::Instant};
 
-use crate::{call_batch_fn, rdb_router::RdbRouter, sinker::base_sinker::BaseSinker, Sinker};
+use crate::{
+    call_batch_fn,
+    rdb_router::RdbRouter,
+    sinker::{base_sinker::BaseSinker, redis::entry_rewriter::EntryRewriter},
+    Sinker,
+};
 use dt_common::{
-    meta::avro::avro_converter::AvroConverter, meta::ddl_meta::ddl_data::DdlData,
-    meta::row_data::RowData, monitor::monitor::Monitor,
+    config::config_enums::MessageFormat,
+    error::Error,
+    meta::{
+        avro::avro_converter::AvroConverter,
+        ddl_meta::ddl_data::DdlData,
+        dt_data::{DtData, DtItem},
+        position::Position,
+        redis::{
+            command::key_parser::KeyParser, redis_entry::RedisEntry, redis_event::RedisEvent,
+            redis_object::RedisObject,
+        },
+        row_data::RowData,
+    },
+    monitor::monitor::Monitor,
 };
 
 pub struct KafkaSinker {
@@ -15,6 +34,8 @@ pub struct KafkaSinker {
     pub router: RdbRouter,
     pub producer: Producer,
     pub avro_converter: AvroConverter,
+    pub message_format: MessageFormat,
+    pub key_parser: KeyParser,
     pub monitor: Arc<Mutex<Monitor>>,
 }
 
@@ -45,6 +66,15 @@ impl Sinker for KafkaSinker {
         Ok(())
     }
 
+    async fn sink_raw(&mut self, mut data: Vec<DtItem>, _batch: bool) -> anyhow::Result<()> {
+        if data.is_empty() {
+            return Ok(());
+        }
+
+        call_batch_fn!(self, data, Self::send_redis);
+        Ok(())
+    }
+
     async fn refresh_meta(&mut self, data: Vec<DdlData>) -> anyhow::Result<()> {
         self.avro_converter.refresh_meta(&data);
         Ok(())
@@ -84,4 +114,184 @@ impl KafkaSinker {
 
         BaseSinker::update_batch_monitor(&mut self.monitor, batch_size, data_size, start_time).await
     }
+
+    async fn send_redis(
+        &mut self,
+        data: &mut [DtItem],
+        sinked_count: usize,
+        batch_size: usize,
+    ) -> anyhow::Result<()> {
+        let start_time = Instant::now();
+        let mut data_size = 0;
+
+        let mut messages = Vec::new();
+        for dt_item in data.iter_mut().skip(sinked_count).take(batch_size) {
+            data_size += dt_item.dt_data.get_data_size();
+            if let DtData::Redis { entry } = &mut dt_item.dt_data {
+                let event = Self::entry_to_event(&self.key_parser, entry, &dt_item.position)?;
+                let topic = self.router.get_topic(&event.db.to_string(), "");
+                let key = event.get_partition_key();
+                let payload = match self.message_format {
+                    MessageFormat::Avro => self.avro_converter.redis_event_to_avro_value(event)?,
+                    MessageFormat::Json => serde_json::to_vec(&event)?,
+                };
+                messages.push(Record {
+                    key,
+                    value: payload,
+                    topic,
+                    partition: -1,
+                });
+            }
+        }
+
+        self.producer.send_all(&messages)?;
+
+        BaseSinker::update_batch_monitor(&mut self.monitor, batch_size, data_size, start_time).await
+    }
+
+    fn entry_to_event(
+        key_parser: &KeyParser,
+        entry: &mut RedisEntry,
+        position: &Position,
+    ) -> anyhow::Result<RedisEvent> {
+        let timestamp = match position {
+            Position::Redis { timestamp, .. } => timestamp.clone(),
+            _ => String::new(),
+        };
+        let mut event = RedisEvent {
+            db: entry.db_id,
+            is_base: entry.is_base,
+            expire_ms: entry.expire_ms,
+            timestamp,
+            position: position.clone(),
+            ..Default::default()
+        };
+
+        // aof commands, or aux data from rdb, E.g. lua scripts, function libraries
+        if !entry.cmd.args.is_empty() {
+            if entry.cmd.keys.is_empty() {
+                entry
+                    .cmd
+                    .parse_keys(key_parser)
+                    .with_context(|| format!("failed to parse keys for cmd: [{}]", entry.cmd))?;
+            }
+            // keys are arguments of the command, key_indexes are 1-based
+            let keys: Vec<&[u8]> = entry
+                .cmd
+                .key_indexes
+                .iter()
+                .map(|i| entry.cmd.args[i - 1].as_slice())
+                .collect();
+            event.is_base64 = Self::has_binary(entry.cmd.args.iter().map(Vec::as_slice));
+            event.command = entry.cmd.name.clone();
+            event.keys = Self::encode_args(keys.into_iter(), event.is_base64);
+            event.args =
+                Self::encode_args(entry.cmd.args.iter().map(Vec::as_slice), event.is_base64);
+            return Ok(event);
+        }
+
+        let value_cmds = match entry.value {
+            RedisObject::String(ref mut obj) => EntryRewriter::rewrite_string(obj),
+            RedisObject::List(ref mut obj) => EntryRewriter::rewrite_list(obj),
+            RedisObject::Set(ref mut obj) => EntryRewriter::rewrite_set(obj),
+            RedisObject::Hash(ref mut obj) => EntryRewriter::rewrite_hash(obj),
+            RedisObject::Zset(ref mut obj) => EntryRewriter::rewrite_zset(obj),
+            RedisObject::Stream(ref mut obj) => Ok(obj.cmds.drain(..).collect()),
+            // module types which can not be rewritten are sent without value
+            RedisObject::Module(ref obj) => {
+                Ok(EntryRewriter::rewrite_module(obj)?.unwrap_or_default())
+            }
+            _ => bail! {Error::SinkerError(format!(
+                "unsupported redis value type: {}, key: {}",
+                entry.get_type(),
+                entry.key
+            ))},
+        }?;
+
+        let key = entry.key.as_bytes();
+        event.is_base64 = Self::has_binary(
+            std::iter::once(key).chain(
+                value_cmds
+                    .iter()
+                    .flat_map(|cmd| cmd.args.iter().map(Vec::as_slice)),
+            ),
+        );
+        event.keys = Self::encode_args(std::iter::once(key), event.is_base64);
+        event.value_type = entry.get_type();
+        event.value_cmds = value_cmds
+            .iter()
+            .map(|cmd| Self::encode_args(cmd.args.iter().map(Vec::as_slice), event.is_base64))
+            .collect();
+        Ok(event)
+    }
+
+    fn has_binary<'a>(mut args: impl Iterator<Item = &'a [u8]>) -> bool {
+        args.any(|arg| std::str::from_utf8(arg).is_err())
+    }
+
+    /// Binary arguments can not be kept in json / avro strings, all keys and arguments
+    /// of the event are base64 encoded if any of them is not valid utf-8
+    fn encode_args<'a>(args: impl Iterator<Item = &'a [u8]>, is_base64: bool) -> Vec<String> {
+        args.map(|arg| {
+            if is_base64 {
+                BASE64_STANDARD.encode(arg)
+            } else {
+                String::from_utf8_lossy(arg).to_string()
+            }
+        })
+        .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use dt_common::meta::redis::redis_object::{RedisCmd, RedisString, StringObject};
+
+    use super::*;
+
+    #[test]
+    fn test_entry_to_event() {
+        let key_parser = KeyParser::new();
+
+        // aof command
+        let mut entry = RedisEntry::new();
+        entry.db_id = 1;
+        entry.cmd = RedisCmd::from_str_args(&["SET", "k1", "v1"]);
+        let event = KafkaSinker::entry_to_event(&key_parser, &mut entry, &Position::None).unwrap();
+        assert_eq!(event.db, 1);
+        assert!(!event.is_base);
+        assert!(!event.is_base64);
+        assert_eq!(event.command, "SET");
+        assert_eq!(event.keys, vec!["k1"]);
+        assert_eq!(event.args, vec!["SET", "k1", "v1"]);
+        assert!(event.value_cmds.is_empty());
+
+        // aof command with a binary value, all keys and args are base64 encoded
+        let mut entry = RedisEntry::new();
+        entry.cmd = RedisCmd::from_args(vec![b"SET".to_vec(), b"k1".to_vec(), vec![0xff, 0xfe]]);
+        let event = KafkaSinker::entry_to_event(&key_parser, &mut entry, &Position::None).unwrap();
+        assert!(event.is_base64);
+        assert_eq!(event.keys, vec!["azE="]);
+        assert_eq!(event.args, vec!["U0VU", "azE=", "//4="]);
+
+        // rdb entry
+        let mut obj = StringObject::new();
+        obj.key = RedisString::from("k2".to_string());
+        obj.value = RedisString::from("v2".to_string());
+        let mut entry = RedisEntry::new();
+        entry.is_base = true;
+        entry.expire_ms = 1000;
+        entry.key = obj.key.clone();
+        entry.value = RedisObject::String(obj);
+        let event = KafkaSinker::entry_to_event(&key_parser, &mut entry, &Position::None).unwrap();
+        assert!(event.is_base);
+        assert!(!event.is_base64);
+        assert!(event.command.is_empty());
+        assert_eq!(event.keys, vec!["k2"]);
+        assert!(event.args.is_empty());
+        assert_eq!(event.value_type, "string");
+        assert_eq!(event.value_cmds, vec![vec!["set", "k2", "v2"]]);
+        assert_eq!(event.expire_ms, 1000);
+        assert_eq!(event.get_partition_key(), "k2");
+    }
 }
@@ -2,6 +2,7 @@ pub mod base_checker;
 pub mod base_sinker;
 pub mod base_struct_sinker;
 pub mod clickhouse;
+pub mod conflict_resolver;
 pub mod dummy_sinker;
 pub mod foxlake;
 pub mod kafka;
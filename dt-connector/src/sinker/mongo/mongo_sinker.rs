@@ -1,8 +1,10 @@
 use std::sync::Arc;
 
+use anyhow::bail;
 use async_trait::async_trait;
 use mongodb::{
-    bson::{doc, Document},
+    bson::{doc, Bson, Document},
+    error::ErrorKind,
     options::UpdateOptions,
     Client, Collection,
 };
@@ -10,10 +12,20 @@ use tokio::{sync::Mutex, time::Instant};
 
 use crate::{call_batch_fn, rdb_router::RdbRouter, sinker::base_sinker::BaseSinker, Sinker};
 use dt_common::{
-    log_error, meta::col_value::ColValue, meta::mongo::mongo_constant::MongoConstants,
-    meta::row_data::RowData, meta::row_type::RowType, monitor::monitor::Monitor,
+    error::Error,
+    log_error, log_info, log_warn,
+    meta::col_value::ColValue,
+    meta::ddl_meta::{ddl_data::DdlData, ddl_statement::DdlStatement, ddl_type::DdlType},
+    meta::mongo::mongo_constant::MongoConstants,
+    meta::row_data::RowData,
+    meta::row_type::RowType,
+    monitor::monitor::Monitor,
 };
 
+// errors of replayed DDL whose effect already exists in target
+// NamespaceNotFound, IndexNotFound, NamespaceExists
+const IGNORED_DDL_ERROR_CODES: [i32; 3] = [26, 27, 48];
+
 #[derive(Clone)]
 pub struct MongoSinker {
     pub router: RdbRouter,
@@ -45,6 +57,62 @@ impl Sinker for MongoSinker {
         Ok(())
     }
 
+    async fn sink_ddl(&mut self, data: Vec<DdlData>, _batch: bool) -> anyhow::Result<()> {
+        for ddl_data in data {
+            let DdlStatement::MongoCommand(s) = &ddl_data.statement else {
+                continue;
+            };
+
+            let value: serde_json::Value = serde_json::from_str(&s.command)?;
+            let Bson::Document(src_command) = Bson::try_from(value)? else {
+                bail! {Error::SinkerError(format!("invalid mongo command: {}", s.command))}
+            };
+
+            // rebuild the command with routed names
+            let (db, command) = match ddl_data.ddl_type {
+                DdlType::RenameTable => {
+                    let command = doc! {
+                        "renameCollection": format!("{}.{}", s.db, s.coll),
+                        "to": format!("{}.{}", s.new_db, s.new_coll),
+                        "dropTarget": src_command.get_bool("dropTarget").unwrap_or(false),
+                    };
+                    ("admin", command)
+                }
+
+                DdlType::DropDatabase => (s.db.as_str(), doc! {"dropDatabase": 1}),
+
+                _ => {
+                    let mut command = Document::new();
+                    for (i, (k, v)) in src_command.into_iter().enumerate() {
+                        // the first key is the command name with collection as value
+                        if i == 0 {
+                            command.insert(k, &s.coll);
+                        } else {
+                            command.insert(k, v);
+                        }
+                    }
+                    (s.db.as_str(), command)
+                }
+            };
+
+            log_info!("sink ddl, db: {}, command: {}", db, command);
+            if let Err(err) = self
+                .mongo_client
+                .database(db)
+                .run_command(command, None)
+                .await
+            {
+                match err.kind.as_ref() {
+                    ErrorKind::Command(e) if IGNORED_DDL_ERROR_CODES.contains(&e.code) => {
+                        log_warn!("sink ddl ignored, db: {}, error: {}", db, e.message);
+                    }
+                    _ => return Err(err.into()),
+                }
+            }
+        }
+        Ok(())
+    }
+
     async fn close(&mut self) -> anyhow::Result<()> {
         self.mongo_client.clone().shutdown().await;
         Ok(())
@@ -9,14 +9,23 @@ use sqlx::{
 use tokio::{sync::Mutex, sync::RwLock, time::Instant};
 
 use crate::{
-    call_batch_fn, close_conn_pool, data_marker::DataMarker, rdb_query_builder::RdbQueryBuilder,
-    rdb_router::RdbRouter, sinker::base_sinker::BaseSinker, Sinker,
+    call_batch_fn, close_conn_pool,
+    data_marker::DataMarker,
+    rdb_query_builder::RdbQueryBuilder,
+    rdb_router::RdbRouter,
+    sinker::{
+        base_sinker::BaseSinker,
+        conflict_resolver::{ConflictResolver, Resolution},
+    },
+    Sinker,
 };
 use dt_common::{
+    config::config_enums::DbType,
     log_error, log_info,
     meta::{
         dcl_meta::dcl_data::DclData,
         ddl_meta::{ddl_data::DdlData, ddl_type::DdlType},
+        dt_data::DtItem,
         mysql::mysql_meta_manager::MysqlMetaManager,
         row_data::RowData,
         row_type::RowType,
@@ -34,6 +43,7 @@ pub struct MysqlSinker {
     pub monitor: Arc<Mutex<Monitor>>,
     pub data_marker: Option<Arc<RwLock<DataMarker>>>,
     pub replace: bool,
+    pub conflict_resolver: Option<ConflictResolver>,
 }
 
 #[async_trait]
@@ -43,14 +53,16 @@ impl Sinker for MysqlSinker {
             return Ok(());
         }
 
-        if !batch {
+        // conflicts are detected row by row
+        if !batch || self.conflict_resolver.is_some() {
             self.serial_sink(&data).await?;
         } else {
             match data[0].row_type {
                 RowType::Insert => {
                     call_batch_fn!(self, data, Self::batch_insert);
                 }
-                RowType::Delete => {
+                // deletes of all rows matching part of the keys can not be batched
+                RowType::Delete if !data.iter().any(|i| i.delete_all) => {
                     call_batch_fn!(self, data, Self::batch_delete);
                 }
                 _ => self.serial_sink(&data).await?,
@@ -60,12 +72,23 @@ impl Sinker for MysqlSinker {
         Ok(())
     }
 
+    /// Used by TransactionParallelizer, each source transaction is sinked in one target transaction
+    async fn sink_raw(&mut self, data: Vec<DtItem>, _batch: bool) -> anyhow::Result<()> {
+        for tx in BaseSinker::split_transactions(data) {
+            self.serial_sink(&tx).await?;
+        }
+        Ok(())
+    }
+
     async fn sink_ddl(&mut self, data: Vec<DdlData>, _batch: bool) -> anyhow::Result<()> {
         for ddl_data in data {
-            let sql = ddl_data.to_sql();
-            let query = sqlx::query(&sql);
+            // truncates may come from other dbs, e.g. pg
+            let sqls = if ddl_data.ddl_type == DdlType::TruncateTable {
+                ddl_data.to_truncate_sqls(&DbType::Mysql)
+            } else {
+                vec![ddl_data.to_sql()]
+            };
             let (db, _tb) = ddl_data.get_schema_tb();
-            log_info!("sink ddl, db: {}, sql: {}", db, sql);
 
             // create a tmp connection with databse since sqlx conn pool does NOT support `USE db`
             let mut conn_options = MySqlConnectOptions::from_str(&self.url)?;
@@ -82,7 +105,10 @@ impl Sinker for MysqlSinker {
                 .max_connections(1)
                 .connect_with(conn_options)
                 .await?;
-            query.execute(&conn_pool).await?;
+            for sql in sqls {
+                log_info!("sink ddl, db: {}, sql: {}", db, sql);
+                sqlx::query(&sql).execute(&conn_pool).await?;
+            }
             conn_pool.close().await;
         }
         Ok(())
@@ -128,7 +154,29 @@ impl MysqlSinker {
             let tb_meta = self.meta_manager.get_tb_meta_by_row_data(row_data).await?;
             let query_builder = RdbQueryBuilder::new_for_mysql(tb_meta, None);
 
-            let query_info = query_builder.get_query_info(row_data, self.replace)?;
+            let mut replace_row_data = None;
+            if let Some(conflict_resolver) = &self.conflict_resolver {
+                let lookup_row_data = ConflictResolver::build_lookup_row(row_data);
+                let query_info = query_builder.get_select_query(&lookup_row_data)?;
+                let query = query_builder.create_mysql_query(&query_info);
+                let target_row_data = query
+                    .fetch_optional(&mut tx)
+                    .await?
+                    .map(|row| RowData::from_mysql_row(&row, tb_meta, &None));
+
+                match conflict_resolver.resolve(row_data, &target_row_data)? {
+                    Resolution::Apply => {}
+                    Resolution::Replace => {
+                        replace_row_data = Some(ConflictResolver::build_replace_row(row_data))
+                    }
+                    Resolution::Skip => continue,
+                }
+            }
+
+            let query_info = match &replace_row_data {
+                Some(replace_row_data) => query_builder.get_query_info(replace_row_data, true)?,
+                None => query_builder.get_query_info(row_data, self.replace)?,
+            };
             let query = query_builder.create_mysql_query(&query_info);
             query
                 .execute(&mut tx)
@@ -228,8 +276,24 @@ impl MysqlSinker {
             //     `src_node` varchar(255) NOT NULL,
             //     `dst_node` varchar(255) NOT NULL,
             //     `n` bigint DEFAULT NULL,
+            //     `data_path` varchar(1024) NOT NULL DEFAULT '',
             //     PRIMARY KEY (`data_origin_node`, `src_node`, `dst_node`)
             // ) ENGINE=InnoDB DEFAULT CHARSET=utf8mb3;
+            if data_marker.track_path() {
+                let sql = format!(
+                    "INSERT INTO `{}`.`{}`(data_origin_node, src_node, dst_node, n, data_path) 
+                    VALUES('{}', '{}', '{}', 1, '{}') 
+                    ON DUPLICATE KEY UPDATE n=n+1, data_path=VALUES(data_path)",
+                    data_marker.marker_schema,
+                    data_marker.marker_tb,
+                    data_marker.data_origin_node,
+                    data_marker.src_node,
+                    data_marker.dst_node,
+                    data_marker.get_dst_data_path()
+                );
+                return Some(sql);
+            }
+
             let sql = format!(
                 "INSERT INTO `{}`.`{}`(data_origin_node, src_node, dst_node, n) 
                 VALUES('{}', '{}', '{}', 1) 
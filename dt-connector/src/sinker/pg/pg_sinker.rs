@@ -1,27 +1,40 @@
-use std::{str::FromStr, sync::Arc};
+use std::{
+    ops::{Deref, DerefMut},
+    str::FromStr,
+    sync::Arc,
+};
 
 use anyhow::Context;
 use async_trait::async_trait;
 use sqlx::{
+    pool::PoolConnection,
     postgres::{PgConnectOptions, PgPoolOptions},
-    Executor, Pool, Postgres,
+    Connection, Executor, PgConnection, Pool, Postgres,
 };
 use tokio::{sync::Mutex, sync::RwLock, time::Instant};
 
 use crate::{
-    call_batch_fn, close_conn_pool, data_marker::DataMarker, rdb_query_builder::RdbQueryBuilder,
-    rdb_router::RdbRouter, sinker::base_sinker::BaseSinker, Sinker,
+    call_batch_fn, close_conn_pool,
+    data_marker::DataMarker,
+    rdb_query_builder::RdbQueryBuilder,
+    rdb_router::RdbRouter,
+    sinker::{
+        base_sinker::BaseSinker,
+        conflict_resolver::{ConflictResolver, Resolution},
+    },
+    Sinker,
 };
 use dt_common::{
+    config::config_enums::DbType,
     log_error, log_info,
     meta::{
-        ddl_meta::ddl_data::DdlData, ddl_meta::ddl_type::DdlType,
-        pg::pg_meta_manager::PgMetaManager, row_data::RowData, row_type::RowType,
+        ddl_meta::ddl_data::DdlData, ddl_meta::ddl_type::DdlType, dt_data::DtItem,
+        message_data::MessageData, pg::pg_meta_manager::PgMetaManager, row_data::RowData,
+        row_type::RowType,
     },
     monitor::monitor::Monitor,
 };
 
-#[derive(Clone)]
 pub struct PgSinker {
     pub url: String,
     pub conn_pool: Pool<Postgres>,
@@ -31,6 +44,43 @@ pub struct PgSinker {
     pub monitor: Arc<Mutex<Monitor>>,
     pub data_marker: Option<Arc<RwLock<DataMarker>>>,
     pub replace: bool,
+    pub conflict_resolver: Option<ConflictResolver>,
+    // index among parallel sinkers, used to name the replication origin of the session
+    pub sinker_index: usize,
+    // session kept across batches with the replication origin set up
+    pub origin_session: Option<PgOriginSession>,
+}
+
+/// A connection whose session may have a replication origin set up. If dropped without being
+/// released, e.g. sinking failed halfway, the connection is closed instead of going back to
+/// the pool, so the server resets the origin and no other query is tagged with it
+pub struct PgOriginSession {
+    conn: Option<PoolConnection<Postgres>>,
+    origin: String,
+}
+
+impl Deref for PgOriginSession {
+    type Target = PgConnection;
+
+    fn deref(&self) -> &Self::Target {
+        self.conn.as_ref().unwrap()
+    }
+}
+
+impl DerefMut for PgOriginSession {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.conn.as_mut().unwrap()
+    }
+}
+
+impl Drop for PgOriginSession {
+    fn drop(&mut self) {
+        if let Some(conn) = self.conn.take() {
+            if !self.origin.is_empty() {
+                let _ = conn.detach();
+            }
+        }
+    }
 }
 
 #[async_trait]
@@ -40,14 +90,16 @@ impl Sinker for PgSinker {
             return Ok(());
         }
 
-        if !batch {
+        // conflicts are detected row by row
+        if !batch || self.conflict_resolver.is_some() {
             self.serial_sink(&data).await?;
         } else {
             match data[0].row_type {
                 RowType::Insert => {
                     call_batch_fn!(self, data, Self::batch_insert);
                 }
-                RowType::Delete => {
+                // deletes of all rows matching part of the keys can not be batched
+                RowType::Delete if !data.iter().any(|i| i.delete_all) => {
                     call_batch_fn!(self, data, Self::batch_delete);
                 }
                 _ => self.serial_sink(&data).await?,
@@ -56,6 +108,14 @@ impl Sinker for PgSinker {
         Ok(())
     }
 
+    /// Used by TransactionParallelizer, each source transaction is sinked in one target transaction
+    async fn sink_raw(&mut self, data: Vec<DtItem>, _batch: bool) -> anyhow::Result<()> {
+        for tx in BaseSinker::split_transactions(data) {
+            self.serial_sink(&tx).await?;
+        }
+        Ok(())
+    }
+
     async fn sink_ddl(&mut self, data: Vec<DdlData>, _batch: bool) -> anyhow::Result<()> {
         for ddl_data in data {
             let (schema, _tb) = ddl_data.get_schema_tb();
@@ -78,7 +138,12 @@ impl Sinker for PgSinker {
                 }
             }
 
-            let sql = ddl_data.to_sql();
+            // truncates may come from other dbs, e.g. mysql
+            let sql = if ddl_data.ddl_type == DdlType::TruncateTable {
+                ddl_data.statement.to_sql(&DbType::Pg)
+            } else {
+                ddl_data.to_sql()
+            };
             log_info!("sink ddl, schema: {}, sql: {}", schema, sql);
 
             let conn_pool = pool_options.connect_with(conn_options).await?;
@@ -89,6 +154,23 @@ impl Sinker for PgSinker {
         Ok(())
     }
 
+    /// Messages are emitted into the target wal as they are, so downstream consumers of
+    /// the target get them too
+    async fn sink_message(&mut self, data: Vec<MessageData>) -> anyhow::Result<()> {
+        let mut conn = self.acquire_conn().await?;
+        for message_data in data.iter() {
+            sqlx::query("SELECT pg_logical_emit_message($1, $2, $3)")
+                .bind(message_data.transactional)
+                .bind(&message_data.prefix)
+                .bind(&message_data.content)
+                .execute(&mut *conn)
+                .await
+                .with_context(|| format!("failed to emit message: [{:?}]", message_data))?;
+        }
+        self.release_conn(conn);
+        Ok(())
+    }
+
     async fn refresh_meta(&mut self, data: Vec<DdlData>) -> anyhow::Result<()> {
         for ddl_data in data.iter() {
             self.meta_manager.invalidate_cache_by_ddl_data(ddl_data);
@@ -97,6 +179,12 @@ impl Sinker for PgSinker {
     }
 
     async fn close(&mut self) -> anyhow::Result<()> {
+        if let Some(mut session) = self.origin_session.take() {
+            sqlx::query("SELECT pg_replication_origin_session_reset()")
+                .execute(&mut *session)
+                .await?;
+            session.origin.clear();
+        }
         self.meta_manager.close().await?;
         return close_conn_pool!(self);
     }
@@ -107,7 +195,8 @@ impl PgSinker {
         let start_time = Instant::now();
         let mut data_size = 0;
 
-        let mut tx = self.conn_pool.begin().await?;
+        let mut conn = self.acquire_conn().await?;
+        let mut tx = conn.begin().await?;
         if let Some(sql) = self.get_data_marker_sql().await {
             sqlx::query(&sql)
                 .execute(&mut tx)
@@ -120,7 +209,29 @@ impl PgSinker {
             let tb_meta = self.meta_manager.get_tb_meta_by_row_data(row_data).await?;
             let query_builder = RdbQueryBuilder::new_for_pg(tb_meta, None);
 
-            let query_info = query_builder.get_query_info(row_data, self.replace)?;
+            let mut replace_row_data = None;
+            if let Some(conflict_resolver) = &self.conflict_resolver {
+                let lookup_row_data = ConflictResolver::build_lookup_row(row_data);
+                let query_info = query_builder.get_select_query(&lookup_row_data)?;
+                let query = query_builder.create_pg_query(&query_info);
+                let target_row_data = query
+                    .fetch_optional(&mut tx)
+                    .await?
+                    .map(|row| RowData::from_pg_row(&row, tb_meta, &None));
+
+                match conflict_resolver.resolve(row_data, &target_row_data)? {
+                    Resolution::Apply => {}
+                    Resolution::Replace => {
+                        replace_row_data = Some(ConflictResolver::build_replace_row(row_data))
+                    }
+                    Resolution::Skip => continue,
+                }
+            }
+
+            let query_info = match &replace_row_data {
+                Some(replace_row_data) => query_builder.get_query_info(replace_row_data, true)?,
+                None => query_builder.get_query_info(row_data, self.replace)?,
+            };
             let query = query_builder.create_pg_query(&query_info);
             query
                 .execute(&mut tx)
@@ -128,6 +239,7 @@ impl PgSinker {
                 .with_context(|| format!("serial sink failed, row_data: [{}]", row_data))?;
         }
         tx.commit().await?;
+        self.release_conn(conn);
 
         BaseSinker::update_serial_monitor(&mut self.monitor, data.len(), data_size, start_time)
             .await
@@ -154,7 +266,9 @@ impl PgSinker {
             query.execute(&mut tx).await?;
             tx.commit().await?;
         } else {
-            query.execute(&self.conn_pool).await?;
+            let mut conn = self.acquire_conn().await?;
+            query.execute(&mut *conn).await?;
+            self.release_conn(conn);
         }
 
         BaseSinker::update_batch_monitor(&mut self.monitor, batch_size, data_size, start_time).await
@@ -185,10 +299,10 @@ impl PgSinker {
             query.execute(&mut tx).await?;
             tx.commit().await
         } else {
-            match query.execute(&self.conn_pool).await {
-                Err(e) => Err(e),
-                _ => Ok(()),
-            }
+            let mut conn = self.acquire_conn().await?;
+            let result = query.execute(&mut *conn).await.map(|_| ());
+            self.release_conn(conn);
+            result
         };
 
         if let Err(error) = exec_error {
@@ -205,16 +319,97 @@ impl PgSinker {
         BaseSinker::update_batch_monitor(&mut self.monitor, batch_size, data_size, start_time).await
     }
 
+    /// With replication origin strategy, the session is set up with the origin of the data
+    /// instead of writing the marker table, so the changes are tagged in wal directly.
+    /// The session is reused by later batches and set up again only if the origin changes
+    async fn acquire_conn(&mut self) -> anyhow::Result<PgOriginSession> {
+        let Some(origin) = self.get_replication_origin().await else {
+            return Ok(PgOriginSession {
+                conn: Some(self.conn_pool.acquire().await?),
+                origin: String::new(),
+            });
+        };
+
+        let mut session = match self.origin_session.take() {
+            Some(session) => session,
+            None => PgOriginSession {
+                conn: Some(self.conn_pool.acquire().await?),
+                origin: String::new(),
+            },
+        };
+        if session.origin == origin {
+            return Ok(session);
+        }
+
+        if !session.origin.is_empty() {
+            sqlx::query("SELECT pg_replication_origin_session_reset()")
+                .execute(&mut *session)
+                .await?;
+        }
+        sqlx::query(
+            "SELECT pg_replication_origin_create($1) WHERE NOT EXISTS (SELECT 1 FROM pg_replication_origin WHERE roname = $1)",
+        )
+        .bind(&origin)
+        .execute(&mut *session)
+        .await
+        .with_context(|| format!("failed to create replication origin: [{}]", origin))?;
+        sqlx::query("SELECT pg_replication_origin_session_setup($1)")
+            .bind(&origin)
+            .execute(&mut *session)
+            .await
+            .with_context(|| format!("failed to set up replication origin: [{}]", origin))?;
+        session.origin = origin;
+        Ok(session)
+    }
+
+    fn release_conn(&mut self, session: PgOriginSession) {
+        if !session.origin.is_empty() {
+            self.origin_session = Some(session);
+        }
+    }
+
+    async fn get_replication_origin(&self) -> Option<String> {
+        if let Some(data_marker) = &self.data_marker {
+            let data_marker = data_marker.read().await;
+            if data_marker.use_replication_origin() {
+                return Some(data_marker.get_replication_origin(self.sinker_index));
+            }
+        }
+        None
+    }
+
     async fn get_data_marker_sql(&self) -> Option<String> {
         if let Some(data_marker) = &self.data_marker {
             let data_marker = data_marker.read().await;
+            if data_marker.use_replication_origin() {
+                return None;
+            }
             // CREATE TABLE ape_trans_pg.topo1 (
             //     data_origin_node varchar(255) NOT NULL,
             //     src_node varchar(255) NOT NULL,
             //     dst_node varchar(255) NOT NULL,
             //     n bigint DEFAULT NULL,
+            //     data_path varchar(1024) NOT NULL DEFAULT '',
             //     PRIMARY KEY (data_origin_node, src_node, dst_node)
             //   );
+            if data_marker.track_path() {
+                let sql = format!(
+                    r#"INSERT INTO "{}"."{}"(data_origin_node, src_node, dst_node, n, data_path)
+                    VALUES('{}', '{}', '{}', 1, '{}') 
+                    ON CONFLICT (data_origin_node, src_node, dst_node) 
+                    DO UPDATE SET n="{}"."{}".n+1, data_path=EXCLUDED.data_path"#,
+                    data_marker.marker_schema,
+                    data_marker.marker_tb,
+                    data_marker.data_origin_node,
+                    data_marker.src_node,
+                    data_marker.dst_node,
+                    data_marker.get_dst_data_path(),
+                    data_marker.marker_schema,
+                    data_marker.marker_tb,
+                );
+                return Some(sql);
+            }
+
             let sql = format!(
                 r#"INSERT INTO "{}"."{}"(data_origin_node, src_node, dst_node, n)
                 VALUES('{}', '{}', '{}', 1) 
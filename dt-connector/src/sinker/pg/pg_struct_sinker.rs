@@ -6,7 +6,8 @@ use crate::{
 };
 
 use dt_common::{
-    config::config_enums::ConflictPolicyEnum, meta::struct_meta::struct_data::StructData,
+    config::config_enums::{ConflictPolicyEnum, StructPhase},
+    meta::struct_meta::struct_data::StructData,
     rdb_filter::RdbFilter,
 };
 
@@ -19,21 +20,37 @@ pub struct PgStructSinker {
     pub conflict_policy: ConflictPolicyEnum,
     pub filter: RdbFilter,
     pub router: RdbRouter,
+    pub phase: StructPhase,
+    pub parallel_size: usize,
+    // created in close, after all batches are sinked
+    pub foreign_key_sqls: Vec<(String, String)>,
 }
 
 #[async_trait]
 impl Sinker for PgStructSinker {
     async fn sink_struct(&mut self, data: Vec<StructData>) -> anyhow::Result<()> {
-        BaseStructSinker::sink_structs(
+        BaseStructSinker::sink_structs_in_phase(
             &DBConnPool::PostgreSQL(self.conn_pool.clone()),
             &self.conflict_policy,
             data,
             &self.filter,
+            &self.phase,
+            self.parallel_size,
+            &mut self.foreign_key_sqls,
         )
         .await
     }
 
     async fn close(&mut self) -> anyhow::Result<()> {
+        if !self.foreign_key_sqls.is_empty() {
+            BaseStructSinker::sink_foreign_keys(
+                &DBConnPool::PostgreSQL(self.conn_pool.clone()),
+                &self.conflict_policy,
+                std::mem::take(&mut self.foreign_key_sqls),
+                self.parallel_size,
+            )
+            .await?;
+        }
         return close_conn_pool!(self);
     }
 }
@@ -0,0 +1,440 @@
+use std::collections::{BTreeMap, HashMap};
+
+use anyhow::bail;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use dt_common::{
+    error::Error,
+    meta::{col_value::ColValue, redis::redis_object::RedisCmd, row_data::RowData},
+};
+
+const JSON_PREFIX: &str = "json:";
+const PLACEHOLDER_START: &str = "${";
+const PLACEHOLDER_END: &str = "}";
+
+type ColValues = HashMap<String, ColValue>;
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DmlValueType {
+    // HSET key col_1 value_1 col_2 value_2 ...
+    #[default]
+    Hash,
+    // SET key {"col_1":value_1,"col_2":value_2}
+    String,
+    // JSON.SET key $ {"col_1":value_1,"col_2":value_2}, requires RedisJSON
+    Json,
+    // ZADD key score member
+    Zset,
+}
+
+/// secondary index of a row, maintained by: SADD key member
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IndexTemplate {
+    pub key: String,
+    pub member: String,
+}
+
+/// how rows of a table are written to redis,
+/// key / member support column placeholders, example: order:${user_id}:${order_id}
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DmlTemplate {
+    pub db: String,
+    pub tb: String,
+    pub key: String,
+    #[serde(default, rename = "type")]
+    pub value_type: DmlValueType,
+    // columns written to hash / string / json, empty means all columns
+    #[serde(default)]
+    pub cols: Vec<String>,
+    // for zset
+    #[serde(default)]
+    pub member: String,
+    #[serde(default)]
+    pub score_col: String,
+    // ttl in seconds from a column, takes priority over ttl_secs, not applied to zset
+    #[serde(default)]
+    pub ttl_col: String,
+    #[serde(default)]
+    pub ttl_secs: u64,
+    #[serde(default)]
+    pub indexes: Vec<IndexTemplate>,
+}
+
+impl DmlTemplate {
+    pub fn parse_templates(
+        config_str: &str,
+    ) -> anyhow::Result<HashMap<(String, String), DmlTemplate>> {
+        let mut results = HashMap::new();
+        if config_str.trim().is_empty() {
+            return Ok(results);
+        }
+
+        // templates=json:[{"db":"db_1","tb":"tb_1","key":"user:${id}","type":"hash","ttl_col":"ttl"}]
+        let templates: Vec<DmlTemplate> =
+            serde_json::from_str(config_str.trim_start_matches(JSON_PREFIX))?;
+        for template in templates {
+            template.validate()?;
+            results.insert((template.db.clone(), template.tb.clone()), template);
+        }
+        Ok(results)
+    }
+
+    fn validate(&self) -> anyhow::Result<()> {
+        let invalid = |reason: &str| -> anyhow::Result<()> {
+            bail! {Error::ConfigError(format!(
+                "invalid redis template of {}.{}, {}",
+                self.db, self.tb, reason
+            ))}
+        };
+
+        if self.key.is_empty() {
+            return invalid("key is empty");
+        }
+        if self.value_type == DmlValueType::Zset
+            && (self.member.is_empty() || self.score_col.is_empty())
+        {
+            return invalid("member and score_col are required for zset");
+        }
+
+        let mut templates = vec![&self.key, &self.member];
+        for index in self.indexes.iter() {
+            if index.key.is_empty() || index.member.is_empty() {
+                return invalid("key and member are required for index");
+            }
+            templates.push(&index.key);
+            templates.push(&index.member);
+        }
+        for template in templates {
+            if Self::parse_placeholders(template).is_none() {
+                return invalid(&format!("unclosed placeholder in: {}", template));
+            }
+        }
+        Ok(())
+    }
+
+    pub fn to_cmds(&self, row_data: &RowData) -> anyhow::Result<Vec<RedisCmd>> {
+        let empty = ColValues::new();
+        let before = row_data.before.as_ref().unwrap_or(&empty);
+        let after = row_data.after.as_ref().unwrap_or(&empty);
+
+        let mut cmds = self.build_del_cmds(before, after);
+        cmds.extend(self.build_set_cmds(after)?);
+        Ok(cmds)
+    }
+
+    /// clean up what was written by before, unless it will be overwritten by after
+    fn build_del_cmds(&self, before: &ColValues, after: &ColValues) -> Vec<RedisCmd> {
+        let mut cmds = Vec::new();
+        if before.is_empty() {
+            return cmds;
+        }
+        let is_delete = after.is_empty();
+
+        if let Some((key, member)) = self.render_primary(before) {
+            if is_delete || self.render_primary(after) != Some((key.clone(), member.clone())) {
+                let cmd = if self.value_type == DmlValueType::Zset {
+                    RedisCmd::from_str_args(&["ZREM", &key, &member])
+                } else {
+                    RedisCmd::from_str_args(&["DEL", &key])
+                };
+                cmds.push(cmd);
+            }
+        }
+
+        for index in self.indexes.iter() {
+            if let Some((key, member)) = Self::render_pair(&index.key, &index.member, before) {
+                if is_delete
+                    || Self::render_pair(&index.key, &index.member, after)
+                        != Some((key.clone(), member.clone()))
+                {
+                    cmds.push(RedisCmd::from_str_args(&["SREM", &key, &member]));
+                }
+            }
+        }
+        cmds
+    }
+
+    fn build_set_cmds(&self, col_values: &ColValues) -> anyhow::Result<Vec<RedisCmd>> {
+        let mut cmds = Vec::new();
+        if col_values.is_empty() {
+            return Ok(cmds);
+        }
+
+        if let Some((key, member)) = self.render_primary(col_values) {
+            let selected_cols = self.select_cols(col_values);
+            let cmd = match self.value_type {
+                DmlValueType::Hash | DmlValueType::String | DmlValueType::Json
+                    if selected_cols.is_empty() =>
+                {
+                    None
+                }
+
+                DmlValueType::Hash => {
+                    let mut cmd = RedisCmd::from_str_args(&["HSET", &key]);
+                    for (col, col_value) in selected_cols {
+                        cmd.add_str_arg(col);
+                        cmd.add_str_arg(&col_value.to_option_string().unwrap_or_default());
+                    }
+                    Some(cmd)
+                }
+
+                DmlValueType::String => {
+                    let value = json!(selected_cols).to_string();
+                    Some(RedisCmd::from_str_args(&["SET", &key, &value]))
+                }
+
+                DmlValueType::Json => {
+                    let value = json!(selected_cols).to_string();
+                    Some(RedisCmd::from_str_args(&["JSON.SET", &key, "$", &value]))
+                }
+
+                DmlValueType::Zset => {
+                    match col_values
+                        .get(&self.score_col)
+                        .and_then(|v| v.to_option_string())
+                    {
+                        Some(score) => {
+                            if score.parse::<f64>().is_err() {
+                                bail! {Error::SinkerError(format!(
+                                    "invalid zset score: {}, col: {}, tb: {}.{}",
+                                    score, self.score_col, self.db, self.tb
+                                ))}
+                            }
+                            Some(RedisCmd::from_str_args(&["ZADD", &key, &score, &member]))
+                        }
+                        None => None,
+                    }
+                }
+            };
+
+            if let Some(cmd) = cmd {
+                cmds.push(cmd);
+                if self.value_type != DmlValueType::Zset {
+                    if let Some(ttl) = self.get_ttl_secs(col_values) {
+                        cmds.push(RedisCmd::from_str_args(&["EXPIRE", &key, &ttl.to_string()]));
+                    }
+                }
+            }
+        }
+
+        for index in self.indexes.iter() {
+            if let Some((key, member)) = Self::render_pair(&index.key, &index.member, col_values) {
+                cmds.push(RedisCmd::from_str_args(&["SADD", &key, &member]));
+            }
+        }
+        Ok(cmds)
+    }
+
+    fn select_cols<'a>(&self, col_values: &'a ColValues) -> BTreeMap<&'a String, &'a ColValue> {
+        // sorted by column names to keep the output stable
+        col_values
+            .iter()
+            .filter(|(col, _)| self.cols.is_empty() || self.cols.contains(col))
+            .collect()
+    }
+
+    fn get_ttl_secs(&self, col_values: &ColValues) -> Option<u64> {
+        if !self.ttl_col.is_empty() {
+            if let Some(ttl) = col_values
+                .get(&self.ttl_col)
+                .and_then(|v| v.to_option_string())
+                .and_then(|v| v.parse::<i64>().ok())
+            {
+                return if ttl > 0 { Some(ttl as u64) } else { None };
+            }
+        }
+
+        if self.ttl_secs > 0 {
+            Some(self.ttl_secs)
+        } else {
+            None
+        }
+    }
+
+    /// rendered (key, member) of the row, member is empty if not zset
+    fn render_primary(&self, col_values: &ColValues) -> Option<(String, String)> {
+        Self::render_pair(&self.key, &self.member, col_values)
+    }
+
+    /// None if any column in placeholders is missing or NULL
+    fn render_pair(key: &str, member: &str, col_values: &ColValues) -> Option<(String, String)> {
+        Some((
+            Self::render(key, col_values)?,
+            Self::render(member, col_values)?,
+        ))
+    }
+
+    fn render(template: &str, col_values: &ColValues) -> Option<String> {
+        let mut result = String::new();
+        for (is_col, token) in Self::parse_placeholders(template)? {
+            if is_col {
+                result.push_str(&col_values.get(token)?.to_option_string()?);
+            } else {
+                result.push_str(token);
+            }
+        }
+        Some(result)
+    }
+
+    /// split template into (is_col, token), None if any placeholder is unclosed
+    fn parse_placeholders(template: &str) -> Option<Vec<(bool, &str)>> {
+        let mut tokens = Vec::new();
+        let mut rest = template;
+        while let Some(start) = rest.find(PLACEHOLDER_START) {
+            let end = rest[start..].find(PLACEHOLDER_END)? + start;
+            tokens.push((false, &rest[..start]));
+            tokens.push((true, &rest[start + PLACEHOLDER_START.len()..end]));
+            rest = &rest[end + PLACEHOLDER_END.len()..];
+        }
+        tokens.push((false, rest));
+        Some(tokens)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dt_common::meta::row_type::RowType;
+
+    fn build_row(
+        row_type: RowType,
+        before: Option<Vec<(&str, ColValue)>>,
+        after: Option<Vec<(&str, ColValue)>>,
+    ) -> RowData {
+        let to_map = |cols: Vec<(&str, ColValue)>| {
+            cols.into_iter()
+                .map(|(k, v)| (k.to_string(), v))
+                .collect::<ColValues>()
+        };
+        RowData::new(
+            "db_1".into(),
+            "tb_1".into(),
+            row_type,
+            before.map(to_map),
+            after.map(to_map),
+        )
+    }
+
+    fn to_strs(cmds: Vec<RedisCmd>) -> Vec<String> {
+        cmds.iter().map(|i| i.to_string()).collect()
+    }
+
+    #[test]
+    fn test_hash_template() {
+        let config = r#"json:[{"db":"db_1","tb":"tb_1","key":"order:${user_id}:${id}","cols":["name","ttl"],"ttl_col":"ttl","indexes":[{"key":"idx:city:${city}","member":"${user_id}:${id}"}]}]"#;
+        let templates = DmlTemplate::parse_templates(config).unwrap();
+        let template = templates.get(&("db_1".into(), "tb_1".into())).unwrap();
+
+        let row = |id: i32, city: ColValue| {
+            vec![
+                ("id", ColValue::Long(id)),
+                ("user_id", ColValue::Long(10)),
+                ("name", ColValue::String("a b".into())),
+                ("city", city),
+                ("ttl", ColValue::Long(60)),
+            ]
+        };
+
+        // insert
+        let row_data = build_row(
+            RowType::Insert,
+            None,
+            Some(row(1, ColValue::String("bj".into()))),
+        );
+        assert_eq!(
+            to_strs(template.to_cmds(&row_data).unwrap()),
+            vec![
+                "HSET order:10:1 name a b ttl 60",
+                "EXPIRE order:10:1 60",
+                "SADD idx:city:bj 10:1",
+            ]
+        );
+
+        // update with key and index changed
+        let row_data = build_row(
+            RowType::Update,
+            Some(row(1, ColValue::String("bj".into()))),
+            Some(row(2, ColValue::None)),
+        );
+        assert_eq!(
+            to_strs(template.to_cmds(&row_data).unwrap()),
+            vec![
+                "DEL order:10:1",
+                "SREM idx:city:bj 10:1",
+                "HSET order:10:2 name a b ttl 60",
+                "EXPIRE order:10:2 60",
+            ]
+        );
+
+        // update with key unchanged
+        let row_data = build_row(
+            RowType::Update,
+            Some(row(1, ColValue::String("bj".into()))),
+            Some(row(1, ColValue::String("bj".into()))),
+        );
+        assert_eq!(
+            to_strs(template.to_cmds(&row_data).unwrap()),
+            vec![
+                "HSET order:10:1 name a b ttl 60",
+                "EXPIRE order:10:1 60",
+                "SADD idx:city:bj 10:1",
+            ]
+        );
+
+        // delete
+        let row_data = build_row(
+            RowType::Delete,
+            Some(row(1, ColValue::String("bj".into()))),
+            None,
+        );
+        assert_eq!(
+            to_strs(template.to_cmds(&row_data).unwrap()),
+            vec!["DEL order:10:1", "SREM idx:city:bj 10:1"]
+        );
+    }
+
+    #[test]
+    fn test_json_and_zset_template() {
+        let config = r#"json:[{"db":"db_1","tb":"tb_1","key":"user:${id}","type":"json","cols":["id","name"],"ttl_secs":100},{"db":"db_1","tb":"tb_2","key":"rank","type":"zset","member":"${id}","score_col":"score"}]"#;
+        let templates = DmlTemplate::parse_templates(config).unwrap();
+
+        let template = templates.get(&("db_1".into(), "tb_1".into())).unwrap();
+        let row_data = build_row(
+            RowType::Insert,
+            None,
+            Some(vec![
+                ("id", ColValue::Long(1)),
+                ("name", ColValue::String("a".into())),
+                ("score", ColValue::Double(1.5)),
+            ]),
+        );
+        assert_eq!(
+            to_strs(template.to_cmds(&row_data).unwrap()),
+            vec![
+                r#"JSON.SET user:1 $ {"id":1,"name":"a"}"#,
+                "EXPIRE user:1 100",
+            ]
+        );
+
+        let template = templates.get(&("db_1".into(), "tb_2".into())).unwrap();
+        assert_eq!(
+            to_strs(template.to_cmds(&row_data).unwrap()),
+            vec!["ZADD rank 1.5 1"]
+        );
+    }
+
+    #[test]
+    fn test_invalid_template() {
+        let configs = [
+            r#"json:[{"db":"db_1","tb":"tb_1","key":""}]"#,
+            r#"json:[{"db":"db_1","tb":"tb_1","key":"user:${id"}]"#,
+            r#"json:[{"db":"db_1","tb":"tb_1","key":"rank","type":"zset"}]"#,
+            r#"json:[{"db":"db_1","tb":"tb_1","key":"user","type":"list"}]"#,
+        ];
+        for config in configs {
+            assert!(DmlTemplate::parse_templates(config).is_err());
+        }
+    }
+}
@@ -1,12 +1,14 @@
-use anyhow::bail;
-use dt_common::error::Error;
 use dt_common::meta::redis::{
     redis_entry::RedisEntry,
     redis_object::{
-        HashObject, ListObject, ModuleObject, RedisCmd, SetObject, StringObject, ZsetObject,
+        BloomObject, HashObject, ListObject, ModuleObject, ModuleValue, RedisCmd, RedisString,
+        SetObject, StringObject, TimeSeriesObject, ZsetObject,
     },
 };
 
+const BLOOM_CHUNK_SIZE: usize = 1024 * 1024;
+const TIME_SERIES_MADD_SIZE: usize = 1000;
+
 const CRC64_TABLE: [u64; 256] = [
     0x0000000000000000,
     0x7ad870c830358979,
@@ -305,10 +307,115 @@ impl EntryRewriter {
         Ok(cmds)
     }
 
-    pub fn rewrite_module(_obj: &mut ModuleObject) -> anyhow::Result<Vec<RedisCmd>> {
-        bail! {Error::RedisRdbError(
-            "module rewrite not implemented".into(),
-        )}
+    /// Returns None if the module type is not supported or failed to decode,
+    /// in which case the entry can only be restored by raw bytes.
+    pub fn rewrite_module(obj: &ModuleObject) -> anyhow::Result<Option<Vec<RedisCmd>>> {
+        let cmds = match &obj.value {
+            ModuleValue::Json(json) => {
+                let mut cmd = RedisCmd::new();
+                cmd.add_str_arg("json.set");
+                cmd.add_redis_arg(&obj.key);
+                cmd.add_str_arg("$");
+                cmd.add_str_arg(json);
+                vec![cmd]
+            }
+            ModuleValue::Bloom(bloom) => Self::rewrite_bloom(&obj.key, bloom),
+            ModuleValue::TimeSeries(ts) => Self::rewrite_time_series(&obj.key, ts),
+            ModuleValue::Unknown => return Ok(None),
+        };
+        Ok(Some(cmds))
+    }
+
+    /// Rewrites a bloom filter as BF.LOADCHUNK commands, in the same layout as BF.SCANDUMP:
+    /// iterator 1 carries the chain header, then filter bits follow with
+    /// iterator = (end offset of the chunk in all filters) + 1.
+    fn rewrite_bloom(key: &RedisString, bloom: &BloomObject) -> Vec<RedisCmd> {
+        // the key is rebuilt as a whole, BF.LOADCHUNK fails on existing keys
+        let mut cmds = vec![Self::del_cmd(key)];
+
+        // packed dumpedChainHeader and dumpedChainLink of RedisBloom, in little endian
+        let mut header = Vec::new();
+        header.extend_from_slice(&bloom.size.to_le_bytes());
+        header.extend_from_slice(&(bloom.filters.len() as u32).to_le_bytes());
+        header.extend_from_slice(&(bloom.options as u32).to_le_bytes());
+        header.extend_from_slice(&(bloom.growth as u32).to_le_bytes());
+        for filter in bloom.filters.iter() {
+            header.extend_from_slice(&(filter.data.len() as u64).to_le_bytes());
+            header.extend_from_slice(&filter.bits.to_le_bytes());
+            header.extend_from_slice(&filter.size.to_le_bytes());
+            header.extend_from_slice(&filter.error.to_le_bytes());
+            header.extend_from_slice(&filter.bpe.to_le_bytes());
+            header.extend_from_slice(&(filter.hashes as u32).to_le_bytes());
+            header.extend_from_slice(&filter.entries.to_le_bytes());
+            header.push(filter.n2 as u8);
+        }
+        cmds.push(Self::bloom_load_chunk_cmd(key, 1, header));
+
+        let mut offset = 0;
+        for filter in bloom.filters.iter() {
+            for chunk in filter.data.chunks(BLOOM_CHUNK_SIZE) {
+                offset += chunk.len();
+                cmds.push(Self::bloom_load_chunk_cmd(key, offset + 1, chunk.to_vec()));
+            }
+        }
+        cmds
+    }
+
+    fn del_cmd(key: &RedisString) -> RedisCmd {
+        let mut cmd = RedisCmd::new();
+        cmd.add_str_arg("del");
+        cmd.add_redis_arg(key);
+        cmd
+    }
+
+    fn bloom_load_chunk_cmd(key: &RedisString, iter: usize, data: Vec<u8>) -> RedisCmd {
+        let mut cmd = RedisCmd::new();
+        cmd.add_str_arg("bf.loadchunk");
+        cmd.add_redis_arg(key);
+        cmd.add_str_arg(&iter.to_string());
+        cmd.add_arg(data);
+        cmd
+    }
+
+    fn rewrite_time_series(key: &RedisString, ts: &TimeSeriesObject) -> Vec<RedisCmd> {
+        // the key is rebuilt as a whole, TS.CREATE fails on existing keys
+        let mut create_cmd = RedisCmd::new();
+        create_cmd.add_str_arg("ts.create");
+        create_cmd.add_redis_arg(key);
+        create_cmd.add_str_arg("retention");
+        create_cmd.add_str_arg(&ts.retention_ms.to_string());
+        create_cmd.add_str_arg("chunk_size");
+        create_cmd.add_str_arg(&ts.chunk_size.to_string());
+        create_cmd.add_str_arg("encoding");
+        create_cmd.add_str_arg(if ts.uncompressed {
+            "uncompressed"
+        } else {
+            "compressed"
+        });
+        if let Some(duplicate_policy) = &ts.duplicate_policy {
+            create_cmd.add_str_arg("duplicate_policy");
+            create_cmd.add_str_arg(duplicate_policy);
+        }
+        if !ts.labels.is_empty() {
+            create_cmd.add_str_arg("labels");
+            for (label, value) in ts.labels.iter() {
+                create_cmd.add_redis_arg(label);
+                create_cmd.add_redis_arg(value);
+            }
+        }
+
+        let mut cmds = vec![Self::del_cmd(key), create_cmd];
+        for samples in ts.samples.chunks(TIME_SERIES_MADD_SIZE) {
+            let mut cmd = RedisCmd::new();
+            cmd.add_str_arg("ts.madd");
+            for (timestamp, value) in samples {
+                cmd.add_redis_arg(key);
+                cmd.add_str_arg(&timestamp.to_string());
+                cmd.add_str_arg(&value.to_string());
+            }
+            cmds.push(cmd);
+        }
+        cmds
     }
 
     pub fn rewrite_set(obj: &mut SetObject) -> anyhow::Result<Vec<RedisCmd>> {
@@ -379,7 +486,11 @@ impl EntryRewriter {
     }
 
     fn calc_crc64(p: &[u8]) -> u64 {
-        let mut crc: u64 = 0;
+        Self::update_crc64(0, p)
+    }
+
+    /// crc64 of rdb files and dump payloads, can be computed incrementally
+    pub fn update_crc64(mut crc: u64, p: &[u8]) -> u64 {
         for b in p {
             let inx = (crc as u8) ^ *b;
             crc = CRC64_TABLE[inx as usize] ^ (crc >> 8);
@@ -387,3 +498,129 @@ impl EntryRewriter {
         crc
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use dt_common::meta::redis::redis_object::BloomFilter;
+
+    use super::*;
+
+    fn str_args(args: &[&str]) -> Vec<Vec<u8>> {
+        args.iter().map(|i| i.as_bytes().to_vec()).collect()
+    }
+
+    #[test]
+    fn test_rewrite_bloom() {
+        let key = RedisString::from("bf_key".to_string());
+        let bloom = BloomObject {
+            size: 3,
+            options: 2,
+            growth: 2,
+            filters: vec![BloomFilter {
+                entries: 100,
+                error: 0.01,
+                hashes: 7,
+                bpe: 9.5,
+                bits: 32,
+                n2: 5,
+                data: vec![0xab; 4],
+                size: 3,
+            }],
+        };
+
+        let cmds = EntryRewriter::rewrite_bloom(&key, &bloom);
+        assert_eq!(cmds.len(), 3);
+        assert_eq!(cmds[0].args, str_args(&["del", "bf_key"]));
+
+        let header: Vec<u8> = [
+            // size
+            vec![3, 0, 0, 0, 0, 0, 0, 0],
+            // filter count
+            vec![1, 0, 0, 0],
+            // options
+            vec![2, 0, 0, 0],
+            // growth
+            vec![2, 0, 0, 0],
+            // bytes of filter data
+            vec![4, 0, 0, 0, 0, 0, 0, 0],
+            // bits
+            vec![32, 0, 0, 0, 0, 0, 0, 0],
+            // items added
+            vec![3, 0, 0, 0, 0, 0, 0, 0],
+            // error 0.01
+            vec![0x7b, 0x14, 0xae, 0x47, 0xe1, 0x7a, 0x84, 0x3f],
+            // bpe 9.5
+            vec![0, 0, 0, 0, 0, 0, 0x23, 0x40],
+            // hashes
+            vec![7, 0, 0, 0],
+            // entries
+            vec![100, 0, 0, 0, 0, 0, 0, 0],
+            // n2
+            vec![5],
+        ]
+        .concat();
+        let mut expected = str_args(&["bf.loadchunk", "bf_key", "1"]);
+        expected.push(header);
+        assert_eq!(cmds[1].args, expected);
+
+        // iterator of filter bits is the end offset + 1
+        let mut expected = str_args(&["bf.loadchunk", "bf_key", "5"]);
+        expected.push(vec![0xab; 4]);
+        assert_eq!(cmds[2].args, expected);
+    }
+
+    #[test]
+    fn test_rewrite_time_series() {
+        let key = RedisString::from("ts_key".to_string());
+        // samples more than TIME_SERIES_MADD_SIZE are split into 2 TS.MADD
+        let ts = TimeSeriesObject {
+            retention_ms: 0,
+            chunk_size: 4096,
+            uncompressed: false,
+            duplicate_policy: Some("last".into()),
+            labels: vec![(
+                RedisString::from("sensor".to_string()),
+                RedisString::from("1".to_string()),
+            )],
+            samples: (0..TIME_SERIES_MADD_SIZE + 1)
+                .map(|i| (1000 + i as u64, i as f64 + 0.5))
+                .collect(),
+        };
+
+        let cmds = EntryRewriter::rewrite_time_series(&key, &ts);
+        assert_eq!(cmds.len(), 4);
+        assert_eq!(cmds[0].args, str_args(&["del", "ts_key"]));
+        assert_eq!(
+            cmds[1].args,
+            str_args(&[
+                "ts.create",
+                "ts_key",
+                "retention",
+                "0",
+                "chunk_size",
+                "4096",
+                "encoding",
+                "compressed",
+                "duplicate_policy",
+                "last",
+                "labels",
+                "sensor",
+                "1",
+            ])
+        );
+
+        assert_eq!(cmds[2].args.len(), 1 + 3 * TIME_SERIES_MADD_SIZE);
+        assert_eq!(
+            cmds[2].args[..7],
+            str_args(&["ts.madd", "ts_key", "1000", "0.5", "ts_key", "1001", "1.5"])
+        );
+        assert_eq!(
+            cmds[2].args[cmds[2].args.len() - 3..],
+            str_args(&["ts_key", "1999", "999.5"])
+        );
+        assert_eq!(
+            cmds[3].args,
+            str_args(&["ts.madd", "ts_key", "2000", "1000.5"])
+        );
+    }
+}
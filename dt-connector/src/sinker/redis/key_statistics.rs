@@ -0,0 +1,386 @@
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+
+use serde::Serialize;
+use serde_json::json;
+
+use dt_common::{
+    log_warn,
+    meta::redis::{command::key_parser::KeyParser, redis_entry::RedisEntry},
+    monitor::time_window_counter::TimeWindowCounter,
+};
+
+// counters are aggregated per batch before added to the sliding windows,
+// so the sub count of each window is bounded by batches, not by writes
+const MAX_SUB_COUNT: usize = 100_000;
+
+/// Aggregates redis entries continuously for statistic reports:
+/// - per key / per key prefix write frequency and written bytes over a sliding window,
+/// - per key prefix memory usage from the rdb snapshot.
+pub struct KeyStatistics {
+    pub time_window_secs: usize,
+    pub report_interval_secs: u64,
+    pub top_n: usize,
+    pub key_prefix_delimiter: String,
+    pub key_prefix_depth: usize,
+    key_parser: KeyParser,
+    key_counters: HashMap<(i64, String), TimeWindowCounter>,
+    prefix_counters: HashMap<(i64, String), TimeWindowCounter>,
+    prefix_memory: HashMap<(i64, String), PrefixMemory>,
+    snapshot_finished: bool,
+    snapshot_reported: bool,
+    final_reported: bool,
+    last_report_time: Instant,
+}
+
+#[derive(Default)]
+struct PrefixMemory {
+    key_count: usize,
+    data_size: usize,
+}
+
+#[derive(Serialize)]
+struct KeyWriteInfo {
+    db_id: i64,
+    key: String,
+    writes: usize,
+    written_bytes: usize,
+}
+
+#[derive(Serialize)]
+struct PrefixWriteInfo {
+    db_id: i64,
+    prefix: String,
+    writes: usize,
+    written_bytes: usize,
+}
+
+#[derive(Serialize)]
+struct PrefixMemoryInfo {
+    db_id: i64,
+    prefix: String,
+    key_count: usize,
+    data_size: usize,
+}
+
+impl KeyStatistics {
+    pub fn new(
+        time_window_secs: usize,
+        report_interval_secs: u64,
+        top_n: usize,
+        key_prefix_delimiter: &str,
+        key_prefix_depth: usize,
+    ) -> Self {
+        Self {
+            time_window_secs,
+            report_interval_secs,
+            top_n,
+            key_prefix_delimiter: key_prefix_delimiter.into(),
+            key_prefix_depth,
+            key_parser: KeyParser::new(),
+            key_counters: HashMap::new(),
+            prefix_counters: HashMap::new(),
+            prefix_memory: HashMap::new(),
+            snapshot_finished: false,
+            snapshot_reported: false,
+            final_reported: false,
+            last_report_time: Instant::now(),
+        }
+    }
+
+    pub fn add_entries<'a>(&mut self, entries: impl Iterator<Item = &'a mut RedisEntry>) {
+        // (db_id, key) -> (written_bytes, writes)
+        let mut key_writes: HashMap<(i64, String), (usize, usize)> = HashMap::new();
+        for entry in entries {
+            if entry.is_base_key() {
+                self.add_snapshot_entry(entry);
+                continue;
+            }
+            // aux data from rdb, E.g. function libraries, neither keys nor writes
+            if entry.is_base {
+                continue;
+            }
+            self.snapshot_finished = true;
+
+            if entry.cmd.keys.is_empty() {
+                if let Err(err) = entry.cmd.parse_keys(&self.key_parser) {
+                    log_warn!(
+                        "failed to parse keys for cmd: [{}], error: {}",
+                        entry.cmd,
+                        err
+                    );
+                    continue;
+                }
+            }
+            // commands without keys, E.g. FLUSHALL, MULTI, EXEC
+            if entry.cmd.keys.is_empty() {
+                continue;
+            }
+
+            let written_bytes = entry.cmd.get_malloc_size() / entry.cmd.keys.len();
+            for key in entry.cmd.keys.iter() {
+                let writes = key_writes.entry((entry.db_id, key.clone())).or_default();
+                writes.0 += written_bytes;
+                writes.1 += 1;
+            }
+        }
+
+        let mut prefix_writes: HashMap<(i64, String), (usize, usize)> = HashMap::new();
+        for ((db_id, key), (written_bytes, writes)) in key_writes {
+            let prefix = self.get_prefix(&key);
+            let prefix_write = prefix_writes.entry((db_id, prefix)).or_default();
+            prefix_write.0 += written_bytes;
+            prefix_write.1 += writes;
+
+            let time_window_secs = self.time_window_secs;
+            self.key_counters
+                .entry((db_id, key))
+                .or_insert_with(|| TimeWindowCounter::new(time_window_secs, MAX_SUB_COUNT))
+                .add(written_bytes, writes);
+        }
+
+        for (prefix, (written_bytes, writes)) in prefix_writes {
+            let time_window_secs = self.time_window_secs;
+            self.prefix_counters
+                .entry(prefix)
+                .or_insert_with(|| TimeWindowCounter::new(time_window_secs, MAX_SUB_COUNT))
+                .add(written_bytes, writes);
+        }
+    }
+
+    /// Returns report lines if the report interval has elapsed, or if the snapshot
+    /// has just finished (the prefix memory report is generated only once).
+    pub fn try_report(&mut self) -> Vec<String> {
+        let mut reports = Vec::new();
+        if self.snapshot_finished && !self.snapshot_reported {
+            reports.extend(self.report_prefix_memory());
+        }
+
+        if self.last_report_time.elapsed() >= Duration::from_secs(self.report_interval_secs) {
+            reports.extend(self.report_writes());
+        }
+        reports
+    }
+
+    /// Generates all pending reports, called when the task finishes.
+    /// Since the statistics are shared by sub sinkers, only the first call generates reports.
+    pub fn final_report(&mut self) -> Vec<String> {
+        if self.final_reported {
+            return Vec::new();
+        }
+        self.final_reported = true;
+
+        let mut reports = self.report_prefix_memory();
+        reports.extend(self.report_writes());
+        reports
+    }
+
+    fn add_snapshot_entry(&mut self, entry: &RedisEntry) {
+        let prefix = self.get_prefix(&entry.key.to_string());
+        let memory = self.prefix_memory.entry((entry.db_id, prefix)).or_default();
+        memory.key_count += 1;
+        memory.data_size += entry.get_data_malloc_size();
+    }
+
+    fn report_prefix_memory(&mut self) -> Vec<String> {
+        if self.snapshot_reported || self.prefix_memory.is_empty() {
+            return Vec::new();
+        }
+        self.snapshot_reported = true;
+
+        let mut infos: Vec<PrefixMemoryInfo> = self
+            .prefix_memory
+            .drain()
+            .map(|((db_id, prefix), memory)| PrefixMemoryInfo {
+                db_id,
+                prefix,
+                key_count: memory.key_count,
+                data_size: memory.data_size,
+            })
+            .collect();
+        infos.sort_by(|a, b| b.data_size.cmp(&a.data_size));
+        vec![json!({ "report": "prefix_memory", "prefixes": infos }).to_string()]
+    }
+
+    fn report_writes(&mut self) -> Vec<String> {
+        self.last_report_time = Instant::now();
+
+        // (db_id, key, written_bytes, writes)
+        let key_stats = Self::collect_window_stats(&mut self.key_counters);
+        if key_stats.is_empty() {
+            return Vec::new();
+        }
+        let prefix_stats = Self::collect_window_stats(&mut self.prefix_counters);
+
+        let to_key_infos = |stats: &[&(i64, String, usize, usize)]| -> Vec<KeyWriteInfo> {
+            stats
+                .iter()
+                .map(|(db_id, key, written_bytes, writes)| KeyWriteInfo {
+                    db_id: *db_id,
+                    key: key.clone(),
+                    writes: *writes,
+                    written_bytes: *written_bytes,
+                })
+                .collect()
+        };
+
+        let hot_keys = self.top_by(&key_stats, |stat| stat.3);
+        let written_bytes_keys = self.top_by(&key_stats, |stat| stat.2);
+        let hot_prefixes: Vec<PrefixWriteInfo> = self
+            .top_by(&prefix_stats, |stat| stat.3)
+            .iter()
+            .map(|(db_id, prefix, written_bytes, writes)| PrefixWriteInfo {
+                db_id: *db_id,
+                prefix: prefix.clone(),
+                writes: *writes,
+                written_bytes: *written_bytes,
+            })
+            .collect();
+
+        let window_secs = self.time_window_secs;
+        vec![
+            json!({ "report": "hot_keys", "window_secs": window_secs, "keys": to_key_infos(&hot_keys) })
+                .to_string(),
+            json!({ "report": "written_bytes_keys", "window_secs": window_secs, "keys": to_key_infos(&written_bytes_keys) })
+                .to_string(),
+            json!({ "report": "hot_prefixes", "window_secs": window_secs, "prefixes": hot_prefixes })
+                .to_string(),
+        ]
+    }
+
+    fn collect_window_stats(
+        counters: &mut HashMap<(i64, String), TimeWindowCounter>,
+    ) -> Vec<(i64, String, usize, usize)> {
+        let mut stats = Vec::new();
+        // remove keys without writes in the current window
+        counters.retain(|(db_id, name), counter| {
+            let statistics = counter.statistics();
+            if statistics.count == 0 {
+                return false;
+            }
+            stats.push((*db_id, name.clone(), statistics.sum, statistics.count));
+            true
+        });
+        stats
+    }
+
+    fn top_by<'a>(
+        &self,
+        stats: &'a [(i64, String, usize, usize)],
+        metric: impl Fn(&(i64, String, usize, usize)) -> usize,
+    ) -> Vec<&'a (i64, String, usize, usize)> {
+        let mut sorted: Vec<_> = stats.iter().collect();
+        // tie-break by name to make reports stable
+        sorted.sort_by(|a, b| metric(b).cmp(&metric(a)).then_with(|| a.1.cmp(&b.1)));
+        sorted.truncate(self.top_n);
+        sorted
+    }
+
+    fn get_prefix(&self, key: &str) -> String {
+        let delimiter = &self.key_prefix_delimiter;
+        if delimiter.is_empty() {
+            return String::new();
+        }
+
+        // prefix ends with the last matched delimiter, keys without delimiter have empty prefix
+        let mut end = 0;
+        for _ in 0..self.key_prefix_depth {
+            match key[end..].find(delimiter.as_str()) {
+                Some(i) => end += i + delimiter.len(),
+                None => break,
+            }
+        }
+        key[..end].to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use dt_common::meta::redis::redis_object::RedisCmd;
+    use serde_json::Value;
+
+    use super::*;
+
+    fn cdc_entry(db_id: i64, args: &[&str]) -> RedisEntry {
+        let mut entry = RedisEntry::new();
+        entry.db_id = db_id;
+        entry.cmd = RedisCmd::from_str_args(args);
+        entry
+    }
+
+    fn snapshot_entry(db_id: i64, key: &str, data_size: usize) -> RedisEntry {
+        let mut entry = RedisEntry::new();
+        entry.is_base = true;
+        entry.db_id = db_id;
+        entry.key = key.to_string().into();
+        entry.data_size = data_size;
+        entry
+    }
+
+    #[test]
+    fn test_get_prefix() {
+        let mut statistics = KeyStatistics::new(60, 60, 10, ":", 1);
+        assert_eq!(statistics.get_prefix("user:1:name"), "user:");
+        assert_eq!(statistics.get_prefix("counter"), "");
+
+        statistics.key_prefix_depth = 2;
+        assert_eq!(statistics.get_prefix("user:1:name"), "user:1:");
+        assert_eq!(statistics.get_prefix("user:1"), "user:");
+
+        statistics.key_prefix_delimiter = String::new();
+        assert_eq!(statistics.get_prefix("user:1"), "");
+    }
+
+    #[test]
+    fn test_reports() {
+        let mut statistics = KeyStatistics::new(60, 0, 2, ":", 1);
+        let mut entries = vec![
+            snapshot_entry(0, "user:1", 100),
+            snapshot_entry(0, "user:2", 50),
+            snapshot_entry(0, "order:1", 10),
+        ];
+        statistics.add_entries(entries.iter_mut());
+        // snapshot not finished, no writes yet
+        assert!(statistics.try_report().is_empty());
+
+        let mut entries = vec![
+            cdc_entry(0, &["SET", "user:1", "a"]),
+            cdc_entry(0, &["SET", "user:1", "b"]),
+            cdc_entry(0, &["SET", "order:1", "a_long_value"]),
+            cdc_entry(0, &["DEL", "user:2", "user:3"]),
+            cdc_entry(0, &["FLUSHALL"]),
+        ];
+        statistics.add_entries(entries.iter_mut());
+
+        let reports: Vec<Value> = statistics
+            .try_report()
+            .iter()
+            .map(|i| serde_json::from_str(i).unwrap())
+            .collect();
+        assert_eq!(reports.len(), 4);
+
+        assert_eq!(reports[0]["report"], "prefix_memory");
+        assert_eq!(reports[0]["prefixes"][0]["prefix"], "user:");
+        assert_eq!(reports[0]["prefixes"][0]["key_count"], 2);
+        assert_eq!(reports[0]["prefixes"][0]["data_size"], 150);
+
+        assert_eq!(reports[1]["report"], "hot_keys");
+        let hot_keys = reports[1]["keys"].as_array().unwrap();
+        assert_eq!(hot_keys.len(), 2);
+        assert_eq!(hot_keys[0]["key"], "user:1");
+        assert_eq!(hot_keys[0]["writes"], 2);
+
+        assert_eq!(reports[2]["report"], "written_bytes_keys");
+        assert_eq!(reports[2]["keys"][0]["key"], "order:1");
+
+        assert_eq!(reports[3]["report"], "hot_prefixes");
+        assert_eq!(reports[3]["prefixes"][0]["prefix"], "user:");
+        assert_eq!(reports[3]["prefixes"][0]["writes"], 4);
+
+        // prefix memory is reported only once
+        let reports = statistics.try_report();
+        assert_eq!(reports.len(), 3);
+    }
+}
@@ -1,3 +1,8 @@
+pub mod dml_template;
 pub mod entry_rewriter;
+pub mod key_statistics;
+pub mod rdb_file_sinker;
+pub mod rdb_writer;
+pub mod redis_checker;
 pub mod redis_sinker;
 pub mod redis_statistic_sinker;
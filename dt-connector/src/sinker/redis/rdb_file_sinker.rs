@@ -0,0 +1,110 @@
+use std::{path::Path, sync::Arc};
+
+use anyhow::Context;
+use async_trait::async_trait;
+use tokio::{
+    fs::{self, File},
+    io::AsyncWriteExt,
+    sync::Mutex,
+    time::Instant,
+};
+
+use dt_common::{
+    log_info,
+    meta::dt_data::{DtData, DtItem},
+    monitor::monitor::Monitor,
+};
+
+use crate::{
+    sinker::{base_sinker::BaseSinker, redis::rdb_writer::RdbWriter},
+    Sinker,
+};
+
+pub struct RedisRdbFileSinker {
+    // shared by all sub sinkers
+    pub rdb_file: Arc<Mutex<RdbFile>>,
+    pub monitor: Arc<Mutex<Monitor>>,
+}
+
+pub struct RdbFile {
+    pub file_path: String,
+    pub file: File,
+    pub writer: RdbWriter,
+    pub entry_count: u64,
+    pub skipped_count: u64,
+    pub finished: bool,
+}
+
+impl RdbFile {
+    pub async fn create(file_path: &str, version: u32) -> anyhow::Result<Self> {
+        let mut writer = RdbWriter::new(version)?;
+        if let Some(dir) = Path::new(file_path).parent() {
+            fs::create_dir_all(dir).await?;
+        }
+        let mut file = File::create(file_path)
+            .await
+            .with_context(|| format!("failed to create rdb file: [{}]", file_path))?;
+
+        writer.write_header();
+        file.write_all(&writer.take_bytes()).await?;
+        Ok(Self {
+            file_path: file_path.into(),
+            file,
+            writer,
+            entry_count: 0,
+            skipped_count: 0,
+            finished: false,
+        })
+    }
+}
+
+#[async_trait]
+impl Sinker for RedisRdbFileSinker {
+    async fn sink_raw(&mut self, data: Vec<DtItem>, _batch: bool) -> anyhow::Result<()> {
+        if data.is_empty() {
+            return Ok(());
+        }
+
+        let start_time = Instant::now();
+        let mut data_size = 0;
+
+        let mut rdb_file = self.rdb_file.lock().await;
+        for dt_item in data.iter() {
+            if let DtData::Redis { entry } = &dt_item.dt_data {
+                data_size += entry.get_data_malloc_size();
+                if rdb_file.writer.write_entry(entry)? {
+                    rdb_file.entry_count += 1;
+                } else {
+                    rdb_file.skipped_count += 1;
+                }
+            }
+        }
+        let bytes = rdb_file.writer.take_bytes();
+        rdb_file.file.write_all(&bytes).await?;
+        drop(rdb_file);
+
+        BaseSinker::update_batch_monitor(&mut self.monitor, data.len(), data_size, start_time).await
+    }
+
+    async fn close(&mut self) -> anyhow::Result<()> {
+        let mut rdb_file = self.rdb_file.lock().await;
+        // all sub sinkers share the same file
+        if rdb_file.finished {
+            return Ok(());
+        }
+
+        rdb_file.writer.write_eof();
+        let bytes = rdb_file.writer.take_bytes();
+        rdb_file.file.write_all(&bytes).await?;
+        rdb_file.file.sync_all().await?;
+        rdb_file.finished = true;
+        log_info!(
+            "rdb file: {} finished, version: {}, entry count: {}, skipped count: {}",
+            rdb_file.file_path,
+            rdb_file.writer.version,
+            rdb_file.entry_count,
+            rdb_file.skipped_count
+        );
+        Ok(())
+    }
+}
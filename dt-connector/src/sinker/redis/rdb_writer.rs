@@ -0,0 +1,470 @@
+use anyhow::{bail, Context};
+use dt_common::{
+    error::Error,
+    log_warn,
+    meta::redis::{redis_entry::RedisEntry, redis_object::RedisObject},
+};
+
+use super::entry_rewriter::EntryRewriter;
+use crate::extractor::redis::rdb::{
+    entry_parser::{
+        RDB_TYPE_HASH, RDB_TYPE_HASH_LISTPACK, RDB_TYPE_HASH_LISTPACK_EX,
+        RDB_TYPE_HASH_LISTPACK_EX_PRE_GA, RDB_TYPE_HASH_METADATA, RDB_TYPE_HASH_METADATA_PRE_GA,
+        RDB_TYPE_LIST, RDB_TYPE_LIST_QUICKLIST, RDB_TYPE_LIST_QUICKLIST_2, RDB_TYPE_MODULE,
+        RDB_TYPE_MODULE_2, RDB_TYPE_SET, RDB_TYPE_SET_LISTPACK, RDB_TYPE_STREAM_LISTPACKS,
+        RDB_TYPE_STREAM_LISTPACKS_2, RDB_TYPE_STREAM_LISTPACKS_3, RDB_TYPE_STRING, RDB_TYPE_ZSET,
+        RDB_TYPE_ZSET_2, RDB_TYPE_ZSET_LISTPACK,
+    },
+    rdb_parser::{K_EOF, K_FLAG_AUX, K_FLAG_EXPIRE_MS, K_FLAG_FUNCTION2, K_FLAG_SELECT},
+};
+
+pub const MIN_RDB_VERSION: u32 = 6;
+pub const MAX_RDB_VERSION: u32 = 12;
+
+/// Encodes redis entries into a RDB file of the chosen version.
+/// Values are copied as is if their source encodings can be loaded by the version,
+/// otherwise they are re-encoded as basic types: string, list, set, zset, hash.
+pub struct RdbWriter {
+    pub version: u32,
+    pub now_db_id: i64,
+    crc: u64,
+    buf: Vec<u8>,
+}
+
+impl RdbWriter {
+    pub fn new(version: u32) -> anyhow::Result<Self> {
+        if !(MIN_RDB_VERSION..=MAX_RDB_VERSION).contains(&version) {
+            bail! {Error::ConfigError(format!(
+                "rdb version: {} not supported, should be in [{}, {}]",
+                version, MIN_RDB_VERSION, MAX_RDB_VERSION
+            ))}
+        }
+
+        Ok(Self {
+            version,
+            now_db_id: -1,
+            crc: 0,
+            buf: Vec::new(),
+        })
+    }
+
+    pub fn write_header(&mut self) {
+        self.put(format!("REDIS{:04}", self.version).as_bytes());
+        self.write_aux(b"redis-ver", Self::redis_version(self.version).as_bytes());
+        self.write_aux(b"redis-bits", b"64");
+        self.write_aux(
+            b"ctime",
+            chrono::Utc::now().timestamp().to_string().as_bytes(),
+        );
+    }
+
+    /// Returns false if the entry can not be written into this version and is skipped
+    pub fn write_entry(&mut self, entry: &RedisEntry) -> anyhow::Result<bool> {
+        if !entry.is_base {
+            bail! {Error::SinkerError(format!(
+                "only snapshot entries can be written into rdb file, got cmd: {}",
+                entry.cmd
+            ))}
+        }
+
+        // aux data, E.g. lua scripts, function libraries
+        if !entry.cmd.args.is_empty() {
+            return Ok(self.write_aux_cmd(entry));
+        }
+
+        let (type_byte, value) = match self.encode_value(entry)? {
+            Some(encoded) => encoded,
+            None => return Ok(false),
+        };
+        if entry.db_id != self.now_db_id {
+            let mut buf = vec![K_FLAG_SELECT];
+            Self::encode_length(&mut buf, entry.db_id as u64);
+            self.put(&buf);
+            self.now_db_id = entry.db_id;
+        }
+
+        let mut buf = Vec::new();
+        if entry.expire_ms > 0 {
+            // expire_ms of entries is the time to live, while rdb keeps the unix time
+            let expire_at = chrono::Utc::now().timestamp_millis() + entry.expire_ms;
+            buf.push(K_FLAG_EXPIRE_MS);
+            buf.extend_from_slice(&(expire_at as u64).to_le_bytes());
+        }
+        buf.push(type_byte);
+        Self::encode_string(&mut buf, entry.key.as_bytes());
+        buf.extend_from_slice(&value);
+        self.put(&buf);
+        Ok(true)
+    }
+
+    pub fn write_eof(&mut self) {
+        self.put(&[K_EOF]);
+        let crc = self.crc;
+        self.buf.extend_from_slice(&crc.to_le_bytes());
+    }
+
+    /// Bytes encoded since the last call
+    pub fn take_bytes(&mut self) -> Vec<u8> {
+        self.buf.drain(..).collect()
+    }
+
+    fn write_aux(&mut self, key: &[u8], value: &[u8]) {
+        let mut buf = vec![K_FLAG_AUX];
+        Self::encode_string(&mut buf, key);
+        Self::encode_string(&mut buf, value);
+        self.put(&buf);
+    }
+
+    fn write_aux_cmd(&mut self, entry: &RedisEntry) -> bool {
+        let name = entry.cmd.get_str_arg(0).to_lowercase();
+        let code = match entry.cmd.args.last() {
+            Some(code) if entry.cmd.args.len() > 2 => code,
+            _ => return false,
+        };
+
+        match name.as_str() {
+            // function load [replace] <code>
+            "function" => {
+                if self.version < 10 {
+                    log_warn!(
+                        "function libraries can not be written into rdb version: {}, skipped",
+                        self.version
+                    );
+                    return false;
+                }
+                let mut buf = vec![K_FLAG_FUNCTION2];
+                Self::encode_string(&mut buf, code);
+                self.put(&buf);
+                true
+            }
+
+            // script load <code>
+            "script" => {
+                let code = code.clone();
+                self.write_aux(b"lua", &code);
+                true
+            }
+
+            _ => false,
+        }
+    }
+
+    /// Returns None if the value can not be encoded into this version,
+    /// E.g. streams and modules whose encodings are newer than the version
+    fn encode_value(&self, entry: &RedisEntry) -> anyhow::Result<Option<(u8, Vec<u8>)>> {
+        if !entry.raw_bytes.is_empty() && Self::min_version(entry.value_type_byte) <= self.version {
+            return Ok(Some((entry.value_type_byte, entry.raw_bytes.clone())));
+        }
+
+        let mut buf = Vec::new();
+        let type_byte = match &entry.value {
+            RedisObject::String(obj) => {
+                Self::encode_string(&mut buf, obj.value.as_bytes());
+                RDB_TYPE_STRING
+            }
+
+            RedisObject::List(obj) => {
+                Self::encode_length(&mut buf, obj.elements.len() as u64);
+                for element in obj.elements.iter() {
+                    Self::encode_string(&mut buf, element.as_bytes());
+                }
+                RDB_TYPE_LIST
+            }
+
+            RedisObject::Set(obj) => {
+                Self::encode_length(&mut buf, obj.elements.len() as u64);
+                for element in obj.elements.iter() {
+                    Self::encode_string(&mut buf, element.as_bytes());
+                }
+                RDB_TYPE_SET
+            }
+
+            RedisObject::Zset(obj) => {
+                Self::encode_length(&mut buf, obj.elements.len() as u64);
+                for element in obj.elements.iter() {
+                    Self::encode_string(&mut buf, element.member.as_bytes());
+                    let score: f64 = element.score.to_string().parse().with_context(|| {
+                        format!("invalid zset score: {}, key: {}", element.score, entry.key)
+                    })?;
+                    if self.version >= 8 {
+                        buf.extend_from_slice(&score.to_le_bytes());
+                    } else {
+                        Self::encode_float(&mut buf, score);
+                    }
+                }
+                if self.version >= 8 {
+                    RDB_TYPE_ZSET_2
+                } else {
+                    RDB_TYPE_ZSET
+                }
+            }
+
+            // ttls of hash fields are dropped
+            RedisObject::Hash(obj) => {
+                Self::encode_length(&mut buf, obj.value.len() as u64);
+                for (field, (value, _)) in obj.value.iter() {
+                    Self::encode_string(&mut buf, field.as_bytes());
+                    Self::encode_string(&mut buf, value.as_bytes());
+                }
+                RDB_TYPE_HASH
+            }
+
+            _ => {
+                log_warn!(
+                    "key: {} of type: {}, type byte: {}, can not be written into rdb version: {}, skipped",
+                    entry.key,
+                    entry.get_type(),
+                    entry.value_type_byte,
+                    self.version
+                );
+                return Ok(None);
+            }
+        };
+        Ok(Some((type_byte, buf)))
+    }
+
+    /// The first rdb version that can load the value encoding
+    fn min_version(type_byte: u8) -> u32 {
+        match type_byte {
+            RDB_TYPE_LIST_QUICKLIST => 7,
+            RDB_TYPE_ZSET_2 | RDB_TYPE_MODULE => 8,
+            RDB_TYPE_MODULE_2 | RDB_TYPE_STREAM_LISTPACKS => 9,
+            RDB_TYPE_HASH_LISTPACK
+            | RDB_TYPE_ZSET_LISTPACK
+            | RDB_TYPE_LIST_QUICKLIST_2
+            | RDB_TYPE_STREAM_LISTPACKS_2 => 10,
+            RDB_TYPE_SET_LISTPACK | RDB_TYPE_STREAM_LISTPACKS_3 => 11,
+            RDB_TYPE_HASH_METADATA_PRE_GA
+            | RDB_TYPE_HASH_LISTPACK_EX_PRE_GA
+            | RDB_TYPE_HASH_METADATA
+            | RDB_TYPE_HASH_LISTPACK_EX => 12,
+            _ => MIN_RDB_VERSION,
+        }
+    }
+
+    fn redis_version(version: u32) -> &'static str {
+        match version {
+            6 => "2.8.0",
+            7 => "3.2.0",
+            8 => "4.0.0",
+            9 => "5.0.0",
+            10 => "7.0.0",
+            11 => "7.2.0",
+            _ => "7.4.0",
+        }
+    }
+
+    fn put(&mut self, bytes: &[u8]) {
+        self.crc = EntryRewriter::update_crc64(self.crc, bytes);
+        self.buf.extend_from_slice(bytes);
+    }
+
+    fn encode_length(buf: &mut Vec<u8>, len: u64) {
+        if len < 1 << 6 {
+            buf.push(len as u8);
+        } else if len < 1 << 14 {
+            buf.push(0x40 | (len >> 8) as u8);
+            buf.push(len as u8);
+        } else if len <= u32::MAX as u64 {
+            buf.push(0x80);
+            buf.extend_from_slice(&(len as u32).to_be_bytes());
+        } else {
+            buf.push(0x81);
+            buf.extend_from_slice(&len.to_be_bytes());
+        }
+    }
+
+    fn encode_string(buf: &mut Vec<u8>, bytes: &[u8]) {
+        Self::encode_length(buf, bytes.len() as u64);
+        buf.extend_from_slice(bytes);
+    }
+
+    fn encode_float(buf: &mut Vec<u8>, v: f64) {
+        if v.is_nan() {
+            buf.push(253);
+        } else if v == f64::INFINITY {
+            buf.push(254);
+        } else if v == f64::NEG_INFINITY {
+            buf.push(255);
+        } else {
+            let s = v.to_string();
+            buf.push(s.len() as u8);
+            buf.extend_from_slice(s.as_bytes());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use dt_common::meta::redis::redis_object::{
+        HashObject, ListObject, RedisCmd, RedisString, StreamObject, StringObject, ZSetEntry,
+        ZsetObject,
+    };
+
+    use super::*;
+    use crate::extractor::redis::{
+        rdb::{rdb_parser::RdbParser, reader::rdb_reader::RdbReader},
+        StreamReader,
+    };
+
+    fn new_entry(db_id: i64, key: &str, value: RedisObject) -> RedisEntry {
+        let mut entry = RedisEntry::new();
+        entry.is_base = true;
+        entry.db_id = db_id;
+        entry.key = RedisString::from(key.to_string());
+        entry.value = value;
+        entry
+    }
+
+    async fn parse(data: &[u8]) -> (String, Vec<RedisEntry>) {
+        let mut cursor = Cursor::new(data);
+        let mut stream_reader: Box<&mut (dyn StreamReader + Send)> = Box::new(&mut cursor);
+        let reader = RdbReader {
+            conn: &mut stream_reader,
+            rdb_length: data.len(),
+            position: 0,
+            copy_raw: false,
+            raw_bytes: Vec::new(),
+        };
+        let mut parser = RdbParser {
+            reader,
+            repl_stream_db_id: 0,
+            now_db_id: 0,
+            expire_ms: 0,
+            idle: 0,
+            freq: 0,
+            is_end: false,
+        };
+
+        let version = parser.load_meta().await.unwrap();
+        let mut entries = Vec::new();
+        while !parser.is_end {
+            if let Some(entry) = parser.load_entry().await.unwrap() {
+                entries.push(entry);
+            }
+        }
+        (version, entries)
+    }
+
+    #[tokio::test]
+    async fn test_write_rdb() {
+        for version in [MIN_RDB_VERSION, 9, MAX_RDB_VERSION] {
+            let mut writer = RdbWriter::new(version).unwrap();
+            writer.write_header();
+
+            let mut string_obj = StringObject::new();
+            string_obj.value = RedisString::from("v1".to_string());
+            let mut entry = new_entry(0, "k1", RedisObject::String(string_obj));
+            entry.expire_ms = 100000;
+            assert!(writer.write_entry(&entry).unwrap());
+
+            let mut list_obj = ListObject::new();
+            list_obj.elements = vec![
+                RedisString::from("a".to_string()),
+                RedisString::from("b".to_string()),
+            ];
+            let entry = new_entry(1, "k2", RedisObject::List(list_obj));
+            assert!(writer.write_entry(&entry).unwrap());
+
+            let mut zset_obj = ZsetObject::new();
+            zset_obj.elements = vec![ZSetEntry {
+                member: RedisString::from("m".to_string()),
+                score: RedisString::from("1.5".to_string()),
+            }];
+            let entry = new_entry(1, "k3", RedisObject::Zset(zset_obj));
+            assert!(writer.write_entry(&entry).unwrap());
+
+            let mut hash_obj = HashObject::new();
+            hash_obj.value.insert(
+                RedisString::from("f".to_string()),
+                (RedisString::from("v".to_string()), None),
+            );
+            let entry = new_entry(1, "k4", RedisObject::Hash(hash_obj));
+            assert!(writer.write_entry(&entry).unwrap());
+
+            // listpack encoded hash, written as is only if the version supports it
+            let mut entry = new_entry(1, "k5", RedisObject::Hash(HashObject::new()));
+            entry.value_type_byte = RDB_TYPE_HASH_LISTPACK;
+            entry.raw_bytes = vec![1, 2, 3];
+            let (type_byte, value) = writer.encode_value(&entry).unwrap().unwrap();
+            if version >= 10 {
+                assert_eq!((type_byte, value), (RDB_TYPE_HASH_LISTPACK, vec![1, 2, 3]));
+            } else {
+                assert_eq!((type_byte, value), (RDB_TYPE_HASH, vec![0]));
+            }
+
+            // streams are copied as is if the version supports their encodings, or skipped
+            let mut entry = new_entry(0, "k6", RedisObject::Stream(StreamObject::new()));
+            entry.value_type_byte = RDB_TYPE_STREAM_LISTPACKS_3;
+            entry.raw_bytes = vec![1, 2, 3];
+            if version >= 11 {
+                assert!(writer.encode_value(&entry).unwrap().is_some());
+            } else {
+                assert!(!writer.write_entry(&entry).unwrap());
+            }
+
+            let mut entry = new_entry(0, "", RedisObject::Unknown);
+            entry.cmd = RedisCmd::from_str_args(&["function", "load", "replace", "code"]);
+            assert_eq!(writer.write_entry(&entry).unwrap(), version >= 10);
+
+            // cdc commands are not allowed
+            let mut entry = new_entry(0, "k1", RedisObject::Unknown);
+            entry.is_base = false;
+            entry.cmd = RedisCmd::from_str_args(&["set", "k1", "v1"]);
+            assert!(writer.write_entry(&entry).is_err());
+
+            writer.write_eof();
+            let data = writer.take_bytes();
+            let (data, checksum) = data.split_at(data.len() - 8);
+            assert_eq!(EntryRewriter::update_crc64(0, data).to_le_bytes(), checksum);
+
+            let data = [data, checksum].concat();
+            let (parsed_version, entries) = parse(&data).await;
+            assert_eq!(parsed_version, format!("{:04}", version));
+
+            let entries: Vec<RedisEntry> = entries
+                .into_iter()
+                .filter(|i| i.cmd.args.is_empty())
+                .collect();
+            assert_eq!(entries.len(), 4);
+            assert_eq!(
+                entries.iter().map(|i| i.db_id).collect::<Vec<i64>>(),
+                vec![0, 1, 1, 1]
+            );
+            assert!(entries[0].expire_ms > 90000 && entries[0].expire_ms <= 100000);
+
+            match &entries[0].value {
+                RedisObject::String(obj) => assert_eq!(obj.value.to_string(), "v1"),
+                _ => panic!(),
+            }
+            match &entries[1].value {
+                RedisObject::List(obj) => assert_eq!(obj.elements.len(), 2),
+                _ => panic!(),
+            }
+            match &entries[2].value {
+                RedisObject::Zset(obj) => assert_eq!(obj.elements[0].score.to_string(), "1.5"),
+                _ => panic!(),
+            }
+            match &entries[3].value {
+                RedisObject::Hash(obj) => assert_eq!(obj.value.len(), 1),
+                _ => panic!(),
+            }
+        }
+    }
+
+    #[test]
+    fn test_encode_length() {
+        for (len, expected) in [
+            (10u64, vec![10u8]),
+            (1000, vec![0x43, 0xe8]),
+            (100000, vec![0x80, 0, 1, 0x86, 0xa0]),
+        ] {
+            let mut buf = Vec::new();
+            RdbWriter::encode_length(&mut buf, len);
+            assert_eq!(buf, expected);
+        }
+        assert!(RdbWriter::new(MAX_RDB_VERSION + 1).is_err());
+    }
+}
@@ -0,0 +1,353 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::Arc,
+};
+
+use anyhow::bail;
+use async_trait::async_trait;
+use base64::{prelude::BASE64_STANDARD, Engine};
+use redis::{Connection, Value};
+use tokio::{sync::Mutex, time::Instant};
+
+use crate::{
+    call_batch_fn,
+    check_log::{
+        check_log::{CheckLog, DiffColValue},
+        log_type::LogType,
+    },
+    sinker::{base_checker::BaseChecker, base_sinker::BaseSinker},
+    Sinker,
+};
+use dt_common::{
+    error::Error,
+    meta::{
+        dt_data::{DtData, DtItem},
+        redis::{
+            cluster_node::ClusterNode,
+            redis_entry::RedisEntry,
+            redis_object::{RedisCmd, RedisObject, RedisString, ZSetEntry},
+        },
+    },
+    monitor::monitor::Monitor,
+    utils::redis_util::RedisUtil,
+};
+
+pub const KEY_COL: &str = "key";
+// keys which are not valid utf-8 are logged base64 encoded in this col instead of KEY_COL
+const KEY_BASE64_COL: &str = "key_base64";
+const TYPE_COL: &str = "type";
+const VALUE_COL: &str = "value";
+const TTL_COL: &str = "ttl";
+
+pub struct RedisChecker {
+    pub cluster_node: Option<ClusterNode>,
+    pub conn: Connection,
+    pub now_db_id: i64,
+    pub batch_size: usize,
+    pub ttl_tolerance_ms: i64,
+    pub monitor: Arc<Mutex<Monitor>>,
+}
+
+#[async_trait]
+impl Sinker for RedisChecker {
+    async fn sink_raw(&mut self, mut data: Vec<DtItem>, _batch: bool) -> anyhow::Result<()> {
+        if data.is_empty() {
+            return Ok(());
+        }
+
+        call_batch_fn!(self, data, Self::batch_check);
+        Ok(())
+    }
+
+    fn get_id(&self) -> String {
+        if let Some(node) = &self.cluster_node {
+            node.address.clone()
+        } else {
+            String::new()
+        }
+    }
+}
+
+impl RedisChecker {
+    async fn batch_check(
+        &mut self,
+        data: &mut [DtItem],
+        start_index: usize,
+        batch_size: usize,
+    ) -> anyhow::Result<()> {
+        let start_time = Instant::now();
+        let mut data_size = 0;
+
+        let mut miss = Vec::new();
+        let mut diff = Vec::new();
+        for dt_item in data.iter().skip(start_index).take(batch_size) {
+            // only keys from snapshot are checked, commands from cdc are ignored
+            let entry = match &dt_item.dt_data {
+                DtData::Redis { entry } if entry.is_base_key() => entry,
+                _ => continue,
+            };
+            data_size += entry.get_data_malloc_size();
+
+            self.select_db(entry.db_id)?;
+            let dst_object = RedisUtil::get_object(&mut self.conn, &entry.key)?;
+            let dst_pttl = RedisUtil::get_pttl(&mut self.conn, &entry.key)?;
+
+            let dst_object = match dst_object {
+                Some(dst_object) => dst_object,
+                None => {
+                    // keys about to expire may have expired in target
+                    if entry.expire_ms <= 0 || entry.expire_ms > self.ttl_tolerance_ms {
+                        miss.push(Self::build_check_log(entry, LogType::Miss, HashMap::new()));
+                    }
+                    continue;
+                }
+            };
+
+            let diff_col_values = self.compare_entry(entry, &dst_object, dst_pttl);
+            if !diff_col_values.is_empty() {
+                diff.push(Self::build_check_log(entry, LogType::Diff, diff_col_values));
+            }
+        }
+        BaseChecker::log_dml(miss, diff);
+
+        BaseSinker::update_batch_monitor(&mut self.monitor, batch_size, data_size, start_time).await
+    }
+
+    fn select_db(&mut self, db_id: i64) -> anyhow::Result<()> {
+        // cluster only has db 0
+        if self.cluster_node.is_some() || db_id == self.now_db_id {
+            return Ok(());
+        }
+
+        let db = db_id.to_string();
+        if Value::Okay != RedisUtil::send_cmd(&mut self.conn, &["SELECT", &db])? {
+            bail! {Error::RedisResultError(format!("\"SELECT {}\" failed", db))}
+        }
+        self.now_db_id = db_id;
+        Ok(())
+    }
+
+    fn compare_entry(
+        &self,
+        entry: &RedisEntry,
+        dst_object: &RedisObject,
+        dst_pttl: i64,
+    ) -> HashMap<String, DiffColValue> {
+        let mut diff_col_values = HashMap::new();
+        let (src_type, dst_type) = (entry.value.get_type(), dst_object.get_type());
+        if src_type != dst_type {
+            diff_col_values.insert(
+                TYPE_COL.to_string(),
+                DiffColValue {
+                    src: Some(src_type),
+                    dst: Some(dst_type),
+                },
+            );
+            return diff_col_values;
+        }
+
+        if let Some(diff_col_value) = Self::compare_value(&entry.value, dst_object) {
+            diff_col_values.insert(VALUE_COL.to_string(), diff_col_value);
+        }
+
+        // expire_ms of rdb entries is the remaining ttl when the key was parsed
+        let src_has_ttl = entry.expire_ms > 0;
+        let dst_has_ttl = dst_pttl > 0;
+        if src_has_ttl != dst_has_ttl
+            || (src_has_ttl && (entry.expire_ms - dst_pttl).abs() > self.ttl_tolerance_ms)
+        {
+            let to_str = |has_ttl: bool, ttl: i64| Some(if has_ttl { ttl } else { -1 }.to_string());
+            diff_col_values.insert(
+                TTL_COL.to_string(),
+                DiffColValue {
+                    src: to_str(src_has_ttl, entry.expire_ms),
+                    dst: to_str(dst_has_ttl, dst_pttl),
+                },
+            );
+        }
+        diff_col_values
+    }
+
+    fn compare_value(src: &RedisObject, dst: &RedisObject) -> Option<DiffColValue> {
+        match (src, dst) {
+            (RedisObject::String(s), RedisObject::String(d)) => {
+                if s.value != d.value {
+                    return Some(DiffColValue {
+                        src: Some(s.value.to_string()),
+                        dst: Some(d.value.to_string()),
+                    });
+                }
+                None
+            }
+
+            (RedisObject::List(s), RedisObject::List(d)) => {
+                Self::compare_elements(s.elements.iter().collect(), d.elements.iter().collect())
+            }
+
+            (RedisObject::Set(s), RedisObject::Set(d)) => {
+                let mut src_elements: Vec<&RedisString> = s.elements.iter().collect();
+                let mut dst_elements: Vec<&RedisString> = d.elements.iter().collect();
+                src_elements.sort_by(|a, b| a.bytes.cmp(&b.bytes));
+                dst_elements.sort_by(|a, b| a.bytes.cmp(&b.bytes));
+                Self::compare_elements(src_elements, dst_elements)
+            }
+
+            (RedisObject::Hash(s), RedisObject::Hash(d)) => {
+                let to_map = |value: &HashMap<RedisString, (RedisString, Option<RedisString>)>| {
+                    value
+                        .iter()
+                        .map(|(k, (v, _))| (k.bytes.clone(), v.to_string()))
+                        .collect::<BTreeMap<Vec<u8>, String>>()
+                };
+                Self::compare_maps(to_map(&s.value), to_map(&d.value))
+            }
+
+            (RedisObject::Zset(s), RedisObject::Zset(d)) => {
+                // scores are compared as numbers, "1" and "1.0" are the same
+                let to_map = |elements: &[ZSetEntry]| {
+                    elements
+                        .iter()
+                        .map(|i| {
+                            let score = i.score.to_string();
+                            let score = score.parse::<f64>().map_or(score, |v| v.to_string());
+                            (i.member.bytes.clone(), score)
+                        })
+                        .collect::<BTreeMap<Vec<u8>, String>>()
+                };
+                Self::compare_maps(to_map(&s.elements), to_map(&d.elements))
+            }
+
+            (RedisObject::Stream(s), RedisObject::Stream(d)) => Self::compare_maps(
+                Self::get_stream_entries(&s.cmds),
+                Self::get_stream_entries(&d.cmds),
+            ),
+
+            // values of module types are not compared
+            _ => None,
+        }
+    }
+
+    fn compare_elements(src: Vec<&RedisString>, dst: Vec<&RedisString>) -> Option<DiffColValue> {
+        if src.len() != dst.len() {
+            return Some(Self::build_len_diff(src.len(), dst.len()));
+        }
+
+        for (i, (s, d)) in src.iter().zip(dst.iter()).enumerate() {
+            if s != d {
+                return Some(DiffColValue {
+                    src: Some(format!("[{}]: {}", i, s)),
+                    dst: Some(format!("[{}]: {}", i, d)),
+                });
+            }
+        }
+        None
+    }
+
+    fn compare_maps(
+        src: BTreeMap<Vec<u8>, String>,
+        dst: BTreeMap<Vec<u8>, String>,
+    ) -> Option<DiffColValue> {
+        if src.len() != dst.len() {
+            return Some(Self::build_len_diff(src.len(), dst.len()));
+        }
+
+        for (k, s) in src.iter() {
+            let d = dst.get(k);
+            if Some(s) != d {
+                let k = String::from_utf8_lossy(k);
+                return Some(DiffColValue {
+                    src: Some(format!("{}: {}", k, s)),
+                    dst: d.map(|d| format!("{}: {}", k, d)),
+                });
+            }
+        }
+        None
+    }
+
+    fn build_len_diff(src_len: usize, dst_len: usize) -> DiffColValue {
+        DiffColValue {
+            src: Some(format!("len: {}", src_len)),
+            dst: Some(format!("len: {}", dst_len)),
+        }
+    }
+
+    /// entry id -> fields of xadd commands, "XADD key MAXLEN 0 ..." generated for empty streams is ignored
+    fn get_stream_entries(cmds: &[RedisCmd]) -> BTreeMap<Vec<u8>, String> {
+        cmds.iter()
+            .filter(|cmd| {
+                cmd.args.len() > 2
+                    && cmd.get_name().eq_ignore_ascii_case("xadd")
+                    && !cmd.get_str_arg(2).eq_ignore_ascii_case("maxlen")
+            })
+            .map(|cmd| {
+                let fields = cmd.args_to_string()[3..].join(" ");
+                (cmd.args[2].clone(), fields)
+            })
+            .collect()
+    }
+
+    fn build_check_log(
+        entry: &RedisEntry,
+        log_type: LogType,
+        diff_col_values: HashMap<String, DiffColValue>,
+    ) -> CheckLog {
+        CheckLog {
+            log_type,
+            schema: entry.db_id.to_string(),
+            tb: String::new(),
+            id_col_values: Self::build_key_col_values(&entry.key),
+            diff_col_values,
+        }
+    }
+
+    fn build_key_col_values(key: &RedisString) -> HashMap<String, Option<String>> {
+        match std::str::from_utf8(&key.bytes) {
+            Ok(key) => HashMap::from([(KEY_COL.to_string(), Some(key.to_string()))]),
+            Err(_) => HashMap::from([(
+                KEY_BASE64_COL.to_string(),
+                Some(BASE64_STANDARD.encode(&key.bytes)),
+            )]),
+        }
+    }
+
+    /// The key written by build_check_log, None if the log has no key
+    pub fn parse_key_col_values(
+        id_col_values: &HashMap<String, Option<String>>,
+    ) -> anyhow::Result<Option<RedisString>> {
+        if let Some(Some(key)) = id_col_values.get(KEY_BASE64_COL) {
+            return Ok(Some(BASE64_STANDARD.decode(key)?.into()));
+        }
+        if let Some(Some(key)) = id_col_values.get(KEY_COL) {
+            return Ok(Some(key.clone().into()));
+        }
+        Ok(None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_key_col_values() {
+        let key = RedisString::from("key_1_中文".to_string());
+        let id_col_values = RedisChecker::build_key_col_values(&key);
+        assert_eq!(
+            id_col_values,
+            HashMap::from([(KEY_COL.to_string(), Some("key_1_中文".to_string()))])
+        );
+        let parsed = RedisChecker::parse_key_col_values(&id_col_values).unwrap();
+        assert_eq!(parsed.unwrap().bytes, key.bytes);
+
+        // binary keys are kept as they are
+        let key = RedisString::from(vec![b'k', 0xff, 0xfe, 0]);
+        let id_col_values = RedisChecker::build_key_col_values(&key);
+        assert!(!id_col_values.contains_key(KEY_COL));
+        let parsed = RedisChecker::parse_key_col_values(&id_col_values).unwrap();
+        assert_eq!(parsed.unwrap().bytes, key.bytes);
+
+        assert!(RedisChecker::parse_key_col_values(&HashMap::new())
+            .unwrap()
+            .is_none());
+    }
+}
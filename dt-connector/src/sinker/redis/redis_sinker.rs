@@ -1,3 +1,4 @@
+use std::collections::HashMap;
 use std::sync::Arc;
 
 use anyhow::bail;
@@ -7,6 +8,7 @@ use redis::ConnectionLike;
 use redis::Value;
 use tokio::{sync::Mutex, sync::RwLock, time::Instant};
 
+use super::dml_template::DmlTemplate;
 use super::entry_rewriter::EntryRewriter;
 use crate::call_batch_fn;
 use crate::data_marker::DataMarker;
@@ -38,6 +40,8 @@ pub struct RedisSinker {
     pub monitor: Arc<Mutex<Monitor>>,
     pub data_marker: Option<Arc<RwLock<DataMarker>>>,
     pub key_parser: KeyParser,
+    // HashMap<(db, tb), DmlTemplate>
+    pub templates: HashMap<(String, String), DmlTemplate>,
 }
 
 #[async_trait]
@@ -137,6 +141,11 @@ impl RedisSinker {
                     }
                 }
 
+                // aof commands, or aux data from rdb, E.g. lua scripts, function libraries
+                RedisWriteMethod::Rewrite if !entry.cmd.args.is_empty() => {
+                    cmds.push(entry.cmd.clone());
+                }
+
                 RedisWriteMethod::Rewrite => {
                     let mut rewrite_cmds = match entry.value {
                         RedisObject::String(ref mut obj) => EntryRewriter::rewrite_string(obj),
@@ -145,11 +154,14 @@ impl RedisSinker {
                         RedisObject::Hash(ref mut obj) => EntryRewriter::rewrite_hash(obj),
                         RedisObject::Zset(ref mut obj) => EntryRewriter::rewrite_zset(obj),
                         RedisObject::Stream(ref mut obj) => Ok(obj.cmds.drain(..).collect()),
-                        RedisObject::Module(_) => {
-                            // TODO: support rewrite some 8.0+ major module2 types, such as: JSON, Bloom, CountMinSketch, TDigest, TopK, TimeSeries
-                            let cmd = EntryRewriter::rewrite_as_restore(entry, self.version)?;
-                            Ok(vec![cmd])
-                        }
+                        RedisObject::Module(ref obj) => match EntryRewriter::rewrite_module(obj)? {
+                            Some(cmds) => Ok(cmds),
+                            // TODO: support rewrite more module2 types, such as: CountMinSketch, TDigest, TopK
+                            None => Ok(vec![EntryRewriter::rewrite_as_restore(
+                                entry,
+                                self.version,
+                            )?]),
+                        },
                         _ => bail! {Error::SinkerError("rewrite not implemented".into())},
                     }?;
                     if let Some(expire_cmd) = EntryRewriter::rewrite_expire(entry)? {
@@ -177,9 +189,7 @@ impl RedisSinker {
         let mut cmds = Vec::new();
         for row_data in data.iter_mut().skip(start_index).take(batch_size) {
             data_size += row_data.data_size;
-            if let Some(cmd) = self.dml_to_redis_cmd(row_data).await? {
-                cmds.push(cmd);
-            }
+            cmds.extend(self.dml_to_redis_cmds(row_data).await?);
         }
         self.batch_sink(&cmds).await?;
 
@@ -192,16 +202,31 @@ impl RedisSinker {
 
         for row_data in data.iter_mut() {
             data_size += row_data.data_size;
-            if let Some(cmd) = self.dml_to_redis_cmd(row_data).await? {
-                self.batch_sink(&[cmd]).await?
-            }
+            let cmds = self.dml_to_redis_cmds(row_data).await?;
+            self.batch_sink(&cmds).await?;
         }
 
         BaseSinker::update_serial_monitor(&mut self.monitor, data.len(), data_size, start_time)
             .await
     }
 
-    async fn dml_to_redis_cmd(&mut self, row_data: &RowData) -> anyhow::Result<Option<RedisCmd>> {
+    async fn dml_to_redis_cmds(&mut self, row_data: &RowData) -> anyhow::Result<Vec<RedisCmd>> {
+        if let Some(template) = self
+            .templates
+            .get(&(row_data.schema.clone(), row_data.tb.clone()))
+        {
+            return template.to_cmds(row_data);
+        }
+
+        let cmd = self.dml_to_default_redis_cmd(row_data).await?;
+        Ok(cmd.into_iter().collect())
+    }
+
+    /// HSET schema.tb.key_col_value col_1 value_1 col_2 value_2 ...
+    async fn dml_to_default_redis_cmd(
+        &mut self,
+        row_data: &RowData,
+    ) -> anyhow::Result<Option<RedisCmd>> {
         if self.meta_manager.is_none() {
             return Ok(None);
         }
@@ -353,8 +378,12 @@ impl RedisSinker {
                 &data_marker.marker
             };
 
-            let data_marker_cmd =
-                RedisCmd::from_str_args(&["SET", key, &data_marker.data_origin_node]);
+            let value = if data_marker.track_path() {
+                data_marker.get_dst_data_path()
+            } else {
+                data_marker.data_origin_node.clone()
+            };
+            let data_marker_cmd = RedisCmd::from_str_args(&["SET", key, &value]);
             log_debug!("data_marker_cmd: [{}] by cmd: [{}]", data_marker_cmd, cmd);
             return Ok(Some(data_marker_cmd));
         }
@@ -11,13 +11,15 @@ use dt_common::meta::dt_data::DtItem;
 use dt_common::meta::redis::redis_statistic_type::RedisStatisticType;
 use dt_common::monitor::monitor::Monitor;
 
-use crate::Sinker;
+use crate::{sinker::redis::key_statistics::KeyStatistics, Sinker};
 
 pub struct RedisStatisticSinker {
     pub statistic_type: RedisStatisticType,
     pub monitor: Arc<Mutex<Monitor>>,
     pub data_size_threshold: usize,
     pub freq_threshold: i64,
+    // shared by all sub sinkers, only used by RedisStatisticType::Continuous
+    pub key_statistics: Arc<Mutex<KeyStatistics>>,
 }
 
 #[derive(Serialize)]
@@ -38,6 +40,18 @@ struct HotKeyInfo {
 #[async_trait]
 impl Sinker for RedisStatisticSinker {
     async fn sink_raw(&mut self, mut data: Vec<DtItem>, _batch: bool) -> anyhow::Result<()> {
+        if let RedisStatisticType::Continuous = self.statistic_type {
+            let mut key_statistics = self.key_statistics.lock().await;
+            key_statistics.add_entries(data.iter_mut().filter_map(|i| match &mut i.dt_data {
+                DtData::Redis { entry } => Some(entry),
+                _ => None,
+            }));
+            for report in key_statistics.try_report() {
+                log_statistic!("{}", report);
+            }
+            return Ok(());
+        }
+
         for dt_item in data.iter_mut() {
             if let DtData::Redis { entry } = &mut dt_item.dt_data {
                 match self.statistic_type {
@@ -65,9 +79,20 @@ impl Sinker for RedisStatisticSinker {
                         };
                         log_statistic!("{}", json!(info).to_string());
                     }
+
+                    RedisStatisticType::Continuous => {}
                 }
             }
         }
         Ok(())
     }
+
+    async fn close(&mut self) -> anyhow::Result<()> {
+        if let RedisStatisticType::Continuous = self.statistic_type {
+            for report in self.key_statistics.lock().await.final_report() {
+                log_statistic!("{}", report);
+            }
+        }
+        Ok(())
+    }
 }
@@ -1,10 +1,12 @@
 use std::sync::Arc;
 
+use anyhow::bail;
 use async_trait::async_trait;
 use tokio::sync::Mutex;
 
 use crate::{rdb_query_builder::RdbQueryBuilder, rdb_router::RdbRouter, Sinker};
 use dt_common::{
+    error::Error,
     log_sql,
     meta::{rdb_meta_manager::RdbMetaManager, row_data::RowData},
     monitor::monitor::Monitor,
@@ -27,6 +29,12 @@ impl Sinker for SqlSinker {
 
         for row_data in data {
             let row_data = if self.reverse {
+                if row_data.partial {
+                    bail! {Error::SinkerError(format!(
+                        "can not generate reverse sql from partial row images, binlog_row_image=full is required, row_data: {}",
+                        row_data
+                    ))}
+                }
                 row_data.reverse()
             } else {
                 row_data
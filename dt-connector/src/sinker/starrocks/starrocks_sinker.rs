@@ -6,12 +6,14 @@ use async_trait::async_trait;
 use chrono::Utc;
 use reqwest::{header, Client, Method, Response, StatusCode};
 use serde_json::{json, Value};
+use sqlx::{MySql, Pool};
 use tokio::{sync::Mutex, time::Instant};
 
 use dt_common::{
     config::config_enums::DbType,
     error::Error,
-    log_error,
+    log_error, log_info,
+    meta::ddl_meta::{ddl_data::DdlData, ddl_type::DdlType},
     meta::mysql::{
         mysql_col_type::MysqlColType, mysql_meta_manager::MysqlMetaManager,
         mysql_tb_meta::MysqlTbMeta,
@@ -34,6 +36,7 @@ pub struct StarRocksSinker {
     pub username: String,
     pub password: String,
     pub meta_manager: MysqlMetaManager,
+    pub conn_pool: Pool<MySql>,
     pub monitor: Arc<Mutex<Monitor>>,
     pub sync_timestamp: i64,
     pub hard_delete: bool,
@@ -54,6 +57,21 @@ impl Sinker for StarRocksSinker {
         Ok(())
     }
 
+    async fn sink_ddl(&mut self, data: Vec<DdlData>, _batch: bool) -> anyhow::Result<()> {
+        // only truncate is supported, other ddls are not compatible with starrocks / doris
+        for ddl_data in data {
+            if ddl_data.ddl_type != DdlType::TruncateTable {
+                continue;
+            }
+
+            for sql in ddl_data.to_truncate_sqls(&self.db_type) {
+                log_info!("sink ddl: {}", sql);
+                sqlx::query(&sql).execute(&self.conn_pool).await?;
+            }
+        }
+        Ok(())
+    }
+
     async fn close(&mut self) -> anyhow::Result<()> {
         self.meta_manager.close().await
     }
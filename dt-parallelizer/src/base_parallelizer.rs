@@ -28,11 +28,13 @@ impl BaseParallelizer {
         }
 
         let mut record_size_counter = Counter::new(0, 0);
-        // ddls and dmls should be drained seperately
+        // ddls, messages and dmls should be drained seperately
         while let Ok(item) = self.pop(buffer, &mut record_size_counter).await {
             if data.is_empty()
                 || (data[0].is_ddl() == item.is_ddl()
-                    && data[0].data_origin_node == item.data_origin_node)
+                    && data[0].is_message() == item.is_message()
+                    && data[0].data_origin_node == item.data_origin_node
+                    && data[0].data_path == item.data_path)
             {
                 data.push(item);
             } else {
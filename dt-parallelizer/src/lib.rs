@@ -10,13 +10,14 @@ pub mod redis_parallelizer;
 pub mod serial_parallelizer;
 pub mod snapshot_parallelizer;
 pub mod table_parallelizer;
+pub mod transaction_parallelizer;
 
 use std::sync::Arc;
 
 use async_trait::async_trait;
 use dt_common::meta::{
     dcl_meta::dcl_data::DclData, ddl_meta::ddl_data::DdlData, dt_data::DtItem, dt_queue::DtQueue,
-    row_data::RowData, struct_meta::struct_data::StructData,
+    message_data::MessageData, row_data::RowData, struct_meta::struct_data::StructData,
 };
 use dt_connector::Sinker;
 use merge_parallelizer::TbMergedData;
@@ -69,6 +70,18 @@ pub trait Parallelizer {
         Ok(())
     }
 
+    /// Messages are rare and should keep their order, so they are sinked by the first sinker
+    async fn sink_message(
+        &mut self,
+        data: Vec<MessageData>,
+        sinkers: &[Arc<async_mutex::Mutex<Box<dyn Sinker + Send>>>],
+    ) -> anyhow::Result<()> {
+        if let Some(sinker) = sinkers.first() {
+            sinker.lock().await.sink_message(data).await?;
+        }
+        Ok(())
+    }
+
     async fn close(&mut self) -> anyhow::Result<()> {
         Ok(())
     }
@@ -63,6 +63,13 @@ impl RdbMerger {
             .get_tb_meta(&row_data.schema, &row_data.tb)
             .await?;
 
+        // rows with partial images can not be converted to delete + insert,
+        // columns absent in after would be reset, and deletes of multiple rows have no single key
+        if row_data.partial || row_data.delete_all {
+            merged.unmerged_rows.push(row_data);
+            return Ok(());
+        }
+
         // case 1: table has no primary/unique key
         // case 2: any key col value is NULL
         let hash_code = Self::get_hash_code(&row_data, tb_meta).await?;
@@ -0,0 +1,288 @@
+use std::{collections::HashSet, sync::Arc};
+
+use async_trait::async_trait;
+use dt_common::{
+    log_debug,
+    meta::{
+        dcl_meta::dcl_data::DclData,
+        ddl_meta::ddl_data::DdlData,
+        dt_data::{DtData, DtItem},
+        dt_queue::DtQueue,
+        rdb_meta_manager::RdbMetaManager,
+        row_data::RowData,
+        struct_meta::struct_data::StructData,
+    },
+};
+use dt_connector::Sinker;
+
+use crate::Parallelizer;
+
+use super::base_parallelizer::BaseParallelizer;
+
+/// Keeps source transactions (delimited by DtData::Begin / DtData::Commit) intact,
+/// each transaction is sinked atomically by a single sinker, and only transactions
+/// whose write sets do not conflict are sinked in parallel.
+pub struct TransactionParallelizer {
+    pub base_parallelizer: BaseParallelizer,
+    pub meta_manager: RdbMetaManager,
+    pub parallel_size: usize,
+}
+
+#[derive(Default)]
+struct WriteSet {
+    // schema.tb.key_name.key_values of all pk / uk written
+    keys: HashSet<String>,
+    // tables written by rows with keys
+    tbs: HashSet<String>,
+    // tables can not be tracked by row keys: without pk / uk, or referenced by foreign keys
+    locked_tbs: HashSet<String>,
+}
+
+impl WriteSet {
+    fn conflicts(&self, other: &WriteSet) -> bool {
+        !self.keys.is_disjoint(&other.keys)
+            || Self::lock_conflicts(&self.locked_tbs, other)
+            || Self::lock_conflicts(&other.locked_tbs, self)
+    }
+
+    fn lock_conflicts(locked_tbs: &HashSet<String>, other: &WriteSet) -> bool {
+        locked_tbs
+            .iter()
+            .any(|tb| other.tbs.contains(tb) || other.locked_tbs.contains(tb))
+    }
+
+    fn merge(&mut self, other: WriteSet) {
+        self.keys.extend(other.keys);
+        self.tbs.extend(other.tbs);
+        self.locked_tbs.extend(other.locked_tbs);
+    }
+}
+
+#[derive(Default)]
+struct Transaction {
+    items: Vec<DtItem>,
+    write_set: WriteSet,
+    row_count: usize,
+}
+
+#[async_trait]
+impl Parallelizer for TransactionParallelizer {
+    fn get_name(&self) -> String {
+        "TransactionParallelizer".to_string()
+    }
+
+    async fn close(&mut self) -> anyhow::Result<()> {
+        self.meta_manager.close().await
+    }
+
+    async fn drain(&mut self, buffer: &DtQueue) -> anyhow::Result<Vec<DtItem>> {
+        let mut data = self.base_parallelizer.drain(buffer).await?;
+        // hold back the trailing incomplete transaction until its Commit arrives
+        let tx_start = data
+            .iter()
+            .rposition(|i| i.dt_data.is_commit())
+            .map_or(0, |i| i + 1);
+        if data[tx_start..]
+            .iter()
+            .any(|i| matches!(i.dt_data, DtData::Dml { .. }))
+        {
+            for item in data.drain(tx_start..).rev() {
+                self.base_parallelizer.poped_data.push_front(item);
+            }
+        }
+        Ok(data)
+    }
+
+    async fn sink_ddl(
+        &mut self,
+        data: Vec<DdlData>,
+        sinkers: &[Arc<async_mutex::Mutex<Box<dyn Sinker + Send>>>],
+    ) -> anyhow::Result<()> {
+        self.base_parallelizer
+            .sink_ddl(vec![data], sinkers, 1, false)
+            .await
+    }
+
+    async fn sink_dcl(
+        &mut self,
+        data: Vec<DclData>,
+        sinkers: &[Arc<async_mutex::Mutex<Box<dyn Sinker + Send>>>],
+    ) -> anyhow::Result<()> {
+        self.base_parallelizer
+            .sink_dcl(vec![data], sinkers, 1, false)
+            .await
+    }
+
+    async fn sink_dml(
+        &mut self,
+        data: Vec<RowData>,
+        sinkers: &[Arc<async_mutex::Mutex<Box<dyn Sinker + Send>>>],
+    ) -> anyhow::Result<()> {
+        // without transaction boundaries, all rows are sinked as one transaction
+        self.base_parallelizer
+            .sink_dml(vec![data], sinkers, 1, false)
+            .await
+    }
+
+    async fn sink_raw(
+        &mut self,
+        data: Vec<DtItem>,
+        sinkers: &[Arc<async_mutex::Mutex<Box<dyn Sinker + Send>>>],
+    ) -> anyhow::Result<()> {
+        let parallel_size = self.parallel_size.max(1);
+        let mut sub_datas: Vec<Vec<DtItem>> = (0..parallel_size).map(|_| Vec::new()).collect();
+        let mut sub_write_sets: Vec<WriteSet> =
+            (0..parallel_size).map(|_| WriteSet::default()).collect();
+        let mut sub_row_counts = vec![0; parallel_size];
+
+        for tx in self.split_transactions(data).await? {
+            let conflicted: Vec<usize> = (0..parallel_size)
+                .filter(|i| sub_write_sets[*i].conflicts(&tx.write_set))
+                .collect();
+
+            let index = match conflicted.len() {
+                // no conflict, assign to the least loaded sinker
+                0 => (0..parallel_size)
+                    .min_by_key(|i| sub_row_counts[*i])
+                    .unwrap(),
+                // conflicts with transactions of a single sinker, which sinks them in order
+                1 => conflicted[0],
+                // conflicts with transactions of multiple sinkers, wait until all of them are sinked
+                _ => {
+                    log_debug!(
+                        "transaction conflicts with {} sinkers, flush before sinking",
+                        conflicted.len()
+                    );
+                    self.flush(&mut sub_datas, sinkers).await?;
+                    sub_write_sets = (0..parallel_size).map(|_| WriteSet::default()).collect();
+                    sub_row_counts = vec![0; parallel_size];
+                    0
+                }
+            };
+
+            sub_datas[index].extend(tx.items);
+            sub_write_sets[index].merge(tx.write_set);
+            sub_row_counts[index] += tx.row_count;
+        }
+
+        self.flush(&mut sub_datas, sinkers).await
+    }
+
+    async fn sink_struct(
+        &mut self,
+        data: Vec<StructData>,
+        sinkers: &[Arc<async_mutex::Mutex<Box<dyn Sinker + Send>>>],
+    ) -> anyhow::Result<()> {
+        sinkers[0].lock().await.sink_struct(data).await
+    }
+}
+
+impl TransactionParallelizer {
+    async fn flush(
+        &self,
+        sub_datas: &mut [Vec<DtItem>],
+        sinkers: &[Arc<async_mutex::Mutex<Box<dyn Sinker + Send>>>],
+    ) -> anyhow::Result<()> {
+        let sub_datas: Vec<Vec<DtItem>> = sub_datas.iter_mut().map(std::mem::take).collect();
+        if sub_datas.iter().all(|i| i.is_empty()) {
+            return Ok(());
+        }
+        self.base_parallelizer
+            .sink_raw(sub_datas, sinkers, self.parallel_size.max(1), false)
+            .await
+    }
+
+    async fn split_transactions(&mut self, data: Vec<DtItem>) -> anyhow::Result<Vec<Transaction>> {
+        let mut txs = Vec::new();
+        let mut tx = Transaction::default();
+
+        for item in data {
+            match &item.dt_data {
+                DtData::Begin {} => {
+                    // Commit of the previous transaction missed, should not happen
+                    let previous = std::mem::take(&mut tx);
+                    if previous.row_count > 0 {
+                        txs.push(previous);
+                    }
+                    tx.items.push(item);
+                }
+
+                DtData::Dml { row_data } => {
+                    self.add_to_write_set(row_data, &mut tx.write_set).await?;
+                    tx.row_count += 1;
+                    tx.items.push(item);
+                }
+
+                DtData::Commit { .. } => {
+                    tx.items.push(item);
+                    let finished = std::mem::take(&mut tx);
+                    // transactions whose dmls are all filtered are ignored
+                    if finished.row_count > 0 {
+                        txs.push(finished);
+                    }
+                }
+
+                _ => {}
+            }
+        }
+
+        if tx.row_count > 0 {
+            txs.push(tx);
+        }
+        Ok(txs)
+    }
+
+    async fn add_to_write_set(
+        &mut self,
+        row_data: &RowData,
+        write_set: &mut WriteSet,
+    ) -> anyhow::Result<()> {
+        let tb_meta = self
+            .meta_manager
+            .get_tb_meta(&row_data.schema, &row_data.tb)
+            .await?;
+        let full_tb = format!("{}.{}", row_data.schema, row_data.tb);
+
+        // rows of tables with foreign keys may conflict with rows of other tables,
+        // which can not be detected by keys
+        if tb_meta.key_map.is_empty()
+            || !tb_meta.foreign_keys.is_empty()
+            || !tb_meta.ref_by_foreign_keys.is_empty()
+        {
+            write_set.locked_tbs.insert(full_tb);
+            for fk in tb_meta
+                .foreign_keys
+                .iter()
+                .chain(tb_meta.ref_by_foreign_keys.iter())
+            {
+                write_set
+                    .locked_tbs
+                    .insert(format!("{}.{}", fk.schema, fk.tb));
+                write_set
+                    .locked_tbs
+                    .insert(format!("{}.{}", fk.ref_schema, fk.ref_tb));
+            }
+            return Ok(());
+        }
+
+        // both before and after images are written for update
+        for col_values in [&row_data.before, &row_data.after].into_iter().flatten() {
+            for (key_name, key_cols) in tb_meta.key_map.iter() {
+                let key_values: Vec<String> = key_cols
+                    .iter()
+                    .map(|col| {
+                        col_values
+                            .get(col)
+                            .and_then(|v| v.to_option_string())
+                            .unwrap_or_default()
+                    })
+                    .collect();
+                write_set
+                    .keys
+                    .insert(format!("{}.{}.{:?}", full_tb, key_name, key_values));
+            }
+        }
+        write_set.tbs.insert(full_tb);
+        Ok(())
+    }
+}
@@ -6,15 +6,16 @@ use std::sync::{
 use async_trait::async_trait;
 use tokio::{sync::Mutex, sync::RwLock, time::Instant};
 
-use crate::{lua_processor::LuaProcessor, Pipeline};
+use crate::{lua_processor::LuaProcessor, mongo_flattener::MongoFlattener, Pipeline};
 use dt_common::{
-    config::sinker_config::SinkerConfig,
+    config::{config_enums::ParallelType, sinker_config::SinkerConfig},
     log_info, log_position,
     meta::{
         dcl_meta::dcl_data::DclData,
         ddl_meta::ddl_data::DdlData,
         dt_data::{DtData, DtItem},
         dt_queue::DtQueue,
+        message_data::MessageData,
         position::Position,
         row_data::RowData,
         syncer::Syncer,
@@ -29,6 +30,7 @@ pub struct BasePipeline {
     pub buffer: Arc<DtQueue>,
     pub parallelizer: Box<dyn Parallelizer + Send + Sync>,
     pub sinker_config: SinkerConfig,
+    pub parallel_type: ParallelType,
     pub sinkers: Vec<Arc<async_mutex::Mutex<Box<dyn Sinker + Send>>>>,
     pub shut_down: Arc<AtomicBool>,
     pub checkpoint_interval_secs: u64,
@@ -37,6 +39,7 @@ pub struct BasePipeline {
     pub monitor: Arc<Mutex<Monitor>>,
     pub data_marker: Option<Arc<RwLock<DataMarker>>>,
     pub lua_processor: Option<LuaProcessor>,
+    pub mongo_flattener: Option<MongoFlattener>,
 }
 
 enum SinkMethod {
@@ -45,6 +48,8 @@ enum SinkMethod {
     Dcl,
     Dml,
     Struct,
+    Transaction,
+    Message,
 }
 
 #[async_trait]
@@ -90,7 +95,9 @@ impl Pipeline for BasePipeline {
 
             if let Some(data_marker) = &mut self.data_marker {
                 if !data.is_empty() {
-                    data_marker.write().await.data_origin_node = data[0].data_origin_node.clone();
+                    let mut data_marker = data_marker.write().await;
+                    data_marker.data_origin_node = data[0].data_origin_node.clone();
+                    data_marker.set_data_path(&data[0].data_path);
                 }
             }
 
@@ -101,6 +108,8 @@ impl Pipeline for BasePipeline {
                 SinkMethod::Dml => self.sink_dml(data).await?,
                 SinkMethod::Raw => self.sink_raw(data).await?,
                 SinkMethod::Struct => self.sink_struct(data).await?,
+                SinkMethod::Transaction => self.sink_transaction(data).await?,
+                SinkMethod::Message => self.sink_message(data).await?,
             };
 
             if let Some(position) = &last_received {
@@ -169,6 +178,11 @@ impl BasePipeline {
         let (mut data, last_received_position, last_commit_position) = Self::fetch_dml(all_data);
         let count = data.len();
         if count > 0 {
+            // flatten mongo documents into relational rows
+            if let Some(mongo_flattener) = &self.mongo_flattener {
+                data = mongo_flattener.process(data)?;
+            }
+
             // execute lua processor
             if let Some(lua_processor) = &self.lua_processor {
                 data = lua_processor.process(data)?;
@@ -179,6 +193,90 @@ impl BasePipeline {
         Ok((count, last_received_position, last_commit_position))
     }
 
+    async fn sink_transaction(
+        &mut self,
+        mut all_data: Vec<DtItem>,
+    ) -> anyhow::Result<(usize, Option<Position>, Option<Position>)> {
+        let (last_received_position, last_commit_position) = Self::fetch_raw(&all_data);
+        // execute lua processor
+        if let Some(lua_processor) = &self.lua_processor {
+            all_data = Self::process_transaction_by_lua(lua_processor, all_data)?;
+        }
+
+        let count = all_data
+            .iter()
+            .filter(|i| matches!(i.dt_data, DtData::Dml { .. }))
+            .count();
+        // messages emitted inside transactions are sinked after the transactions
+        let messages: Vec<MessageData> = all_data
+            .iter()
+            .filter_map(|i| match &i.dt_data {
+                DtData::Message { message_data } => Some(message_data.clone()),
+                _ => None,
+            })
+            .collect();
+        if count > 0 {
+            // Begin / Commit are kept for the parallelizer to split transactions
+            self.parallelizer.sink_raw(all_data, &self.sinkers).await?
+        }
+        if !messages.is_empty() {
+            self.parallelizer
+                .sink_message(messages, &self.sinkers)
+                .await?
+        }
+        Ok((count, last_received_position, last_commit_position))
+    }
+
+    fn process_transaction_by_lua(
+        lua_processor: &LuaProcessor,
+        mut all_data: Vec<DtItem>,
+    ) -> anyhow::Result<Vec<DtItem>> {
+        let mut result = Vec::new();
+        let mut dml_items = Vec::new();
+        for item in all_data.drain(..) {
+            if matches!(item.dt_data, DtData::Dml { .. }) {
+                dml_items.push(item);
+                continue;
+            }
+            Self::process_dml_items_by_lua(lua_processor, &mut dml_items, &mut result)?;
+            result.push(item);
+        }
+        Self::process_dml_items_by_lua(lua_processor, &mut dml_items, &mut result)?;
+        Ok(result)
+    }
+
+    fn process_dml_items_by_lua(
+        lua_processor: &LuaProcessor,
+        dml_items: &mut Vec<DtItem>,
+        result: &mut Vec<DtItem>,
+    ) -> anyhow::Result<()> {
+        if dml_items.is_empty() {
+            return Ok(());
+        }
+
+        let (position, data_origin_node, data_path) = (
+            dml_items[0].position.clone(),
+            dml_items[0].data_origin_node.clone(),
+            dml_items[0].data_path.clone(),
+        );
+        let mut data = Vec::new();
+        for item in dml_items.drain(..) {
+            if let DtData::Dml { row_data } = item.dt_data {
+                data.push(row_data);
+            }
+        }
+        // rows may be filtered by lua, items in the same transaction share the same origin
+        for row_data in lua_processor.process(data)? {
+            result.push(DtItem {
+                dt_data: DtData::Dml { row_data },
+                position: position.clone(),
+                data_origin_node: data_origin_node.clone(),
+                data_path: data_path.clone(),
+            });
+        }
+        Ok(())
+    }
+
     async fn sink_ddl(
         &mut self,
         all_data: Vec<DtItem>,
@@ -209,6 +307,18 @@ impl BasePipeline {
         Ok((count, last_received_position, last_commit_position))
     }
 
+    async fn sink_message(
+        &mut self,
+        all_data: Vec<DtItem>,
+    ) -> anyhow::Result<(usize, Option<Position>, Option<Position>)> {
+        let (data, last_received_position, last_commit_position) = Self::fetch_message(all_data);
+        let count = data.len();
+        if count > 0 {
+            self.parallelizer.sink_message(data, &self.sinkers).await?;
+        }
+        Ok((count, last_received_position, last_commit_position))
+    }
+
     pub fn fetch_raw(data: &[DtItem]) -> (Option<Position>, Option<Position>) {
         let mut last_received_position = Option::None;
         let mut last_commit_position = Option::None;
@@ -311,6 +421,31 @@ impl BasePipeline {
         (result, last_received_position, last_commit_position)
     }
 
+    fn fetch_message(
+        mut data: Vec<DtItem>,
+    ) -> (Vec<MessageData>, Option<Position>, Option<Position>) {
+        let mut result = Vec::new();
+        let mut last_received_position = Option::None;
+        let mut last_commit_position = Option::None;
+        for i in data.drain(..) {
+            match i.dt_data {
+                DtData::Commit { .. } | DtData::Heartbeat {} => {
+                    last_commit_position = Some(i.position);
+                    last_received_position = last_commit_position.clone();
+                }
+
+                DtData::Message { message_data } => {
+                    last_received_position = Some(i.position);
+                    result.push(message_data);
+                }
+
+                _ => {}
+            }
+        }
+
+        (result, last_received_position, last_commit_position)
+    }
+
     fn get_sink_method(&self, data: &Vec<DtItem>) -> SinkMethod {
         for i in data {
             match i.dt_data {
@@ -321,9 +456,13 @@ impl BasePipeline {
                     SinkerConfig::FoxlakePush { .. }
                     | SinkerConfig::FoxlakeMerge { .. }
                     | SinkerConfig::Foxlake { .. } => return SinkMethod::Raw,
+                    _ if matches!(self.parallel_type, ParallelType::RdbTransaction) => {
+                        return SinkMethod::Transaction
+                    }
                     _ => return SinkMethod::Dml,
                 },
                 DtData::Redis { .. } | DtData::Foxlake { .. } => return SinkMethod::Raw,
+                DtData::Message { .. } => return SinkMethod::Message,
                 DtData::Begin {} | DtData::Commit { .. } | DtData::Heartbeat {} => {
                     continue;
                 }
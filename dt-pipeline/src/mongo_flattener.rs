@@ -0,0 +1,665 @@
+use std::collections::HashMap;
+
+use anyhow::bail;
+use dt_common::{
+    config::{
+        config_enums::DbType,
+        mongo_flatten_config::{FlattenArray, FlattenColumn, FlattenTable, MongoFlattenConfig},
+    },
+    error::Error,
+    log_warn,
+    meta::{
+        col_value::ColValue, mongo::mongo_constant::MongoConstants, row_data::RowData,
+        row_type::RowType,
+    },
+    utils::sql_util::SqlUtil,
+};
+use mongodb::bson::{Bson, Document};
+
+const SIGN_COL_NAME: &str = "_ape_dts_is_deleted";
+const TIMESTAMP_COL_NAME: &str = "_ape_dts_timestamp";
+
+/// flattens mongo documents into rows of relational tables by the mapping:
+/// mapped field paths into columns, remaining fields into a json column,
+/// arrays into child tables keyed by (parent_col, index_col)
+pub struct MongoFlattener {
+    pub tables: HashMap<(String, String), FlattenTable>,
+    pub db_type: DbType,
+}
+
+impl MongoFlattener {
+    pub fn new(config: &MongoFlattenConfig, db_type: &DbType) -> Self {
+        let tables = config
+            .tables
+            .iter()
+            .map(|i| ((i.db.clone(), i.coll.clone()), i.clone()))
+            .collect();
+        Self {
+            tables,
+            db_type: db_type.clone(),
+        }
+    }
+
+    pub fn process(&self, data: Vec<RowData>) -> anyhow::Result<Vec<RowData>> {
+        let mut new_data = Vec::new();
+        for row_data in data {
+            // collections not in mapping are passed through
+            match self
+                .tables
+                .get(&(row_data.schema.clone(), row_data.tb.clone()))
+            {
+                Some(table) => self.flatten(table, row_data, &mut new_data)?,
+                None => new_data.push(row_data),
+            }
+        }
+        Ok(new_data)
+    }
+
+    fn flatten(
+        &self,
+        table: &FlattenTable,
+        row_data: RowData,
+        new_data: &mut Vec<RowData>,
+    ) -> anyhow::Result<()> {
+        let schema = row_data.schema.clone();
+        let get_doc = |col_values: &Option<HashMap<String, ColValue>>, key: &str| {
+            if let Some(ColValue::MongoDoc(doc)) = col_values.as_ref().and_then(|i| i.get(key)) {
+                Some(doc.clone())
+            } else {
+                None
+            }
+        };
+
+        let (id_col, id_value) = match row_data.row_type {
+            RowType::Insert => Self::get_id(table, get_doc(&row_data.after, MongoConstants::DOC)),
+            _ => Self::get_id(table, get_doc(&row_data.before, MongoConstants::DOC)),
+        };
+        if id_value == ColValue::None {
+            log_warn!(
+                "document without _id can not be flattened, ignore, schema: {}, tb: {}",
+                row_data.schema,
+                row_data.tb
+            );
+            return Ok(());
+        }
+        let key = HashMap::from([(id_col, id_value.clone())]);
+
+        match row_data.row_type {
+            RowType::Insert => {
+                let doc = get_doc(&row_data.after, MongoConstants::DOC).unwrap();
+                let after =
+                    Self::build_col_values(&table.columns, &table.json_col, &table.arrays, &doc);
+                new_data.push(Self::new_row(
+                    &schema,
+                    &table.coll,
+                    RowType::Insert,
+                    None,
+                    Some(after),
+                ));
+                Self::insert_children(&schema, table, &id_value, &doc, new_data);
+            }
+
+            RowType::Delete => {
+                new_data.push(Self::new_row(
+                    &schema,
+                    &table.coll,
+                    RowType::Delete,
+                    Some(key),
+                    None,
+                ));
+                self.delete_children(&schema, table, &id_value, new_data);
+            }
+
+            RowType::Update => {
+                if let Some(doc) = get_doc(&row_data.after, MongoConstants::DOC) {
+                    // full document, replace the row and its children
+                    let after = Self::build_col_values(
+                        &table.columns,
+                        &table.json_col,
+                        &table.arrays,
+                        &doc,
+                    );
+                    new_data.push(Self::new_row(
+                        &schema,
+                        &table.coll,
+                        RowType::Update,
+                        Some(key),
+                        Some(after),
+                    ));
+                    self.delete_children(&schema, table, &id_value, new_data);
+                    Self::insert_children(&schema, table, &id_value, &doc, new_data);
+                } else if let Some(diff_doc) = get_doc(&row_data.after, MongoConstants::DIFF_DOC) {
+                    // diff from op_log, only mapped columns can be updated
+                    let after = Self::build_diff_col_values(table, &diff_doc);
+                    if !after.is_empty() {
+                        let mut row = Self::new_row(
+                            &schema,
+                            &table.coll,
+                            RowType::Update,
+                            Some(key),
+                            Some(after),
+                        );
+                        row.partial = true;
+                        new_data.push(row);
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn get_id(table: &FlattenTable, doc: Option<Document>) -> (String, ColValue) {
+        let id_col = table
+            .columns
+            .iter()
+            .find(|i| i.path == MongoConstants::ID)
+            .map_or(MongoConstants::ID.to_string(), |i| i.col.clone());
+        let id_value = doc
+            .and_then(|i| i.get(MongoConstants::ID).cloned())
+            .map_or(ColValue::None, |i| Self::bson_to_col_value(&i));
+        (id_col, id_value)
+    }
+
+    fn build_col_values(
+        columns: &[FlattenColumn],
+        json_col: &str,
+        arrays: &[FlattenArray],
+        doc: &Document,
+    ) -> HashMap<String, ColValue> {
+        let mut col_values = HashMap::new();
+        for column in columns.iter() {
+            let col_value =
+                Self::get_path(doc, &column.path).map_or(ColValue::None, Self::bson_to_col_value);
+            col_values.insert(column.col.clone(), col_value);
+        }
+
+        if !json_col.is_empty() {
+            let mut remaining = doc.clone();
+            for path in columns
+                .iter()
+                .map(|i| &i.path)
+                .chain(arrays.iter().map(|i| &i.path))
+            {
+                Self::remove_path(&mut remaining, path);
+            }
+            col_values.insert(
+                json_col.to_string(),
+                ColValue::Json2(Bson::Document(remaining).into_relaxed_extjson().to_string()),
+            );
+        }
+        col_values
+    }
+
+    fn build_diff_col_values(
+        table: &FlattenTable,
+        diff_doc: &Document,
+    ) -> HashMap<String, ColValue> {
+        let mut col_values = HashMap::new();
+        for (op, fields) in diff_doc.iter() {
+            let fields = match fields {
+                Bson::Document(fields) => fields,
+                _ => continue,
+            };
+
+            for (field, value) in fields.iter() {
+                let prefix = format!("{}.", field);
+                let mut mapped = false;
+                for column in table.columns.iter() {
+                    let col_value = if column.path == *field {
+                        Self::bson_to_col_value(value)
+                    } else if let Some(sub_path) = column.path.strip_prefix(&prefix) {
+                        match value {
+                            Bson::Document(sub_doc) => Self::get_path(sub_doc, sub_path)
+                                .map_or(ColValue::None, Self::bson_to_col_value),
+                            _ => ColValue::None,
+                        }
+                    } else {
+                        continue;
+                    };
+
+                    let col_value = if op == MongoConstants::UNSET {
+                        ColValue::None
+                    } else {
+                        col_value
+                    };
+                    col_values.insert(column.col.clone(), col_value);
+                    mapped = true;
+                }
+
+                if !mapped && (!table.json_col.is_empty() || !table.arrays.is_empty()) {
+                    log_warn!(
+                        "{} of field: {} can not be applied to json column / child tables without the full document, schema: {}, tb: {}",
+                        op,
+                        field,
+                        table.db,
+                        table.coll
+                    );
+                }
+            }
+        }
+        col_values
+    }
+
+    fn insert_children(
+        schema: &str,
+        table: &FlattenTable,
+        id_value: &ColValue,
+        doc: &Document,
+        new_data: &mut Vec<RowData>,
+    ) {
+        for array in table.arrays.iter() {
+            let elements = match Self::get_path(doc, &array.path) {
+                Some(Bson::Array(elements)) => elements,
+                _ => continue,
+            };
+
+            for (i, element) in elements.iter().enumerate() {
+                let mut after = match element {
+                    Bson::Document(element_doc) => {
+                        Self::build_col_values(&array.columns, &array.json_col, &[], element_doc)
+                    }
+                    _ => {
+                        let mut after = HashMap::new();
+                        if !array.json_col.is_empty() {
+                            after.insert(
+                                array.json_col.clone(),
+                                ColValue::Json2(element.clone().into_relaxed_extjson().to_string()),
+                            );
+                        }
+                        after
+                    }
+                };
+                // columns with empty path refer to the element itself
+                for column in array.columns.iter().filter(|c| c.path.is_empty()) {
+                    after.insert(column.col.clone(), Self::bson_to_col_value(element));
+                }
+                after.insert(array.parent_col.clone(), id_value.clone());
+                after.insert(array.index_col.clone(), ColValue::Long(i as i32));
+                new_data.push(Self::new_row(
+                    schema,
+                    &array.tb,
+                    RowType::Insert,
+                    None,
+                    Some(after),
+                ));
+            }
+        }
+    }
+
+    fn delete_children(
+        &self,
+        schema: &str,
+        table: &FlattenTable,
+        id_value: &ColValue,
+        new_data: &mut Vec<RowData>,
+    ) {
+        // rows can only be deleted by part of the keys in mysql / pg
+        if !matches!(self.db_type, DbType::Mysql | DbType::Pg) {
+            return;
+        }
+
+        for array in table.arrays.iter() {
+            // index_col absent, delete all children of the document
+            let before = HashMap::from([(array.parent_col.clone(), id_value.clone())]);
+            let mut row = Self::new_row(schema, &array.tb, RowType::Delete, Some(before), None);
+            row.delete_all = true;
+            new_data.push(row);
+        }
+    }
+
+    fn new_row(
+        schema: &str,
+        tb: &str,
+        row_type: RowType,
+        before: Option<HashMap<String, ColValue>>,
+        after: Option<HashMap<String, ColValue>>,
+    ) -> RowData {
+        RowData::new(schema.into(), tb.into(), row_type, before, after)
+    }
+
+    fn get_path<'a>(doc: &'a Document, path: &str) -> Option<&'a Bson> {
+        match path.split_once('.') {
+            Some((head, rest)) => match doc.get(head) {
+                Some(Bson::Document(sub_doc)) => Self::get_path(sub_doc, rest),
+                _ => None,
+            },
+            None => doc.get(path),
+        }
+    }
+
+    fn remove_path(doc: &mut Document, path: &str) {
+        match path.split_once('.') {
+            Some((head, rest)) => {
+                if let Some(Bson::Document(sub_doc)) = doc.get_mut(head) {
+                    Self::remove_path(sub_doc, rest);
+                }
+            }
+            None => {
+                doc.remove(path);
+            }
+        }
+    }
+
+    fn bson_to_col_value(value: &Bson) -> ColValue {
+        match value {
+            Bson::Null | Bson::Undefined => ColValue::None,
+            Bson::String(v) | Bson::Symbol(v) => ColValue::String(v.clone()),
+            Bson::Boolean(v) => ColValue::Bool(*v),
+            Bson::Int32(v) => ColValue::Long(*v),
+            Bson::Int64(v) => ColValue::LongLong(*v),
+            Bson::Double(v) => ColValue::Double(*v),
+            Bson::Decimal128(v) => ColValue::Decimal(v.to_string()),
+            Bson::ObjectId(v) => ColValue::String(v.to_hex()),
+            Bson::DateTime(v) => {
+                match chrono::DateTime::from_timestamp_millis(v.timestamp_millis()) {
+                    Some(v) => ColValue::DateTime(v.format("%Y-%m-%d %H:%M:%S%.3f").to_string()),
+                    None => ColValue::None,
+                }
+            }
+            Bson::Document(_) | Bson::Array(_) => {
+                ColValue::Json2(value.clone().into_relaxed_extjson().to_string())
+            }
+            _ => ColValue::String(value.clone().into_relaxed_extjson().to_string()),
+        }
+    }
+
+    /// sqls to create the schemas and tables: (schema, tb, schema_sql, tb_sql)
+    pub fn build_create_sqls(&self) -> anyhow::Result<Vec<(String, String, String, String)>> {
+        let db_type = &self.db_type;
+        let mut sqls = Vec::new();
+        for table in self.tables.values() {
+            let schema_sql = Self::build_create_schema_sql(&table.db, db_type)?;
+            // key columns must be the first columns in StarRocks
+            let mut cols = Vec::new();
+            for column in table
+                .columns
+                .iter()
+                .filter(|i| i.path == MongoConstants::ID)
+            {
+                cols.push((
+                    column.col.clone(),
+                    Self::get_col_type(column, true, db_type),
+                ));
+            }
+            for column in table
+                .columns
+                .iter()
+                .filter(|i| i.path != MongoConstants::ID)
+            {
+                cols.push((
+                    column.col.clone(),
+                    Self::get_col_type(column, false, db_type),
+                ));
+            }
+            if !table.json_col.is_empty() {
+                cols.push((table.json_col.clone(), Self::get_json_col_type(db_type)));
+            }
+            let key_cols = vec![cols[0].0.clone()];
+            let tb_sql =
+                Self::build_create_tb_sql(&table.db, &table.coll, &cols, &key_cols, db_type);
+            sqls.push((
+                table.db.clone(),
+                table.coll.clone(),
+                schema_sql.clone(),
+                tb_sql,
+            ));
+
+            for array in table.arrays.iter() {
+                let mut cols = vec![
+                    (array.parent_col.clone(), cols[0].1.clone()),
+                    (array.index_col.clone(), Self::get_index_col_type(db_type)),
+                ];
+                for column in array.columns.iter() {
+                    cols.push((
+                        column.col.clone(),
+                        Self::get_col_type(column, false, db_type),
+                    ));
+                }
+                if !array.json_col.is_empty() {
+                    cols.push((array.json_col.clone(), Self::get_json_col_type(db_type)));
+                }
+                let key_cols = vec![array.parent_col.clone(), array.index_col.clone()];
+                let tb_sql =
+                    Self::build_create_tb_sql(&table.db, &array.tb, &cols, &key_cols, db_type);
+                sqls.push((
+                    table.db.clone(),
+                    array.tb.clone(),
+                    schema_sql.clone(),
+                    tb_sql,
+                ));
+            }
+        }
+        Ok(sqls)
+    }
+
+    fn build_create_schema_sql(schema: &str, db_type: &DbType) -> anyhow::Result<String> {
+        let escaped_schema = SqlUtil::escape_by_db_type(schema, db_type);
+        let sql = match db_type {
+            DbType::Mysql | DbType::StarRocks | DbType::ClickHouse => {
+                format!("CREATE DATABASE IF NOT EXISTS {}", escaped_schema)
+            }
+            DbType::Pg => format!("CREATE SCHEMA IF NOT EXISTS {}", escaped_schema),
+            _ => bail! {Error::ConfigError(format!(
+                "flattening mongo documents into {} is not supported",
+                db_type
+            ))},
+        };
+        Ok(sql)
+    }
+
+    fn build_create_tb_sql(
+        schema: &str,
+        tb: &str,
+        cols: &[(String, String)],
+        key_cols: &[String],
+        db_type: &DbType,
+    ) -> String {
+        let escape = |i: &str| SqlUtil::escape_by_db_type(i, db_type);
+        let mut col_defs: Vec<String> = cols
+            .iter()
+            .map(|(col, col_type)| {
+                if key_cols.contains(col) && *db_type != DbType::ClickHouse {
+                    format!("{} {} NOT NULL", escape(col), col_type)
+                } else {
+                    format!("{} {}", escape(col), col_type)
+                }
+            })
+            .collect();
+        let keys = key_cols
+            .iter()
+            .map(|i| escape(i))
+            .collect::<Vec<String>>()
+            .join(",");
+        let full_tb = format!("{}.{}", escape(schema), escape(tb));
+
+        match db_type {
+            DbType::StarRocks => format!(
+                "CREATE TABLE IF NOT EXISTS {} ({}) PRIMARY KEY ({}) DISTRIBUTED BY HASH({})",
+                full_tb,
+                col_defs.join(", "),
+                keys,
+                escape(&key_cols[0])
+            ),
+
+            DbType::ClickHouse => {
+                col_defs.push(format!("{} Int8", escape(SIGN_COL_NAME)));
+                col_defs.push(format!("{} Int64", escape(TIMESTAMP_COL_NAME)));
+                format!(
+                    "CREATE TABLE IF NOT EXISTS {} ({}) ENGINE = ReplacingMergeTree({}) PRIMARY KEY ({}) ORDER BY ({})",
+                    full_tb,
+                    col_defs.join(", "),
+                    escape(TIMESTAMP_COL_NAME),
+                    keys,
+                    keys
+                )
+            }
+
+            _ => format!(
+                "CREATE TABLE IF NOT EXISTS {} ({}, PRIMARY KEY ({}))",
+                full_tb,
+                col_defs.join(", "),
+                keys
+            ),
+        }
+    }
+
+    fn get_col_type(column: &FlattenColumn, is_key: bool, db_type: &DbType) -> String {
+        if !column.col_type.is_empty() {
+            return column.col_type.clone();
+        }
+
+        let col_type = match (db_type, is_key) {
+            (DbType::ClickHouse, true) => "String",
+            (DbType::ClickHouse, false) => "Nullable(String)",
+            (_, true) => "varchar(255)",
+            (DbType::StarRocks, false) => "string",
+            (_, false) => "text",
+        };
+        col_type.to_string()
+    }
+
+    fn get_json_col_type(db_type: &DbType) -> String {
+        let col_type = match db_type {
+            DbType::Pg => "jsonb",
+            DbType::ClickHouse => "String",
+            _ => "json",
+        };
+        col_type.to_string()
+    }
+
+    fn get_index_col_type(db_type: &DbType) -> String {
+        let col_type = match db_type {
+            DbType::ClickHouse => "Int32",
+            _ => "int",
+        };
+        col_type.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mongodb::bson::{doc, oid::ObjectId};
+
+    use super::*;
+
+    fn build_flattener(db_type: DbType) -> MongoFlattener {
+        let mapping = r#"[{
+            "db": "test_db_1",
+            "coll": "orders",
+            "columns": [
+                {"path": "_id", "col": "id"},
+                {"path": "address.city", "col": "city"},
+                {"path": "amount", "col": "amount", "type": "decimal(10,2)"}
+            ],
+            "json_col": "extra",
+            "arrays": [{"path": "items", "tb": "orders_items", "columns": [{"path": "sku", "col": "sku"}]}]
+        }]"#;
+        let config = MongoFlattenConfig {
+            mapping_file: String::new(),
+            create_tables: false,
+            tables: serde_json::from_str(mapping).unwrap(),
+        };
+        MongoFlattener::new(&config, &db_type)
+    }
+
+    fn build_row_data(
+        row_type: RowType,
+        before: Option<(&str, Document)>,
+        after: Option<(&str, Document)>,
+    ) -> RowData {
+        let to_col_values = |i: Option<(&str, Document)>| {
+            i.map(|(k, v)| HashMap::from([(k.to_string(), ColValue::MongoDoc(v))]))
+        };
+        RowData::new(
+            "test_db_1".into(),
+            "orders".into(),
+            row_type,
+            to_col_values(before),
+            to_col_values(after),
+        )
+    }
+
+    #[test]
+    fn test_flatten_insert() {
+        let flattener = build_flattener(DbType::Mysql);
+        let oid = ObjectId::parse_str("6501a1c7f1d2a3b4c5d6e7f8").unwrap();
+        let doc = doc! {
+            "_id": oid,
+            "address": {"city": "hangzhou", "street": "a"},
+            "amount": 10.5,
+            "note": "fast",
+            "items": [{"sku": "s1", "qty": 1}, {"sku": "s2", "qty": 2}],
+        };
+        let row_data = build_row_data(RowType::Insert, None, Some((MongoConstants::DOC, doc)));
+        let data = flattener.process(vec![row_data]).unwrap();
+        assert_eq!(data.len(), 3);
+
+        let after = data[0].after.as_ref().unwrap();
+        assert_eq!(data[0].tb, "orders");
+        assert_eq!(after["id"], ColValue::String(oid.to_hex()));
+        assert_eq!(after["city"], ColValue::String("hangzhou".into()));
+        assert_eq!(after["amount"], ColValue::Double(10.5));
+        assert_eq!(
+            after["extra"],
+            ColValue::Json2(r#"{"address":{"street":"a"},"note":"fast"}"#.into())
+        );
+
+        let after = data[2].after.as_ref().unwrap();
+        assert_eq!(data[2].tb, "orders_items");
+        assert_eq!(after["parent_id"], ColValue::String(oid.to_hex()));
+        assert_eq!(after["idx"], ColValue::Long(1));
+        assert_eq!(after["sku"], ColValue::String("s2".into()));
+    }
+
+    #[test]
+    fn test_flatten_update_and_delete() {
+        let flattener = build_flattener(DbType::Mysql);
+        let key = doc! {"_id": 1};
+
+        // diff from op_log
+        let row_data = build_row_data(
+            RowType::Update,
+            Some((MongoConstants::DOC, key.clone())),
+            Some((
+                MongoConstants::DIFF_DOC,
+                doc! {"$set": {"address": {"city": "beijing"}}},
+            )),
+        );
+        let data = flattener.process(vec![row_data]).unwrap();
+        assert_eq!(data.len(), 1);
+        assert!(data[0].partial);
+        assert_eq!(data[0].before.as_ref().unwrap()["id"], ColValue::Long(1));
+        assert_eq!(
+            data[0].after.as_ref().unwrap(),
+            &HashMap::from([("city".to_string(), ColValue::String("beijing".into()))])
+        );
+
+        // children are deleted by parent_id
+        let row_data = build_row_data(RowType::Delete, Some((MongoConstants::DOC, key)), None);
+        let data = flattener.process(vec![row_data]).unwrap();
+        assert_eq!(data.len(), 2);
+        assert_eq!(data[1].tb, "orders_items");
+        assert!(data[1].delete_all);
+        assert!(!data[1].partial);
+        assert_eq!(
+            data[1].before.as_ref().unwrap(),
+            &HashMap::from([("parent_id".to_string(), ColValue::Long(1))])
+        );
+    }
+
+    #[test]
+    fn test_build_create_sqls() {
+        let flattener = build_flattener(DbType::Pg);
+        let sqls = flattener.build_create_sqls().unwrap();
+        assert_eq!(sqls.len(), 2);
+        assert_eq!(sqls[0].2, r#"CREATE SCHEMA IF NOT EXISTS "test_db_1""#);
+        assert_eq!(
+            sqls[0].3,
+            r#"CREATE TABLE IF NOT EXISTS "test_db_1"."orders" ("id" varchar(255) NOT NULL, "city" text, "amount" decimal(10,2), "extra" jsonb, PRIMARY KEY ("id"))"#
+        );
+        assert_eq!(
+            sqls[1].3,
+            r#"CREATE TABLE IF NOT EXISTS "test_db_1"."orders_items" ("parent_id" varchar(255) NOT NULL, "idx" int NOT NULL, "sku" text, PRIMARY KEY ("parent_id","idx"))"#
+        );
+    }
+}
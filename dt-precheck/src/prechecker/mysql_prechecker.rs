@@ -89,10 +89,15 @@ impl Prechecker for MySqlPrechecker {
         }
 
         let mut errs: Vec<String> = vec![];
-        let cdc_configs = ["log_bin", "binlog_format", "binlog_row_image"]
-            .iter()
-            .map(|c| c.to_string())
-            .collect::<Vec<String>>();
+        let cdc_configs = [
+            "log_bin",
+            "binlog_format",
+            "binlog_row_image",
+            "binlog_row_value_options",
+        ]
+        .iter()
+        .map(|c| c.to_string())
+        .collect::<Vec<String>>();
         let result = self.fetcher.fetch_configuration(cdc_configs).await;
         match result {
             Ok(configs) => {
@@ -107,9 +112,19 @@ impl Prechecker for MySqlPrechecker {
                             }
                         }
                         "binlog_row_image" => {
-                            if v.to_lowercase() != "full" {
+                            // minimal / noblob images are supported, absent columns are unchanged
+                            if !["full", "minimal", "noblob"].contains(&v.to_lowercase().as_str()) {
                                 errs.push(format!(
-                                    "binlog_row_image setting:[{}] is not 'full'",
+                                    "binlog_row_image setting:[{}] is not 'full', 'minimal' or 'noblob'",
+                                    v.to_lowercase()
+                                ));
+                            }
+                        }
+                        "binlog_row_value_options" => {
+                            // json diffs in PARTIAL_UPDATE_ROWS_EVENT are not decoded by the binlog connector
+                            if v.to_lowercase().contains("partial_json") {
+                                errs.push(format!(
+                                    "binlog_row_value_options setting:[{}] is not supported, it should be ''",
                                     v.to_lowercase()
                                 ));
                             }
@@ -15,7 +15,7 @@ use dt_common::{
         extractor_config::ExtractorConfig,
         task_config::TaskConfig,
     },
-    meta::{dt_queue::DtQueue, syncer::Syncer},
+    meta::{dt_queue::DtQueue, redis::command::key_parser::KeyParser, syncer::Syncer},
     monitor::monitor::Monitor,
     rdb_filter::RdbFilter,
     time_filter::TimeFilter,
@@ -107,6 +107,8 @@ impl Prechecker for RedisPrechecker {
             keepalive_interval_secs: 0,
             heartbeat_interval_secs: 0,
             heartbeat_key: String::new(),
+            node_address: String::new(),
+            key_parser: KeyParser::new(),
         };
 
         if let Err(error) = psyncer.start_psync().await {
@@ -13,8 +13,13 @@ use dt_common::{
         task_config::TaskConfig,
     },
     meta::{
-        avro::avro_converter::AvroConverter, mongo::mongo_cdc_source::MongoCdcSource,
-        pg::pg_meta_manager::PgMetaManager, redis::redis_statistic_type::RedisStatisticType,
+        avro::avro_converter::AvroConverter,
+        mongo::mongo_cdc_source::MongoCdcSource,
+        pg::pg_meta_manager::PgMetaManager,
+        redis::{
+            command::key_parser::KeyParser, redis_statistic_type::RedisStatisticType,
+            redis_write_method::RedisWriteMethod,
+        },
         syncer::Syncer,
     },
     meta::{
@@ -44,10 +49,12 @@ use dt_connector::{
         },
         pg::{
             pg_cdc_extractor::PgCdcExtractor, pg_check_extractor::PgCheckExtractor,
-            pg_snapshot_extractor::PgSnapshotExtractor, pg_struct_extractor::PgStructExtractor,
+            pg_snapshot_extractor::PgSnapshotExtractor, pg_stream_buffer::PgStreamBuffer,
+            pg_struct_extractor::PgStructExtractor,
         },
         redis::{
-            redis_client::RedisClient, redis_psync_extractor::RedisPsyncExtractor,
+            redis_check_extractor::RedisCheckExtractor, redis_client::RedisClient,
+            redis_psync_extractor::RedisPsyncExtractor,
             redis_reshard_extractor::RedisReshardExtractor,
             redis_scan_extractor::RedisScanExtractor,
             redis_snapshot_file_extractor::RedisSnapshotFileExtractor,
@@ -152,9 +159,12 @@ impl ExtractorUtil {
                 url,
                 binlog_filename,
                 binlog_position,
+                binlog_dir,
                 server_id,
                 gtid_enabled,
                 gtid_set,
+                failover_urls,
+                failover_timeout_secs,
                 binlog_heartbeat_interval_secs,
                 binlog_timeout_secs,
                 heartbeat_interval_secs,
@@ -179,6 +189,7 @@ impl ExtractorUtil {
                     url,
                     binlog_filename,
                     binlog_position,
+                    binlog_dir,
                     server_id,
                     binlog_heartbeat_interval_secs,
                     binlog_timeout_secs,
@@ -189,6 +200,12 @@ impl ExtractorUtil {
                     resumer: cdc_resumer,
                     gtid_enabled,
                     gtid_set,
+                    failover_urls: failover_urls
+                        .split(',')
+                        .map(|i| i.trim().to_string())
+                        .filter(|i| !i.is_empty())
+                        .collect(),
+                    failover_timeout_secs,
                 };
                 Box::new(extractor)
             }
@@ -246,6 +263,11 @@ impl ExtractorUtil {
                 heartbeat_interval_secs,
                 heartbeat_tb,
                 ddl_meta_tb,
+                capture_ddl_by_message,
+                message_prefixes,
+                proto_version,
+                streaming,
+                stream_spill_dir,
                 start_time_utc,
                 end_time_utc,
             } => {
@@ -267,6 +289,20 @@ impl ExtractorUtil {
                     heartbeat_interval_secs,
                     heartbeat_tb,
                     ddl_meta_tb,
+                    capture_ddl_by_message,
+                    message_prefixes: message_prefixes
+                        .split(',')
+                        .map(|i| i.trim().to_string())
+                        .filter(|i| !i.is_empty())
+                        .collect(),
+                    proto_version,
+                    streaming,
+                    // spill streamed transactions once they exceed the limits of buffer
+                    stream_buffer: PgStreamBuffer::new(
+                        &stream_spill_dir,
+                        config.pipeline.buffer_memory_mb * 1024 * 1024,
+                        config.pipeline.buffer_size,
+                    )?,
                     resumer: cdc_resumer,
                     base_extractor,
                 };
@@ -278,12 +314,16 @@ impl ExtractorUtil {
                 app_name,
                 db,
                 tb,
+                parallel_size,
+                batch_size,
             } => {
                 let mongo_client = TaskUtil::create_mongo_client(&url, &app_name).await?;
                 let extractor = MongoSnapshotExtractor {
                     resumer: snapshot_resumer,
                     db,
                     tb,
+                    parallel_size,
+                    batch_size,
                     mongo_client,
                     base_extractor,
                 };
@@ -363,7 +403,12 @@ impl ExtractorUtil {
                 Box::new(extractor)
             }
 
-            ExtractorConfig::RedisSnapshot { url, repl_port } => {
+            ExtractorConfig::RedisSnapshot {
+                url,
+                repl_port,
+                is_cluster,
+            } => {
+                let node_address = Self::get_redis_node_address(&url, is_cluster)?;
                 let extractor = RedisPsyncExtractor {
                     conn: RedisClient::new(&url).await?,
                     syncer,
@@ -378,6 +423,8 @@ impl ExtractorUtil {
                     keepalive_interval_secs: 0,
                     heartbeat_interval_secs: 0,
                     heartbeat_key: String::new(),
+                    node_address,
+                    key_parser: KeyParser::new(),
                 };
                 Box::new(extractor)
             }
@@ -395,13 +442,24 @@ impl ExtractorUtil {
                 url,
                 scan_count,
                 statistic_type,
+                method,
+                keyspace_notify,
             } => {
                 let conn = RedisUtil::create_redis_conn(&url).await?;
-                let statistic_type = RedisStatisticType::from_str(&statistic_type)?;
+                let statistic_type = if statistic_type.is_empty() {
+                    None
+                } else {
+                    Some(RedisStatisticType::from_str(&statistic_type)?)
+                };
                 let extractor = RedisScanExtractor {
+                    url,
                     conn,
+                    now_db_id: -1,
                     statistic_type,
+                    method: RedisWriteMethod::from_str(&method)?,
                     scan_count,
+                    keyspace_notify,
+                    key_parser: KeyParser::new(),
                     filter,
                     base_extractor,
                 };
@@ -417,7 +475,9 @@ impl ExtractorUtil {
                 keepalive_interval_secs,
                 heartbeat_interval_secs,
                 heartbeat_key,
+                is_cluster,
             } => {
+                let node_address = Self::get_redis_node_address(&url, is_cluster)?;
                 let extractor = RedisPsyncExtractor {
                     conn: RedisClient::new(&url).await?,
                     repl_id,
@@ -432,6 +492,8 @@ impl ExtractorUtil {
                     resumer: cdc_resumer,
                     base_extractor,
                     extract_type: ExtractType::Cdc,
+                    node_address,
+                    key_parser: KeyParser::new(),
                 };
                 Box::new(extractor)
             }
@@ -443,7 +505,9 @@ impl ExtractorUtil {
                 keepalive_interval_secs,
                 heartbeat_interval_secs,
                 heartbeat_key,
+                is_cluster,
             } => {
+                let node_address = Self::get_redis_node_address(&url, is_cluster)?;
                 let extractor = RedisPsyncExtractor {
                     conn: RedisClient::new(&url).await?,
                     syncer,
@@ -458,6 +522,8 @@ impl ExtractorUtil {
                     keepalive_interval_secs,
                     heartbeat_interval_secs,
                     heartbeat_key,
+                    node_address,
+                    key_parser: KeyParser::new(),
                 };
                 Box::new(extractor)
             }
@@ -470,6 +536,21 @@ impl ExtractorUtil {
                 Box::new(extractor)
             }
 
+            ExtractorConfig::RedisCheck {
+                url,
+                check_log_dir,
+                batch_size,
+            } => {
+                let conn = RedisUtil::create_redis_conn(&url).await?;
+                let extractor = RedisCheckExtractor {
+                    base_extractor,
+                    conn,
+                    check_log_dir,
+                    batch_size,
+                };
+                Box::new(extractor)
+            }
+
             ExtractorConfig::Kafka {
                 url,
                 group,
@@ -539,4 +620,13 @@ impl ExtractorUtil {
         };
         Ok(meta_manager)
     }
+
+    fn get_redis_node_address(url: &str, is_cluster: bool) -> anyhow::Result<String> {
+        // for a cluster source, each extractor connects to one of the master nodes
+        if is_cluster {
+            RedisUtil::get_url_address(url)
+        } else {
+            Ok(String::new())
+        }
+    }
 }
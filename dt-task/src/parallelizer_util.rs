@@ -23,7 +23,8 @@ use dt_parallelizer::{
     mongo_merger::MongoMerger, partition_parallelizer::PartitionParallelizer,
     rdb_merger::RdbMerger, rdb_partitioner::RdbPartitioner, redis_parallelizer::RedisParallelizer,
     serial_parallelizer::SerialParallelizer, snapshot_parallelizer::SnapshotParallelizer,
-    table_parallelizer::TableParallelizer, Merger, Parallelizer,
+    table_parallelizer::TableParallelizer, transaction_parallelizer::TransactionParallelizer,
+    Merger, Parallelizer,
 };
 
 pub struct ParallelizerUtil {}
@@ -81,6 +82,15 @@ impl ParallelizerUtil {
                 })
             }
 
+            ParallelType::RdbTransaction => {
+                let meta_manager = TaskUtil::create_rdb_meta_manager(config).await?.unwrap();
+                Box::new(TransactionParallelizer {
+                    base_parallelizer,
+                    meta_manager,
+                    parallel_size,
+                })
+            }
+
             ParallelType::Serial => Box::new(SerialParallelizer { base_parallelizer }),
 
             ParallelType::Table => Box::new(TableParallelizer {
@@ -101,7 +111,9 @@ impl ParallelizerUtil {
 
             ParallelType::Redis => {
                 let mut slot_node_map = HashMap::new();
-                if let SinkerConfig::Redis { is_cluster, .. } = config.sinker {
+                if let SinkerConfig::Redis { is_cluster, .. }
+                | SinkerConfig::RedisCheck { is_cluster, .. } = config.sinker
+                {
                     let mut conn = RedisUtil::create_redis_conn(&config.sinker_basic.url).await?;
                     if is_cluster {
                         let nodes = RedisUtil::get_cluster_master_nodes(&mut conn)?;
@@ -33,6 +33,7 @@ use dt_connector::{
         clickhouse::{
             clickhouse_sinker::ClickhouseSinker, clickhouse_struct_sinker::ClickhouseStructSinker,
         },
+        conflict_resolver::ConflictResolver,
         dummy_sinker::DummySinker,
         foxlake::{
             foxlake_merger::FoxlakeMerger, foxlake_pusher::FoxlakePusher,
@@ -46,7 +47,14 @@ use dt_connector::{
             mysql_struct_sinker::MysqlStructSinker,
         },
         pg::{pg_checker::PgChecker, pg_sinker::PgSinker, pg_struct_sinker::PgStructSinker},
-        redis::{redis_sinker::RedisSinker, redis_statistic_sinker::RedisStatisticSinker},
+        redis::{
+            dml_template::DmlTemplate,
+            key_statistics::KeyStatistics,
+            rdb_file_sinker::{RdbFile, RedisRdbFileSinker},
+            redis_checker::RedisChecker,
+            redis_sinker::RedisSinker,
+            redis_statistic_sinker::RedisStatisticSinker,
+        },
         sql_sinker::SqlSinker,
         starrocks::{
             starrocks_sinker::StarRocksSinker, starrocks_struct_sinker::StarrocksStructSinker,
@@ -98,6 +106,7 @@ impl SinkerUtil {
                 batch_size,
                 replace,
                 disable_foreign_key_checks,
+                conflict_resolve,
             } => {
                 let router = create_router!(task_config, Mysql);
                 let conn_pool = TaskUtil::create_mysql_conn_pool(
@@ -121,6 +130,7 @@ impl SinkerUtil {
                         monitor: monitor.clone(),
                         data_marker: data_marker.clone(),
                         replace,
+                        conflict_resolver: ConflictResolver::new(conflict_resolve.clone()),
                     };
                     sub_sinkers.push(Arc::new(async_mutex::Mutex::new(Box::new(sinker))));
                 }
@@ -164,6 +174,7 @@ impl SinkerUtil {
                 batch_size,
                 replace,
                 disable_foreign_key_checks,
+                conflict_resolve,
             } => {
                 let router = create_router!(task_config, Pg);
                 let conn_pool = TaskUtil::create_pg_conn_pool(
@@ -175,7 +186,7 @@ impl SinkerUtil {
                 .await?;
                 let meta_manager = PgMetaManager::new(conn_pool.clone()).await?;
 
-                for _ in 0..parallel_size {
+                for sinker_index in 0..parallel_size {
                     let sinker = PgSinker {
                         url: url.to_string(),
                         conn_pool: conn_pool.clone(),
@@ -185,6 +196,9 @@ impl SinkerUtil {
                         monitor: monitor.clone(),
                         data_marker: data_marker.clone(),
                         replace,
+                        conflict_resolver: ConflictResolver::new(conflict_resolve.clone()),
+                        sinker_index,
+                        origin_session: None,
                     };
                     sub_sinkers.push(Arc::new(async_mutex::Mutex::new(Box::new(sinker))));
                 }
@@ -262,6 +276,7 @@ impl SinkerUtil {
                 ack_timeout_secs,
                 required_acks,
                 with_field_defs,
+                message_format,
             } => {
                 let router = RdbRouter::from_config(
                     &task_config.router,
@@ -294,6 +309,8 @@ impl SinkerUtil {
                         router: router.clone(),
                         producer,
                         avro_converter: avro_converter.clone(),
+                        message_format: message_format.clone(),
+                        key_parser: KeyParser::new(),
                         monitor: monitor.clone(),
                     };
                     sub_sinkers.push(Arc::new(async_mutex::Mutex::new(Box::new(sinker))));
@@ -303,6 +320,7 @@ impl SinkerUtil {
             SinkerConfig::MysqlStruct {
                 url,
                 conflict_policy,
+                phase,
             } => {
                 let filter = create_filter!(task_config, Mysql);
                 let router = create_router!(task_config, Mysql);
@@ -318,6 +336,9 @@ impl SinkerUtil {
                     conflict_policy: conflict_policy.clone(),
                     filter: filter.clone(),
                     router,
+                    phase: phase.clone(),
+                    parallel_size: parallel_size as usize,
+                    foreign_key_sqls: Vec::new(),
                 };
                 sub_sinkers.push(Arc::new(async_mutex::Mutex::new(Box::new(sinker))));
             }
@@ -325,6 +346,7 @@ impl SinkerUtil {
             SinkerConfig::PgStruct {
                 url,
                 conflict_policy,
+                phase,
             } => {
                 let filter = create_filter!(task_config, Pg);
                 let router = create_router!(task_config, Pg);
@@ -336,6 +358,9 @@ impl SinkerUtil {
                     conflict_policy: conflict_policy.clone(),
                     filter: filter.clone(),
                     router,
+                    phase: phase.clone(),
+                    parallel_size: parallel_size as usize,
+                    foreign_key_sqls: Vec::new(),
                 };
                 sub_sinkers.push(Arc::new(async_mutex::Mutex::new(Box::new(sinker))));
             }
@@ -345,9 +370,11 @@ impl SinkerUtil {
                 batch_size,
                 method,
                 is_cluster,
+                templates,
             } => {
                 // redis sinker may need meta data from RDB extractor
                 let meta_manager = ExtractorUtil::get_extractor_meta_manager(task_config).await?;
+                let templates = DmlTemplate::parse_templates(&templates)?;
                 let mut conn = RedisUtil::create_redis_conn(&url).await?;
                 let version = RedisUtil::get_redis_version(&mut conn)?;
                 let method = RedisWriteMethod::from_str(&method)?;
@@ -376,6 +403,7 @@ impl SinkerUtil {
                             monitor: monitor.clone(),
                             data_marker: data_marker.clone(),
                             key_parser: KeyParser::new(),
+                            templates: templates.clone(),
                         };
                         sub_sinkers.push(Arc::new(async_mutex::Mutex::new(Box::new(sinker))));
                     }
@@ -393,6 +421,55 @@ impl SinkerUtil {
                             monitor: monitor.clone(),
                             data_marker: data_marker.clone(),
                             key_parser: KeyParser::new(),
+                            templates: templates.clone(),
+                        };
+                        sub_sinkers.push(Arc::new(async_mutex::Mutex::new(Box::new(sinker))));
+                    }
+                }
+            }
+
+            SinkerConfig::RedisCheck {
+                url,
+                batch_size,
+                is_cluster,
+                ttl_tolerance_secs,
+                ..
+            } => {
+                let ttl_tolerance_ms = ttl_tolerance_secs as i64 * 1000;
+                if is_cluster {
+                    let mut conn = RedisUtil::create_redis_conn(&url).await?;
+                    let url_info = Url::parse(&url)?;
+                    let username = url_info.username();
+                    let password = url_info.password().unwrap_or("").to_string();
+
+                    let nodes = RedisUtil::get_cluster_master_nodes(&mut conn)?;
+                    for node in nodes.iter() {
+                        if !node.is_master {
+                            continue;
+                        }
+
+                        let new_url = format!("redis://{}:{}@{}", username, password, node.address);
+                        let conn = RedisUtil::create_redis_conn(&new_url).await?;
+                        let sinker = RedisChecker {
+                            cluster_node: Some(node.clone()),
+                            conn,
+                            now_db_id: -1,
+                            batch_size,
+                            ttl_tolerance_ms,
+                            monitor: monitor.clone(),
+                        };
+                        sub_sinkers.push(Arc::new(async_mutex::Mutex::new(Box::new(sinker))));
+                    }
+                } else {
+                    for _ in 0..parallel_size {
+                        let conn = RedisUtil::create_redis_conn(&url).await?;
+                        let sinker = RedisChecker {
+                            cluster_node: None,
+                            conn,
+                            now_db_id: -1,
+                            batch_size,
+                            ttl_tolerance_ms,
+                            monitor: monitor.clone(),
                         };
                         sub_sinkers.push(Arc::new(async_mutex::Mutex::new(Box::new(sinker))));
                     }
@@ -403,15 +480,43 @@ impl SinkerUtil {
                 statistic_type,
                 data_size_threshold,
                 freq_threshold,
+                time_window_secs,
+                report_interval_secs,
+                top_n,
+                key_prefix_delimiter,
+                key_prefix_depth,
                 ..
             } => {
                 let statistic_type = RedisStatisticType::from_str(&statistic_type)?;
+                let key_statistics = Arc::new(Mutex::new(KeyStatistics::new(
+                    time_window_secs,
+                    report_interval_secs,
+                    top_n,
+                    &key_prefix_delimiter,
+                    key_prefix_depth,
+                )));
                 for _ in 0..parallel_size {
                     let sinker = RedisStatisticSinker {
                         statistic_type: statistic_type.clone(),
                         data_size_threshold,
                         freq_threshold,
                         monitor: monitor.clone(),
+                        key_statistics: key_statistics.clone(),
+                    };
+                    sub_sinkers.push(Arc::new(async_mutex::Mutex::new(Box::new(sinker))));
+                }
+            }
+
+            SinkerConfig::RedisSnapshotFile {
+                file_path,
+                rdb_version,
+            } => {
+                let rdb_file =
+                    Arc::new(Mutex::new(RdbFile::create(&file_path, rdb_version).await?));
+                for _ in 0..parallel_size {
+                    let sinker = RedisRdbFileSinker {
+                        rdb_file: rdb_file.clone(),
+                        monitor: monitor.clone(),
                     };
                     sub_sinkers.push(Arc::new(async_mutex::Mutex::new(Box::new(sinker))));
                 }
@@ -461,6 +566,7 @@ impl SinkerUtil {
                         password,
                         batch_size,
                         meta_manager,
+                        conn_pool,
                         monitor: monitor.clone(),
                         sync_timestamp: Utc::now().timestamp_millis(),
                         hard_delete: false,
@@ -1,5 +1,5 @@
 use std::{
-    collections::VecDeque,
+    collections::{HashMap, VecDeque},
     panic,
     sync::{
         atomic::{AtomicBool, Ordering},
@@ -10,6 +10,7 @@ use std::{
 use anyhow::{bail, Context};
 use log4rs::config::RawConfig;
 use ratelimit::Ratelimiter;
+use reqwest::Url;
 use tokio::{
     fs::metadata, fs::File, io::AsyncReadExt, sync::Mutex, sync::RwLock, task::JoinSet,
     time::Duration, try_join,
@@ -24,11 +25,13 @@ use dt_common::{
         task_config::TaskConfig,
     },
     error::Error,
-    log_finished, log_info,
-    meta::{avro::avro_converter::AvroConverter, dt_queue::DtQueue},
+    log_finished, log_info, log_warn,
+    meta::{
+        avro::avro_converter::AvroConverter, dt_queue::DtQueue, redis::cluster_node::ClusterNode,
+    },
     monitor::{group_monitor::GroupMonitor, monitor::Monitor, FlushableMonitor},
     rdb_filter::RdbFilter,
-    utils::{sql_util::SqlUtil, time_util::TimeUtil},
+    utils::{redis_util::RedisUtil, sql_util::SqlUtil, time_util::TimeUtil},
 };
 use dt_common::{
     log_error,
@@ -42,7 +45,7 @@ use dt_connector::{
 };
 use dt_pipeline::{
     base_pipeline::BasePipeline, http_server_pipeline::HttpServerPipeline,
-    lua_processor::LuaProcessor, Pipeline,
+    lua_processor::LuaProcessor, mongo_flattener::MongoFlattener, Pipeline,
 };
 
 use crate::task_util::TaskUtil;
@@ -65,6 +68,8 @@ const LOG_LEVEL_PLACEHODLER: &str = "LOG_LEVEL_PLACEHODLER";
 const LOG_DIR_PLACEHODLER: &str = "LOG_DIR_PLACEHODLER";
 const DEFAULT_CHECK_LOG_DIR_PLACEHODLER: &str = "LOG_DIR_PLACEHODLER/check";
 const DEFAULT_STATISTIC_LOG_DIR_PLACEHODLER: &str = "LOG_DIR_PLACEHODLER/statistic";
+const REDIS_CLUSTER_CHECK_INTERVAL_SECS: u64 = 10;
+const REDIS_CLUSTER_MAX_RESTART_TIMES: usize = 10;
 
 impl TaskRunner {
     pub fn new(task_config_file: &str) -> anyhow::Result<Self> {
@@ -92,8 +97,22 @@ impl TaskRunner {
         let router = RdbRouter::from_config(&self.config.router, db_type)?;
         let snapshot_resumer = SnapshotResumer::from_config(&self.config)?;
         let cdc_resumer = CdcResumer::from_config(&self.config)?;
+        self.create_flatten_tbs().await?;
 
         match &self.config.extractor {
+            ExtractorConfig::RedisSnapshot {
+                is_cluster: true, ..
+            }
+            | ExtractorConfig::RedisCdc {
+                is_cluster: true, ..
+            }
+            | ExtractorConfig::RedisSnapshotAndCdc {
+                is_cluster: true, ..
+            } => {
+                self.start_redis_cluster_task(&router, &snapshot_resumer, &cdc_resumer)
+                    .await?
+            }
+
             ExtractorConfig::MysqlStruct { url, .. }
             | ExtractorConfig::PgStruct { url, .. }
             | ExtractorConfig::MysqlSnapshot { url, .. }
@@ -111,6 +130,7 @@ impl TaskRunner {
                         &router,
                         &snapshot_resumer,
                         &cdc_resumer,
+                        Arc::new(Mutex::new(Syncer::default())),
                     )
                     .await?
             }
@@ -159,7 +179,13 @@ impl TaskRunner {
 
             if let Some(extractor_config) = schema_extractor_config {
                 self.clone()
-                    .start_single_task(&extractor_config, router, snapshot_resumer, cdc_resumer)
+                    .start_single_task(
+                        &extractor_config,
+                        router,
+                        snapshot_resumer,
+                        cdc_resumer,
+                        Arc::new(Mutex::new(Syncer::default())),
+                    )
                     .await?;
                 continue;
             }
@@ -290,14 +316,20 @@ impl TaskRunner {
                 batch_size: *batch_size,
             },
 
-            ExtractorConfig::MongoSnapshot { url, app_name, .. } => {
-                ExtractorConfig::MongoSnapshot {
-                    url: url.clone(),
-                    app_name: app_name.clone(),
-                    db: schema.into(),
-                    tb: tb.into(),
-                }
-            }
+            ExtractorConfig::MongoSnapshot {
+                url,
+                app_name,
+                parallel_size,
+                batch_size,
+                ..
+            } => ExtractorConfig::MongoSnapshot {
+                url: url.clone(),
+                app_name: app_name.clone(),
+                db: schema.into(),
+                tb: tb.into(),
+                parallel_size: *parallel_size,
+                batch_size: *batch_size,
+            },
 
             ExtractorConfig::FoxlakeS3 {
                 url,
@@ -331,6 +363,7 @@ impl TaskRunner {
                     &router,
                     &snapshot_resumer,
                     &cdc_resumer,
+                    Arc::new(Mutex::new(Syncer::default())),
                 )
                 .await;
             (single_task_id, res)
@@ -338,12 +371,336 @@ impl TaskRunner {
         Ok(())
     }
 
+    async fn start_redis_cluster_task(
+        &self,
+        router: &RdbRouter,
+        snapshot_resumer: &SnapshotResumer,
+        cdc_resumer: &CdcResumer,
+    ) -> anyhow::Result<()> {
+        let url = &self.config.extractor_basic.url;
+        let is_cdc = !matches!(self.config.extractor, ExtractorConfig::RedisSnapshot { .. });
+        let masters = Self::get_redis_cluster_masters(&[url.to_owned()]).await?;
+
+        // start a thread to flush global monitors
+        let global_shut_down = Arc::new(AtomicBool::new(false));
+        let global_shut_down_clone = global_shut_down.clone();
+        let interval_secs = self.config.pipeline.checkpoint_interval_secs;
+        let extractor_monitor = self.extractor_monitor.clone();
+        let pipeline_monitor = self.pipeline_monitor.clone();
+        let sinker_monitor = self.sinker_monitor.clone();
+        let global_monitor_task = tokio::spawn(async move {
+            Self::flush_group_monitors(
+                interval_secs,
+                global_shut_down_clone,
+                extractor_monitor,
+                pipeline_monitor,
+                sinker_monitor,
+            )
+            .await
+        });
+
+        // start a PSYNC task for each master node
+        let mut join_set: JoinSet<(String, anyhow::Result<()>)> = JoinSet::new();
+        let mut node_tasks = HashMap::new();
+        for node in masters {
+            let position = cdc_resumer
+                .redis_node_positions
+                .get(&node.address)
+                .cloned()
+                .unwrap_or_default();
+            // a cdc task can not start from the configured repl_id / repl_offset, which belong
+            // to one node only, each node must resume from its own position
+            if matches!(self.config.extractor, ExtractorConfig::RedisCdc { .. })
+                && position == Position::None
+            {
+                bail! {Error::ConfigError(format!(
+                    "no position found for redis node: [{}] in resume files, use extract_type=snapshot_and_cdc instead",
+                    node.address
+                ))}
+            }
+            let task = RedisNodeTask::new(node, position, 0);
+            self.spawn_redis_node_task(
+                &task,
+                router,
+                snapshot_resumer,
+                cdc_resumer,
+                &mut join_set,
+            )?;
+            node_tasks.insert(task.node.address.clone(), task);
+        }
+
+        // tasks of nodes which failed, waiting to be restarted in the current master of their slots
+        let mut failed_tasks: Vec<RedisNodeTask> = Vec::new();
+        let check_interval_millis = REDIS_CLUSTER_CHECK_INTERVAL_SECS * 1000;
+        loop {
+            if join_set.is_empty() && failed_tasks.is_empty() {
+                break;
+            }
+
+            let refresh = tokio::select! {
+                Some(result) = join_set.join_next() => {
+                    let (address, result) = result?;
+                    let task = node_tasks.remove(&address).unwrap();
+                    match result {
+                        Ok(()) => log_info!("redis node task: [{}] finished", address),
+                        Err(err) => {
+                            if !is_cdc {
+                                bail!("redis node task: [{}] failed, error: {}", address, err)
+                            }
+                            log_warn!("redis node task: [{}] failed, error: {}", address, err);
+                            failed_tasks.push(task.into_failed().await);
+                        }
+                    }
+                    false
+                }
+
+                _ = TimeUtil::sleep_millis(check_interval_millis), if is_cdc => true,
+            };
+
+            // failed tasks are restarted in the next check, the failover may be in progress
+            if !refresh {
+                continue;
+            }
+
+            // refresh topology, restart failed tasks in current masters after failover,
+            // and start tasks for new masters
+            let mut seed_urls = vec![url.to_owned()];
+            for address in node_tasks
+                .keys()
+                .chain(failed_tasks.iter().map(|i| &i.node.address))
+            {
+                seed_urls.push(RedisUtil::get_node_url(url, address)?);
+            }
+            let masters = match Self::get_redis_cluster_masters(&seed_urls).await {
+                Ok(masters) => masters,
+                Err(err) => {
+                    log_warn!("get redis cluster masters failed, error: {}", err);
+                    continue;
+                }
+            };
+
+            for node in masters {
+                if let Some(task) = node_tasks.get_mut(&node.address) {
+                    if task.node.slots != node.slots {
+                        log_info!(
+                            "slots of redis node: [{}] changed, slots count: {} -> {}",
+                            node.address,
+                            task.node.slots.len(),
+                            node.slots.len()
+                        );
+                        task.node = node;
+                    }
+                    continue;
+                }
+
+                // the failed task which owned the slots of this master, the master may be
+                // the same node restarted, or a replica promoted after failover
+                let failed_task = failed_tasks
+                    .iter()
+                    .position(|i| i.node.slots.iter().any(|slot| node.slots.contains(slot)))
+                    .map(|i| failed_tasks.remove(i));
+
+                let task = if let Some(failed_task) = failed_task {
+                    if failed_task.failed_times >= REDIS_CLUSTER_MAX_RESTART_TIMES {
+                        bail!(
+                            "redis node task: [{}] failed {} times, last position: {}",
+                            failed_task.node.address,
+                            failed_task.failed_times,
+                            failed_task.position
+                        )
+                    }
+                    log_info!(
+                        "restart redis node task in [{}] for [{}], position: {}",
+                        node.address,
+                        failed_task.node.address,
+                        failed_task.position
+                    );
+                    RedisNodeTask::new(node, failed_task.position, failed_task.failed_times)
+                } else {
+                    log_info!("start redis node task for new master: [{}]", node.address);
+                    RedisNodeTask::new(node, Position::None, 0)
+                };
+
+                self.spawn_redis_node_task(
+                    &task,
+                    router,
+                    snapshot_resumer,
+                    cdc_resumer,
+                    &mut join_set,
+                )?;
+                node_tasks.insert(task.node.address.clone(), task);
+            }
+        }
+
+        global_shut_down.store(true, Ordering::Release);
+        global_monitor_task.await?;
+        Ok(())
+    }
+
+    fn spawn_redis_node_task(
+        &self,
+        task: &RedisNodeTask,
+        router: &RdbRouter,
+        snapshot_resumer: &SnapshotResumer,
+        cdc_resumer: &CdcResumer,
+        join_set: &mut JoinSet<(String, anyhow::Result<()>)>,
+    ) -> anyhow::Result<()> {
+        let node_url =
+            RedisUtil::get_node_url(&self.config.extractor_basic.url, &task.node.address)?;
+        let node_extractor_config =
+            self.build_redis_node_extractor_config(node_url, &task.node, &task.position)?;
+
+        let address = task.node.address.clone();
+        let syncer = task.syncer.clone();
+        let router = router.clone();
+        let snapshot_resumer = snapshot_resumer.clone();
+        let cdc_resumer = cdc_resumer.clone();
+        let me = self.clone();
+        join_set.spawn(async move {
+            let res = me
+                .start_single_task(
+                    &node_extractor_config,
+                    &router,
+                    &snapshot_resumer,
+                    &cdc_resumer,
+                    syncer,
+                )
+                .await;
+            (address, res)
+        });
+        Ok(())
+    }
+
+    fn build_redis_node_extractor_config(
+        &self,
+        node_url: String,
+        node: &ClusterNode,
+        position: &Position,
+    ) -> anyhow::Result<ExtractorConfig> {
+        let (repl_port, keepalive_interval_secs, heartbeat_interval_secs, heartbeat_key) =
+            match &self.config.extractor {
+                ExtractorConfig::RedisSnapshot { repl_port, .. } => {
+                    return Ok(ExtractorConfig::RedisSnapshot {
+                        url: node_url,
+                        repl_port: *repl_port,
+                        is_cluster: true,
+                    })
+                }
+
+                ExtractorConfig::RedisCdc {
+                    repl_port,
+                    keepalive_interval_secs,
+                    heartbeat_interval_secs,
+                    heartbeat_key,
+                    ..
+                } => (
+                    *repl_port,
+                    *keepalive_interval_secs,
+                    *heartbeat_interval_secs,
+                    heartbeat_key,
+                ),
+
+                ExtractorConfig::RedisSnapshotAndCdc {
+                    repl_port,
+                    keepalive_interval_secs,
+                    heartbeat_interval_secs,
+                    heartbeat_key,
+                    ..
+                } => (
+                    *repl_port,
+                    *keepalive_interval_secs,
+                    *heartbeat_interval_secs,
+                    heartbeat_key,
+                ),
+
+                _ => bail! {Error::ConfigError("unsupported extractor config".into())},
+            };
+
+        // writes to the heartbeat key must be routed to this node, add a hash tag of its slots
+        let heartbeat_db_key = ConfigTokenParser::parse(
+            heartbeat_key,
+            &['.'],
+            &SqlUtil::get_escape_pairs(&DbType::Redis),
+        );
+        let heartbeat_key = match (heartbeat_db_key.len(), node.slots.first()) {
+            (2, Some(slot)) => format!(
+                "{}.{{{}}}{}",
+                heartbeat_db_key[0], node.slot_hash_tag_map[slot], heartbeat_db_key[1]
+            ),
+            _ => heartbeat_key.clone(),
+        };
+
+        let config = match position {
+            Position::Redis {
+                repl_id,
+                repl_offset,
+                now_db_id,
+                ..
+            } => ExtractorConfig::RedisCdc {
+                url: node_url,
+                repl_id: repl_id.clone(),
+                repl_offset: *repl_offset,
+                repl_port,
+                keepalive_interval_secs,
+                heartbeat_interval_secs,
+                heartbeat_key,
+                now_db_id: *now_db_id,
+                is_cluster: true,
+            },
+
+            // nodes without positions are fully synced, such as a new master discovered in cdc,
+            // starting from its latest offset would lose keys migrated to it before
+            _ => {
+                log_info!(
+                    "no position found for redis node: [{}], start from a full sync",
+                    node.address
+                );
+                ExtractorConfig::RedisSnapshotAndCdc {
+                    url: node_url,
+                    repl_id: String::new(),
+                    repl_port,
+                    keepalive_interval_secs,
+                    heartbeat_interval_secs,
+                    heartbeat_key,
+                    is_cluster: true,
+                }
+            }
+        };
+        Ok(config)
+    }
+
+    async fn get_redis_cluster_masters(urls: &[String]) -> anyhow::Result<Vec<ClusterNode>> {
+        // try each url in case some nodes are down
+        let mut errors = Vec::new();
+        for url in urls {
+            let masters = match RedisUtil::create_redis_conn(url).await {
+                Ok(mut conn) => RedisUtil::get_cluster_master_nodes(&mut conn),
+                Err(err) => Err(err),
+            };
+            match masters {
+                // masters without slots are failed or empty nodes
+                Ok(masters) => {
+                    return Ok(masters
+                        .into_iter()
+                        .filter(|i| !i.slots.is_empty())
+                        .collect())
+                }
+                Err(err) => errors.push(err.to_string()),
+            }
+        }
+        bail! {Error::MetadataError(format!(
+            "can not get redis cluster masters, errors: {:?}",
+            errors
+        ))}
+    }
+
     async fn start_single_task(
         self,
         extractor_config: &ExtractorConfig,
         router: &RdbRouter,
         snapshot_resumer: &SnapshotResumer,
         cdc_resumer: &CdcResumer,
+        syncer: Arc<Mutex<Syncer>>,
     ) -> anyhow::Result<()> {
         let max_bytes = self.config.pipeline.buffer_memory_mb * 1024 * 1024;
         let buffer = Arc::new(DtQueue::new(
@@ -352,10 +709,6 @@ impl TaskRunner {
         ));
 
         let shut_down = Arc::new(AtomicBool::new(false));
-        let syncer = Arc::new(Mutex::new(Syncer {
-            received_position: Position::None,
-            committed_position: Position::None,
-        }));
 
         let (extractor_data_marker, sinker_data_marker) = if let Some(data_marker_config) =
             &self.config.data_marker
@@ -376,6 +729,22 @@ impl TaskRunner {
             ExtractorConfig::MysqlSnapshot { db, tb, .. } => format!("{}.{}", db, tb),
             ExtractorConfig::PgSnapshot { schema, tb, .. } => format!("{}.{}", schema, tb),
             ExtractorConfig::MongoSnapshot { db, tb, .. } => format!("{}.{}", db, tb),
+            // a task for each master node of the redis cluster
+            ExtractorConfig::RedisSnapshot {
+                url,
+                is_cluster: true,
+                ..
+            }
+            | ExtractorConfig::RedisCdc {
+                url,
+                is_cluster: true,
+                ..
+            }
+            | ExtractorConfig::RedisSnapshotAndCdc {
+                url,
+                is_cluster: true,
+                ..
+            } => RedisUtil::get_url_address(url)?,
             _ => String::new(),
         };
 
@@ -466,9 +835,17 @@ impl TaskRunner {
         self.pre_single_task(sinker_data_marker).await?;
 
         // start threads
+        let extractor_shut_down = shut_down.clone();
         let f1 = tokio::spawn(async move {
-            extractor.extract().await.unwrap();
-            extractor.close().await.unwrap();
+            if let Err(err) = extractor.extract().await {
+                // stop the pipeline after the extracted data sinked, and return the error
+                extractor_shut_down.store(true, Ordering::Release);
+                if let Err(close_err) = extractor.close().await {
+                    log_error!("close extractor failed, error: {:?}", close_err);
+                }
+                return Err(err);
+            }
+            extractor.close().await
         });
 
         let f2 = tokio::spawn(async move {
@@ -487,7 +864,7 @@ impl TaskRunner {
             )
             .await
         });
-        try_join!(f1, f2, f3)?;
+        let (extract_result, _, _) = try_join!(f1, f2, f3)?;
 
         // finished log
         let (schema, tb) = match extractor_config {
@@ -497,7 +874,7 @@ impl TaskRunner {
             | ExtractorConfig::FoxlakeS3 { schema, tb, .. } => (schema.to_owned(), tb.to_owned()),
             _ => (String::new(), String::new()),
         };
-        if !tb.is_empty() {
+        if !tb.is_empty() && extract_result.is_ok() {
             log_finished!(
                 "{}",
                 Position::RdbSnapshotFinished {
@@ -525,7 +902,7 @@ impl TaskRunner {
             }
         );
 
-        Ok(())
+        extract_result
     }
 
     async fn create_pipeline(
@@ -558,6 +935,11 @@ impl TaskRunner {
                             lua_code: processor_config.lua_code.clone(),
                         });
 
+                let mongo_flattener =
+                    self.config.mongo_flatten.as_ref().map(|config| {
+                        MongoFlattener::new(config, &self.config.sinker_basic.db_type)
+                    });
+
                 let parallelizer = ParallelizerUtil::create_parallelizer(
                     &self.config,
                     monitor.clone(),
@@ -569,6 +951,7 @@ impl TaskRunner {
                     buffer,
                     parallelizer,
                     sinker_config: self.config.sinker.clone(),
+                    parallel_type: self.config.parallelizer.parallel_type.clone(),
                     sinkers,
                     shut_down,
                     checkpoint_interval_secs: self.config.pipeline.checkpoint_interval_secs,
@@ -577,6 +960,7 @@ impl TaskRunner {
                     monitor,
                     data_marker,
                     lua_processor,
+                    mongo_flattener,
                 };
                 Ok(Box::new(pipeline))
             }
@@ -612,7 +996,8 @@ impl TaskRunner {
 
         match &self.config.sinker {
             SinkerConfig::MysqlCheck { check_log_dir, .. }
-            | SinkerConfig::PgCheck { check_log_dir, .. } => {
+            | SinkerConfig::PgCheck { check_log_dir, .. }
+            | SinkerConfig::RedisCheck { check_log_dir, .. } => {
                 if !check_log_dir.is_empty() {
                     config_str = config_str.replace(CHECK_LOG_DIR_PLACEHODLER, check_log_dir);
                 }
@@ -712,6 +1097,67 @@ impl TaskRunner {
         }
     }
 
+    async fn create_flatten_tbs(&self) -> anyhow::Result<()> {
+        let mongo_flatten = match &self.config.mongo_flatten {
+            Some(mongo_flatten) if mongo_flatten.create_tables => mongo_flatten,
+            _ => return Ok(()),
+        };
+
+        let db_type = &self.config.sinker_basic.db_type;
+        let mongo_flattener = MongoFlattener::new(mongo_flatten, db_type);
+        for (schema, tb, schema_sql, tb_sql) in mongo_flattener.build_create_sqls()? {
+            match &self.config.sinker {
+                SinkerConfig::Mysql { url, .. } | SinkerConfig::StarRocks { url, .. } => {
+                    // StarRocks is compatible with mysql protocol
+                    TaskUtil::check_and_create_tb(
+                        url,
+                        &schema,
+                        &tb,
+                        &schema_sql,
+                        &tb_sql,
+                        &DbType::Mysql,
+                    )
+                    .await?
+                }
+
+                SinkerConfig::Pg { url, .. } => {
+                    TaskUtil::check_and_create_tb(
+                        url,
+                        &schema,
+                        &tb,
+                        &schema_sql,
+                        &tb_sql,
+                        &DbType::Pg,
+                    )
+                    .await?
+                }
+
+                SinkerConfig::ClickHouse { url, .. } => {
+                    let url_info = Url::parse(url)?;
+                    let client = clickhouse::Client::default()
+                        .with_url(format!(
+                            "http://{}:{}",
+                            url_info.host_str().unwrap(),
+                            url_info.port().unwrap()
+                        ))
+                        .with_user(url_info.username())
+                        .with_password(url_info.password().unwrap_or(""));
+                    log_info!("schema_sql: {}, tb_sql: {}", schema_sql, tb_sql);
+                    client.query(&schema_sql).execute().await?;
+                    client.query(&tb_sql).execute().await?;
+                }
+
+                _ => {
+                    bail! {Error::ConfigError(format!(
+                        "flattening mongo documents into sinker: {} is not supported",
+                        db_type
+                    ))}
+                }
+            }
+        }
+        Ok(())
+    }
+
     async fn pre_single_task(&self, sinker_data_marker: Option<DataMarker>) -> anyhow::Result<()> {
         // create heartbeat table
         let schema_tb = match &self.config.extractor {
@@ -798,6 +1244,7 @@ impl TaskRunner {
                             src_node varchar(255) NOT NULL,
                             dst_node varchar(255) NOT NULL,
                             n bigint DEFAULT NULL,
+                            data_path varchar(1024) NOT NULL DEFAULT '',
                             PRIMARY KEY (data_origin_node, src_node, dst_node)
                         )",
                         data_marker.marker_schema, data_marker.marker_tb
@@ -814,7 +1261,8 @@ impl TaskRunner {
                     .await?
                 }
 
-                SinkerConfig::Pg { url, .. } => {
+                // replication origins are created by PgSinker on demand
+                SinkerConfig::Pg { url, .. } if !data_marker.use_replication_origin() => {
                     let schema_sql = format!(
                         r#"CREATE SCHEMA IF NOT EXISTS "{}""#,
                         data_marker.marker_schema
@@ -825,6 +1273,7 @@ impl TaskRunner {
                             src_node varchar(255) NOT NULL,
                             dst_node varchar(255) NOT NULL,
                             n bigint DEFAULT NULL,
+                            data_path varchar(1024) NOT NULL DEFAULT '',
                             PRIMARY KEY (data_origin_node, src_node, dst_node)
                         )"#,
                         data_marker.marker_schema, data_marker.marker_tb
@@ -847,3 +1296,34 @@ impl TaskRunner {
         Ok(())
     }
 }
+
+struct RedisNodeTask {
+    node: ClusterNode,
+    // the position to start from
+    position: Position,
+    syncer: Arc<Mutex<Syncer>>,
+    // times of continuous failures without any progress
+    failed_times: usize,
+}
+
+impl RedisNodeTask {
+    fn new(node: ClusterNode, position: Position, failed_times: usize) -> Self {
+        Self {
+            node,
+            position,
+            syncer: Arc::new(Mutex::new(Syncer::default())),
+            failed_times,
+        }
+    }
+
+    async fn into_failed(mut self) -> Self {
+        let committed_position = self.syncer.lock().await.committed_position.clone();
+        if committed_position == Position::None || committed_position == self.position {
+            self.failed_times += 1;
+        } else {
+            self.position = committed_position;
+            self.failed_times = 1;
+        }
+        self
+    }
+}
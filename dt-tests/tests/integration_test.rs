@@ -1,5 +1,6 @@
 mod log_reader;
 mod mongo_to_mongo;
+mod mongo_to_mysql;
 mod mysql_to_clickhouse;
 mod mysql_to_doris;
 mod mysql_to_foxlake;
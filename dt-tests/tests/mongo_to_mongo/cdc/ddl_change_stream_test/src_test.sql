@@ -0,0 +1,29 @@
+use test_db_1
+
+-- create
+db.createCollection("tb_2");
+db.createCollection("tb_3");
+db.tb_2.insertOne({ "name": "a", "age": "1" });
+db.tb_3.insertOne({ "name": "a", "age": "1" });
+
+-- create index
+db.tb_1.createIndex({ "name": 1 });
+db.tb_1.createIndex({ "name": 1, "age": -1 }, { "name": "idx_name_age", "unique": true });
+db.tb_1.insertOne({ "name": "a", "age": "1" });
+
+-- drop index
+db.tb_1.dropIndex("name_1");
+
+-- rename
+db.tb_2.renameCollection("tb_2_new");
+db.tb_2_new.insertOne({ "name": "b", "age": "2" });
+
+-- drop
+db.tb_3.drop();
+
+use test_db_2
+
+-- drop database
+db.createCollection("tb_1");
+db.tb_1.insertOne({ "name": "a", "age": "1" });
+db.dropDatabase();
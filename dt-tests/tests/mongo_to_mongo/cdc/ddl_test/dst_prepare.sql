@@ -0,0 +1,9 @@
+use test_db_1
+
+db.dropDatabase();
+
+db.createCollection("tb_1");
+
+use test_db_2
+
+db.dropDatabase();
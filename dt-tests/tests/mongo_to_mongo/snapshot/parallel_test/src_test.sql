@@ -0,0 +1,87 @@
+use test_db_1
+
+-- ObjectId ids
+db.tb_1.insertOne({ "name": "a_0", "age": 0 });
+db.tb_1.insertOne({ "name": "a_1", "age": 1 });
+db.tb_1.insertOne({ "name": "a_2", "age": 2 });
+db.tb_1.insertOne({ "name": "a_3", "age": 3 });
+db.tb_1.insertOne({ "name": "a_4", "age": 4 });
+db.tb_1.insertOne({ "name": "a_5", "age": 5 });
+db.tb_1.insertOne({ "name": "a_6", "age": 6 });
+db.tb_1.insertOne({ "name": "a_7", "age": 7 });
+db.tb_1.insertOne({ "name": "a_8", "age": 8 });
+db.tb_1.insertOne({ "name": "a_9", "age": 9 });
+db.tb_1.insertOne({ "name": "a_10", "age": 10 });
+db.tb_1.insertOne({ "name": "a_11", "age": 11 });
+db.tb_1.insertOne({ "name": "a_12", "age": 12 });
+db.tb_1.insertOne({ "name": "a_13", "age": 13 });
+db.tb_1.insertOne({ "name": "a_14", "age": 14 });
+db.tb_1.insertOne({ "name": "a_15", "age": 15 });
+db.tb_1.insertOne({ "name": "a_16", "age": 16 });
+db.tb_1.insertOne({ "name": "a_17", "age": 17 });
+db.tb_1.insertOne({ "name": "a_18", "age": 18 });
+db.tb_1.insertOne({ "name": "a_19", "age": 19 });
+
+-- mixed type ids
+db.tb_2.insertOne({ "_id": 0, "name": "a_0" });
+db.tb_2.insertOne({ "_id": NumberLong(100), "name": "b_0" });
+db.tb_2.insertOne({ "_id": "id_0", "name": "c_0" });
+db.tb_2.insertOne({ "_id": ObjectId(), "name": "d_0" });
+db.tb_2.insertOne({ "_id": { "k": 0 }, "name": "e_0" });
+db.tb_2.insertOne({ "_id": ISODate("2024-01-01T00:00:00Z"), "name": "f_0" });
+db.tb_2.insertOne({ "_id": 1, "name": "a_1" });
+db.tb_2.insertOne({ "_id": NumberLong(101), "name": "b_1" });
+db.tb_2.insertOne({ "_id": "id_1", "name": "c_1" });
+db.tb_2.insertOne({ "_id": ObjectId(), "name": "d_1" });
+db.tb_2.insertOne({ "_id": { "k": 1 }, "name": "e_1" });
+db.tb_2.insertOne({ "_id": ISODate("2024-01-02T00:00:00Z"), "name": "f_1" });
+db.tb_2.insertOne({ "_id": 2, "name": "a_2" });
+db.tb_2.insertOne({ "_id": NumberLong(102), "name": "b_2" });
+db.tb_2.insertOne({ "_id": "id_2", "name": "c_2" });
+db.tb_2.insertOne({ "_id": ObjectId(), "name": "d_2" });
+db.tb_2.insertOne({ "_id": { "k": 2 }, "name": "e_2" });
+db.tb_2.insertOne({ "_id": ISODate("2024-01-03T00:00:00Z"), "name": "f_2" });
+db.tb_2.insertOne({ "_id": 3, "name": "a_3" });
+db.tb_2.insertOne({ "_id": NumberLong(103), "name": "b_3" });
+db.tb_2.insertOne({ "_id": "id_3", "name": "c_3" });
+db.tb_2.insertOne({ "_id": ObjectId(), "name": "d_3" });
+db.tb_2.insertOne({ "_id": { "k": 3 }, "name": "e_3" });
+db.tb_2.insertOne({ "_id": ISODate("2024-01-04T00:00:00Z"), "name": "f_3" });
+db.tb_2.insertOne({ "_id": 4, "name": "a_4" });
+db.tb_2.insertOne({ "_id": NumberLong(104), "name": "b_4" });
+db.tb_2.insertOne({ "_id": "id_4", "name": "c_4" });
+db.tb_2.insertOne({ "_id": ObjectId(), "name": "d_4" });
+db.tb_2.insertOne({ "_id": { "k": 4 }, "name": "e_4" });
+db.tb_2.insertOne({ "_id": ISODate("2024-01-05T00:00:00Z"), "name": "f_4" });
+db.tb_2.insertOne({ "_id": 5, "name": "a_5" });
+db.tb_2.insertOne({ "_id": NumberLong(105), "name": "b_5" });
+db.tb_2.insertOne({ "_id": "id_5", "name": "c_5" });
+db.tb_2.insertOne({ "_id": ObjectId(), "name": "d_5" });
+db.tb_2.insertOne({ "_id": { "k": 5 }, "name": "e_5" });
+db.tb_2.insertOne({ "_id": ISODate("2024-01-06T00:00:00Z"), "name": "f_5" });
+db.tb_2.insertOne({ "_id": 6, "name": "a_6" });
+db.tb_2.insertOne({ "_id": NumberLong(106), "name": "b_6" });
+db.tb_2.insertOne({ "_id": "id_6", "name": "c_6" });
+db.tb_2.insertOne({ "_id": ObjectId(), "name": "d_6" });
+db.tb_2.insertOne({ "_id": { "k": 6 }, "name": "e_6" });
+db.tb_2.insertOne({ "_id": ISODate("2024-01-07T00:00:00Z"), "name": "f_6" });
+db.tb_2.insertOne({ "_id": 7, "name": "a_7" });
+db.tb_2.insertOne({ "_id": NumberLong(107), "name": "b_7" });
+db.tb_2.insertOne({ "_id": "id_7", "name": "c_7" });
+db.tb_2.insertOne({ "_id": ObjectId(), "name": "d_7" });
+db.tb_2.insertOne({ "_id": { "k": 7 }, "name": "e_7" });
+db.tb_2.insertOne({ "_id": ISODate("2024-01-08T00:00:00Z"), "name": "f_7" });
+db.tb_2.insertOne({ "_id": 8, "name": "a_8" });
+db.tb_2.insertOne({ "_id": NumberLong(108), "name": "b_8" });
+db.tb_2.insertOne({ "_id": "id_8", "name": "c_8" });
+db.tb_2.insertOne({ "_id": ObjectId(), "name": "d_8" });
+db.tb_2.insertOne({ "_id": { "k": 8 }, "name": "e_8" });
+db.tb_2.insertOne({ "_id": ISODate("2024-01-09T00:00:00Z"), "name": "f_8" });
+db.tb_2.insertOne({ "_id": 9, "name": "a_9" });
+db.tb_2.insertOne({ "_id": NumberLong(109), "name": "b_9" });
+db.tb_2.insertOne({ "_id": "id_9", "name": "c_9" });
+db.tb_2.insertOne({ "_id": ObjectId(), "name": "d_9" });
+db.tb_2.insertOne({ "_id": { "k": 9 }, "name": "e_9" });
+db.tb_2.insertOne({ "_id": ISODate("2024-01-10T00:00:00Z"), "name": "f_9" });
+db.tb_2.insertOne({ "_id": null, "name": "g" });
+db.tb_2.insertOne({ "_id": true, "name": "h" });
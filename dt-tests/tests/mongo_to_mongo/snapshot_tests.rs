@@ -18,6 +18,12 @@ mod test {
         TestBase::run_mongo_snapshot_test("mongo_to_mongo/snapshot/route_test").await;
     }
 
+    #[tokio::test]
+    #[serial]
+    async fn snapshot_parallel_test() {
+        TestBase::run_mongo_snapshot_test("mongo_to_mongo/snapshot/parallel_test").await;
+    }
+
     // #[tokio::test]
     #[serial]
     async fn snapshot_resume_test() {
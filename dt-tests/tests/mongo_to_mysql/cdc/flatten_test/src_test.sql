@@ -0,0 +1,12 @@
+use test_db_1
+
+-- insert
+db.orders.insertOne({ "_id": "o1", "user": "a", "city": "x", "items": [{ "sku": "a1", "qty": 1 }, { "sku": "a2", "qty": 2 }] });
+db.orders.insertOne({ "_id": "o2", "user": "b", "city": "y", "items": [{ "sku": "b1", "qty": 1 }, { "sku": "b2", "qty": 2 }] });
+db.orders.insertOne({ "_id": "o3", "user": "c", "city": "z", "items": [{ "sku": "c1", "qty": 1 }] });
+
+-- update, children of o1 are deleted and rebuilt from the new array
+db.orders.updateOne({ "_id": "o1" }, { "$set": { "items": [{ "sku": "a3" }] } });
+
+-- delete, children of o3 are deleted with the document
+db.orders.deleteOne({ "_id": "o3" });
@@ -0,0 +1,24 @@
+#[cfg(test)]
+mod test {
+    use std::collections::HashMap;
+
+    use serial_test::serial;
+
+    use crate::test_runner::test_base::TestBase;
+
+    #[tokio::test]
+    #[serial]
+    async fn cdc_flatten_test() {
+        let mut dst_expected_counts = HashMap::new();
+        dst_expected_counts.insert(("test_db_1", "orders"), 2);
+        dst_expected_counts.insert(("test_db_1", "orders_items"), 3);
+
+        TestBase::run_mongo_to_rdb_cdc_test_and_check_dst_count(
+            "mongo_to_mysql/cdc/flatten_test",
+            3000,
+            5000,
+            dst_expected_counts,
+        )
+        .await;
+    }
+}
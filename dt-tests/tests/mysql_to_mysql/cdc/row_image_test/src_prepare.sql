@@ -0,0 +1,7 @@
+DROP DATABASE IF EXISTS test_db_1;
+
+CREATE DATABASE test_db_1;
+
+CREATE TABLE test_db_1.tb_1(id INT AUTO_INCREMENT, f_1 INT DEFAULT 10, f_2 VARCHAR(255) DEFAULT 'abc', f_3 BLOB, f_4 TEXT, f_5 JSON, PRIMARY KEY(id));
+
+CREATE TABLE test_db_1.tb_2(id INT, uk INT, f_1 INT, f_2 JSON, PRIMARY KEY(id), UNIQUE KEY(uk));
@@ -0,0 +1,42 @@
+-- minimal row image
+SET SESSION binlog_row_image = 'MINIMAL';
+
+INSERT INTO test_db_1.tb_1(id, f_1, f_3, f_4, f_5) VALUES(1, 1, 'blob_1', 'text_1', '{"a":1,"b":[1,2,3],"c":{"d":"e"}}');
+
+INSERT INTO test_db_1.tb_1(f_2) VALUES('only f_2');
+
+INSERT INTO test_db_1.tb_1(id) VALUES(3);
+
+INSERT INTO test_db_1.tb_2 VALUES(1, 1, 1, '{"a":1}'), (2, 2, 2, '[1,2,3]');
+
+UPDATE test_db_1.tb_1 SET f_1 = 100 WHERE id = 1;
+
+UPDATE test_db_1.tb_1 SET f_2 = NULL, f_4 = 'text_2' WHERE id = 2;
+
+UPDATE test_db_1.tb_1 SET id = 4 WHERE id = 3;
+
+UPDATE test_db_1.tb_2 SET uk = 3 WHERE id = 2;
+
+-- json functions
+UPDATE test_db_1.tb_1 SET f_5 = JSON_SET(f_5, '$.a', 2, '$.x', 'new', '$.c.d', JSON_OBJECT('k', 'v''s')) WHERE id = 1;
+
+UPDATE test_db_1.tb_1 SET f_5 = JSON_REPLACE(f_5, '$.b[1]', 20) WHERE id = 1;
+
+UPDATE test_db_1.tb_1 SET f_5 = JSON_REMOVE(f_5, '$.b[0]', '$.x') WHERE id = 1;
+
+UPDATE test_db_1.tb_2 SET f_2 = JSON_SET(f_2, '$.b', 'a\\b"c'), f_1 = 10 WHERE id = 1;
+
+UPDATE test_db_1.tb_2 SET f_2 = JSON_SET(f_2, '$[3]', 4) WHERE id = 2;
+
+-- noblob row image
+SET SESSION binlog_row_image = 'NOBLOB';
+
+UPDATE test_db_1.tb_1 SET f_1 = 200 WHERE id = 1;
+
+UPDATE test_db_1.tb_1 SET f_3 = 'blob_2' WHERE id = 2;
+
+DELETE FROM test_db_1.tb_1 WHERE id = 4;
+
+DELETE FROM test_db_1.tb_2 WHERE id = 2;
+
+SET SESSION binlog_row_image = 'FULL';
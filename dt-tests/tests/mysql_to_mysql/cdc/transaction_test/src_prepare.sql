@@ -0,0 +1,9 @@
+DROP DATABASE IF EXISTS test_db_1;
+
+CREATE DATABASE test_db_1;
+
+CREATE TABLE test_db_1.tb_1 ( f_0 int, f_1 int DEFAULT NULL, f_2 varchar(255) DEFAULT NULL, PRIMARY KEY (f_0), UNIQUE KEY uk_1 (f_1) ) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4;
+
+CREATE TABLE test_db_1.tb_2 ( f_0 int, f_1 int DEFAULT NULL, PRIMARY KEY (f_0) ) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4;
+
+CREATE TABLE test_db_1.no_pk ( f_0 int, f_1 int DEFAULT NULL ) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4;
@@ -11,6 +11,12 @@ mod test {
         TestBase::run_cdc_test("mysql_to_mysql/cdc/basic_test", 3000, 4000).await;
     }
 
+    #[tokio::test]
+    #[serial]
+    async fn cdc_transaction_test() {
+        TestBase::run_cdc_test("mysql_to_mysql/cdc/transaction_test", 3000, 3000).await;
+    }
+
     #[tokio::test]
     #[serial]
     async fn cdc_uk_changed_test() {
@@ -29,6 +35,12 @@ mod test {
         TestBase::run_cdc_test("mysql_to_mysql/cdc/json_test", 3000, 2000).await;
     }
 
+    #[tokio::test]
+    #[serial]
+    async fn cdc_row_image_test() {
+        TestBase::run_cdc_test("mysql_to_mysql/cdc/row_image_test", 3000, 2000).await;
+    }
+
     #[tokio::test]
     #[serial]
     async fn cdc_ddl_test() {
@@ -109,6 +121,28 @@ mod test {
         .await;
     }
 
+    /// ring: node1 -> node2 -> node3 -> node1, filtered by data path instead of do_nodes/ignore_nodes
+    #[tokio::test]
+    #[serial]
+    async fn cycle_ring_test() {
+        let tx_check_data = vec![
+            ("node1", "node2", "node1", "10"),
+            ("node1", "node2", "node3", "10"),
+            ("node2", "node3", "node2", "10"),
+            ("node2", "node3", "node1", "10"),
+            ("node3", "node1", "node3", "10"),
+            ("node3", "node1", "node2", "10"),
+        ];
+
+        RdbCycleTestRunner::run_cycle_cdc_test(
+            "mysql_to_mysql/cdc/cycle_ring_test",
+            2000,
+            4000,
+            &tx_check_data,
+        )
+        .await;
+    }
+
     #[tokio::test]
     #[serial]
     async fn cycle_net_test() {
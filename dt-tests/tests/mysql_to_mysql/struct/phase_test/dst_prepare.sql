@@ -0,0 +1,3 @@
+drop database if exists struct_it_mysql2mysql_1;
+
+-- create database if not exists struct_it_mysql2mysql_1;
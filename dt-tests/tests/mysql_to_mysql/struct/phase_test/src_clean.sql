@@ -0,0 +1 @@
+-- drop database if exists struct_it_mysql2mysql_1;
\ No newline at end of file
@@ -11,6 +11,13 @@ mod test {
         TestBase::run_mysql_struct_test("mysql_to_mysql/struct/basic_test").await;
     }
 
+    /// pre_data by struct_task_config.ini, then post_data by task_config.ini
+    #[tokio::test]
+    #[serial]
+    async fn struct_phase_test() {
+        TestBase::run_mysql_struct_test("mysql_to_mysql/struct/phase_test").await;
+    }
+
     // TODO: fix this test: index migration is not configured,
     //       and the target UK order may be inconsistent which cannot be verified through check
     // /// do_structures=database,table
@@ -0,0 +1,10 @@
+DROP SCHEMA IF EXISTS test_db_1 CASCADE;
+
+CREATE SCHEMA test_db_1;
+
+CREATE TABLE test_db_1.tb_1 ( f_0 int, f_1 int DEFAULT NULL, f_2 varchar(255) DEFAULT NULL, PRIMARY KEY (f_0) );
+
+-- make large transactions streamed before commit
+ALTER SYSTEM SET logical_decoding_work_mem = '64kB';
+
+SELECT pg_reload_conf();
@@ -0,0 +1,11 @@
+-- large transactions, streamed in several blocks and spilled to disk
+INSERT INTO test_db_1.tb_1 SELECT i, i, repeat('a', 200) FROM generate_series(1, 20000) AS i;
+
+UPDATE test_db_1.tb_1 SET f_1 = f_1 + 1, f_2 = repeat('b', 200) WHERE f_0 <= 10000;
+
+DELETE FROM test_db_1.tb_1 WHERE f_0 > 15000;
+
+-- small transactions, not streamed
+INSERT INTO test_db_1.tb_1 VALUES (30000, 1, 'c');
+
+UPDATE test_db_1.tb_1 SET f_2 = 'd' WHERE f_0 = 30000;
@@ -0,0 +1,11 @@
+DROP SCHEMA IF EXISTS test_db_1 CASCADE;
+
+CREATE SCHEMA test_db_1;
+
+CREATE TABLE test_db_1.tb_1 ( f_0 int, f_1 int DEFAULT NULL, f_2 varchar(255) DEFAULT NULL, PRIMARY KEY (f_0), UNIQUE (f_1) );
+
+CREATE TABLE test_db_1.tb_2 ( f_0 int, f_1 int DEFAULT NULL, PRIMARY KEY (f_0) );
+
+CREATE TABLE test_db_1.no_pk ( f_0 int, f_1 int DEFAULT NULL );
+
+ALTER TABLE test_db_1.no_pk REPLICA IDENTITY FULL;
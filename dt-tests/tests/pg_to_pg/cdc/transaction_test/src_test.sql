@@ -0,0 +1,25 @@
+-- each statement below is a source transaction with multiple rows
+
+-- non-conflicting transactions
+INSERT INTO test_db_1.tb_1 VALUES (1, 1, 'a'), (2, 2, 'b');
+INSERT INTO test_db_1.tb_2 VALUES (1, 1), (2, 2);
+INSERT INTO test_db_1.tb_1 VALUES (3, 3, 'c'), (4, 4, 'd');
+INSERT INTO test_db_1.no_pk VALUES (1, 1), (2, 2);
+
+-- transactions conflicting by pk
+UPDATE test_db_1.tb_1 SET f_2 = 'aa' WHERE f_0 IN (1, 3);
+UPDATE test_db_1.tb_1 SET f_2 = 'bb' WHERE f_0 IN (1, 2);
+DELETE FROM test_db_1.tb_2 WHERE f_0 IN (1, 2);
+INSERT INTO test_db_1.tb_2 VALUES (1, 10), (2, 20);
+
+-- transactions conflicting by uk
+UPDATE test_db_1.tb_1 SET f_1 = f_1 + 10 WHERE f_0 IN (1, 2);
+UPDATE test_db_1.tb_1 SET f_1 = 2 WHERE f_0 = 1;
+UPDATE test_db_1.tb_1 SET f_1 = 1 WHERE f_0 = 2;
+UPDATE test_db_1.tb_1 SET f_1 = 5 WHERE f_0 = 4;
+UPDATE test_db_1.tb_1 SET f_1 = 4 WHERE f_0 = 3;
+
+-- transactions on table without pk
+UPDATE test_db_1.no_pk SET f_1 = f_1 + 10;
+DELETE FROM test_db_1.no_pk WHERE f_0 = 1;
+INSERT INTO test_db_1.no_pk VALUES (3, 3), (4, 4);
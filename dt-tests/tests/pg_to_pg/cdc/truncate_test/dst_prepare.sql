@@ -0,0 +1,25 @@
+DROP SCHEMA IF EXISTS test_db_1 CASCADE;
+
+CREATE SCHEMA test_db_1;
+
+CREATE TABLE test_db_1.tb_1 ( f_0 int, f_1 int DEFAULT NULL, PRIMARY KEY (f_0) );
+INSERT INTO test_db_1.tb_1 VALUES (1, 1), (2, 2);
+
+CREATE TABLE test_db_1.tb_2 ( f_0 int, f_1 int DEFAULT NULL, PRIMARY KEY (f_0) );
+INSERT INTO test_db_1.tb_2 VALUES (1, 1), (2, 2);
+
+CREATE TABLE test_db_1.tb_3 ( f_0 int, f_1 int DEFAULT NULL, PRIMARY KEY (f_0) );
+INSERT INTO test_db_1.tb_3 VALUES (1, 1), (2, 2);
+
+CREATE TABLE test_db_1.parent_tb ( f_0 int, f_1 int DEFAULT NULL, PRIMARY KEY (f_0) );
+INSERT INTO test_db_1.parent_tb VALUES (1, 1), (2, 2);
+
+CREATE TABLE test_db_1.child_tb ( f_0 int, f_1 int DEFAULT NULL, PRIMARY KEY (f_0), FOREIGN KEY (f_1) REFERENCES test_db_1.parent_tb (f_0) );
+INSERT INTO test_db_1.child_tb VALUES (1, 1), (2, 2);
+
+CREATE TABLE test_db_1.serial_tb ( f_0 serial, f_1 int DEFAULT NULL, PRIMARY KEY (f_0) );
+INSERT INTO test_db_1.serial_tb (f_1) VALUES (1), (2);
+
+-- truncates on filtered tables are not synced
+CREATE TABLE test_db_1.ignored_tb ( f_0 int, f_1 int DEFAULT NULL, PRIMARY KEY (f_0) );
+INSERT INTO test_db_1.ignored_tb VALUES (1, 1), (2, 2);
@@ -0,0 +1,15 @@
+-- single table
+TRUNCATE test_db_1.tb_1;
+
+-- multiple tables
+TRUNCATE TABLE test_db_1.tb_2, test_db_1.tb_3;
+
+-- cascade
+TRUNCATE TABLE test_db_1.parent_tb CASCADE;
+
+-- restart identity, rows inserted after truncate should get the same ids in target
+TRUNCATE TABLE test_db_1.serial_tb RESTART IDENTITY;
+INSERT INTO test_db_1.serial_tb (f_1) VALUES (3), (4);
+
+-- filtered
+TRUNCATE TABLE test_db_1.ignored_tb;
@@ -11,6 +11,12 @@ mod test {
         TestBase::run_cdc_test("pg_to_pg/cdc/basic_test", 3000, 9000).await;
     }
 
+    #[tokio::test]
+    #[serial]
+    async fn cdc_transaction_test() {
+        TestBase::run_cdc_test("pg_to_pg/cdc/transaction_test", 3000, 3000).await;
+    }
+
     #[tokio::test]
     #[serial]
     async fn cdc_postgis_test() {
@@ -65,12 +71,30 @@ mod test {
         TestBase::run_cdc_test("pg_to_pg/cdc/foreign_key_test", 3000, 4000).await;
     }
 
+    #[tokio::test]
+    #[serial]
+    async fn cdc_stream_test() {
+        TestBase::run_cdc_test("pg_to_pg/cdc/stream_test", 3000, 10000).await;
+    }
+
     #[tokio::test]
     #[serial]
     async fn cdc_ddl_test() {
         TestBase::run_ddl_test("pg_to_pg/cdc/ddl_test", 3000, 5000).await;
     }
 
+    #[tokio::test]
+    #[serial]
+    async fn cdc_ddl_message_test() {
+        TestBase::run_ddl_test("pg_to_pg/cdc/ddl_message_test", 3000, 5000).await;
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn cdc_truncate_test() {
+        TestBase::run_ddl_test("pg_to_pg/cdc/truncate_test", 3000, 5000).await;
+    }
+
     #[tokio::test]
     #[serial]
     async fn cycle_basic_test() {
@@ -88,6 +112,18 @@ mod test {
         .await;
     }
 
+    #[tokio::test]
+    #[serial]
+    async fn cycle_origin_test() {
+        RdbCycleTestRunner::run_cycle_cdc_test(
+            "pg_to_pg/cdc/cycle_origin_test",
+            2000,
+            4000,
+            &vec![],
+        )
+        .await;
+    }
+
     #[tokio::test]
     #[serial]
     async fn cycle_star_test() {
@@ -109,6 +145,50 @@ mod test {
         .await;
     }
 
+    /// ring: node1 -> node2 -> node3 -> node1, filtered by data path instead of do_nodes/ignore_nodes
+    #[tokio::test]
+    #[serial]
+    async fn cycle_ring_test() {
+        let tx_check_data = vec![
+            ("node1", "node2", "node1", "10"),
+            ("node1", "node2", "node3", "10"),
+            ("node2", "node3", "node2", "10"),
+            ("node2", "node3", "node1", "10"),
+            ("node3", "node1", "node3", "10"),
+            ("node3", "node1", "node2", "10"),
+        ];
+
+        RdbCycleTestRunner::run_cycle_cdc_test(
+            "pg_to_pg/cdc/cycle_ring_test",
+            2000,
+            4000,
+            &tx_check_data,
+        )
+        .await;
+    }
+
+    /// same as cycle_star_test, filtered by data path instead of do_nodes/ignore_nodes
+    #[tokio::test]
+    #[serial]
+    async fn cycle_star_path_test() {
+        let tx_check_data = vec![
+            ("node1", "node2", "node1", "10"),
+            ("node1", "node2", "node3", "10"),
+            ("node1", "node3", "node1", "10"),
+            ("node1", "node3", "node2", "10"),
+            ("node2", "node1", "node2", "10"),
+            ("node3", "node1", "node3", "10"),
+        ];
+
+        RdbCycleTestRunner::run_cycle_cdc_test(
+            "pg_to_pg/cdc/cycle_star_path_test",
+            2000,
+            4000,
+            &tx_check_data,
+        )
+        .await;
+    }
+
     #[tokio::test]
     #[serial]
     async fn cycle_net_test() {
@@ -0,0 +1,3 @@
+drop schema if exists struct_it_pg2pg_1 CASCADE;
+
+-- create schema struct_it_pg2pg_1;
@@ -0,0 +1 @@
+-- drop schema if exists struct_it_pg2pg_1 CASCADE;
\ No newline at end of file
@@ -11,6 +11,13 @@ mod test {
         TestBase::run_pg_struct_test("pg_to_pg/struct/basic_test").await;
     }
 
+    /// pre_data by struct_task_config.ini, then post_data by task_config.ini
+    #[tokio::test]
+    #[serial]
+    async fn struct_phase_test() {
+        TestBase::run_pg_struct_test("pg_to_pg/struct/phase_test").await;
+    }
+
     /// do_structures=database,table
     #[tokio::test]
     #[serial]
@@ -0,0 +1,15 @@
+flushall
+
+SET str_same val_1
+SET str_diff val_2
+RPUSH list_same a b c
+RPUSH list_diff a c b
+SADD set_same c b a
+SADD set_diff a b
+HSET hash_same f_1 v_1 f_2 v_2
+HSET hash_diff f_1 v_1 f_2 v_3
+ZADD zset_same 1 m_1 2 m_2
+ZADD zset_diff 1 m_1 3 m_2
+XADD stream_same 1-1 f_1 v_1
+XADD stream_diff 1-1 f_1 v_2
+RPUSH type_diff val_1
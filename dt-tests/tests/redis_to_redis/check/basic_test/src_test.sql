@@ -0,0 +1,27 @@
+-------------------- string entries
+SET str_same val_1
+SET str_diff val_1
+SET str_miss val_1
+
+-------------------- list entries
+RPUSH list_same a b c
+RPUSH list_diff a b c
+
+-------------------- set entries
+SADD set_same a b c
+SADD set_diff a b c
+
+-------------------- hash entries
+HSET hash_same f_1 v_1 f_2 v_2
+HSET hash_diff f_1 v_1 f_2 v_2
+
+-------------------- zset entries
+ZADD zset_same 1 m_1 2 m_2
+ZADD zset_diff 1 m_1 2 m_2
+
+-------------------- stream entries
+XADD stream_same 1-1 f_1 v_1
+XADD stream_diff 1-1 f_1 v_1
+
+-------------------- type diff
+SET type_diff val_1
@@ -0,0 +1,12 @@
+#[cfg(test)]
+mod test {
+
+    use crate::test_runner::test_base::TestBase;
+    use serial_test::serial;
+
+    #[tokio::test]
+    #[serial]
+    async fn check_basic_test() {
+        TestBase::run_redis_check_test("redis_to_redis/check/basic_test").await;
+    }
+}
@@ -0,0 +1,19 @@
+-------------------- keys hashed to different nodes
+SET key_1 val_1
+SET key_2 val_2
+SET key_3 val_3
+SET key_4 val_4
+SET key_5 val_5
+SET key_6 val_6
+
+HSET hset_key_1 field_1 val_1 field_2 val_2
+RPUSH list_key_1 val_1 val_2 val_3
+SADD set_key_1 val_1 val_2 val_3
+ZADD zset_key_1 1 val_1 2 val_2
+XADD stream_key_1 * field_1 val_1
+
+-------------------- keys with hash tags, in the same node
+MSET {tag}key_1 val_1 {tag}key_2 val_2
+
+-------------------- expire
+SET expire_key_1 val_1 EX 100000
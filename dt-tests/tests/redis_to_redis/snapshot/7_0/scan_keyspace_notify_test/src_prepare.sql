@@ -0,0 +1,14 @@
+flushall
+CONFIG SET notify-keyspace-events EA
+
+-------------------- scanned entries
+SET set_key_1 val_1
+SET set_key_2 val_2
+SET set_key_3 val_3
+HSET hset_key_1 field_1 val_1 field_2 val_2
+RPUSH list_key_1 val_1 val_2
+SADD sadd_key_1 val_1 val_2
+ZADD zadd_key_1 1 val_1 2 val_2
+
+SELECT 1
+SET set_key_1 val_1
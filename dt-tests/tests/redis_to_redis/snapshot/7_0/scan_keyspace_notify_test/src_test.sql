@@ -0,0 +1,33 @@
+-------------------- changes synced by keyspace notifications
+SELECT 0
+
+-- add
+SET set_key_4 val_4
+HSET hset_key_2 field_1 val_1
+RPUSH list_key_2 val_1
+
+-- update
+SET set_key_1 val_1_new
+APPEND set_key_2 _appended
+HSET hset_key_1 field_1 val_1_new field_3 val_3
+HDEL hset_key_1 field_2
+LPUSH list_key_1 val_0
+RPOP list_key_1
+SREM sadd_key_1 val_1
+ZINCRBY zadd_key_1 10 val_1
+
+-- the same key changed many times
+INCR incr_key_1
+INCR incr_key_1
+INCR incr_key_1
+
+-- ttl
+EXPIRE set_key_4 3600
+
+-- delete
+DEL set_key_3
+RENAME list_key_2 list_key_3
+
+SELECT 1
+SET set_key_1 val_1_new
+SET set_key_2 val_2
@@ -77,6 +77,31 @@ mod test {
         TestBase::run_redis_snapshot_test("redis_to_redis/snapshot/7_0/rewrite_stream_test").await;
     }
 
+    // test extracting keys by SCAN + DUMP instead of psync
+    #[tokio::test]
+    #[serial]
+    async fn snapshot_scan_test() {
+        TestBase::run_redis_snapshot_test("redis_to_redis/snapshot/7_0/scan_test").await;
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn snapshot_scan_rewrite_test() {
+        TestBase::run_redis_snapshot_test("redis_to_redis/snapshot/7_0/scan_rewrite_test").await;
+    }
+
+    // the task keeps running after scan to sync changes by keyspace notifications
+    #[tokio::test]
+    #[serial]
+    async fn snapshot_scan_keyspace_notify_test() {
+        TestBase::run_redis_cdc_test(
+            "redis_to_redis/snapshot/7_0/scan_keyspace_notify_test",
+            3000,
+            3000,
+        )
+        .await;
+    }
+
     #[tokio::test]
     #[serial]
     async fn snapshot_filter_db_test() {
@@ -112,4 +137,10 @@ mod test {
     async fn snapshot_to_cluster_test() {
         TestBase::run_redis_snapshot_test("redis_to_redis/snapshot/7_0/to_cluster_test").await;
     }
+
+    #[tokio::test]
+    #[serial]
+    async fn snapshot_from_cluster_test() {
+        TestBase::run_redis_snapshot_test("redis_to_redis/snapshot/7_0/from_cluster_test").await;
+    }
 }
@@ -0,0 +1,8 @@
+flushall
+
+SET snapshot_key_1 val_1
+SET snapshot_key_2 val_2
+SET snapshot_key_3 val_3
+SET snapshot_key_4 val_4
+HSET snapshot_hset_key_1 field_1 val_1
+RPUSH snapshot_list_key_1 val_1 val_2
@@ -0,0 +1,16 @@
+-------------------- keys hashed to different nodes
+SET cdc_key_1 val_1
+SET cdc_key_2 val_2
+SET cdc_key_3 val_3
+SET cdc_key_4 val_4
+HSET cdc_hset_key_1 field_1 val_1 field_2 val_2
+SADD cdc_set_key_1 val_1 val_2
+ZADD cdc_zset_key_1 1 val_1 2 val_2
+
+-------------------- updates of snapshot keys
+SET snapshot_key_1 val_1_1
+DEL snapshot_key_2
+RPUSH snapshot_list_key_1 val_3
+
+-------------------- keys with hash tags, in the same node
+MSET {tag}cdc_key_1 val_1 {tag}cdc_key_2 val_2
@@ -10,4 +10,15 @@ mod test {
         TestBase::run_redis_cdc_test("redis_to_redis/snapshot_and_cdc/7_0/basic_test", 2000, 3000)
             .await;
     }
+
+    #[tokio::test]
+    #[serial]
+    async fn snapshot_and_cdc_from_cluster_test() {
+        TestBase::run_redis_cdc_test(
+            "redis_to_redis/snapshot_and_cdc/7_0/from_cluster_test",
+            2000,
+            3000,
+        )
+        .await;
+    }
 }
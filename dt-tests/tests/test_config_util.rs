@@ -15,6 +15,7 @@ const EXTRACTOR: &str = "extractor";
 const SINKER: &str = "sinker";
 const RUNTIME: &str = "runtime";
 const RESUMER: &str = "resumer";
+const MONGO_FLATTEN: &str = "mongo_flatten";
 const TEST_PROJECT: &str = "dt-tests";
 
 #[allow(dead_code)]
@@ -92,9 +93,29 @@ impl TestConfigUtil {
         dst_task_config_file: &str,
         project_root: &str,
     ) {
-        let config = TaskConfig::new(src_task_config_file).unwrap();
+        let mut src_task_config_file = src_task_config_file;
         let mut update_configs = Vec::new();
 
+        // mongo_flatten/mapping_file is read while loading the task config,
+        // so it should be absolute before TaskConfig::new
+        let ini = IniLoader::new(src_task_config_file).ini;
+        if let Some(mapping_file) = ini.get(MONGO_FLATTEN, "mapping_file") {
+            let mapping_file = format!("{}/{}", project_root, mapping_file);
+            update_configs.push((
+                MONGO_FLATTEN.to_string(),
+                "mapping_file".to_string(),
+                mapping_file,
+            ));
+            TestConfigUtil::update_task_config(
+                src_task_config_file,
+                dst_task_config_file,
+                &update_configs,
+            );
+            src_task_config_file = dst_task_config_file;
+        }
+
+        let config = TaskConfig::new(src_task_config_file).unwrap();
+
         // runtime/log4rs_file
         let log4rs_file = format!("{}/{}", project_root, config.runtime.log4rs_file);
         update_configs.push((RUNTIME.to_string(), "log4rs_file".to_string(), log4rs_file));
@@ -123,7 +144,8 @@ impl TestConfigUtil {
         match config.extractor {
             ExtractorConfig::MysqlCheck { check_log_dir, .. }
             | ExtractorConfig::PgCheck { check_log_dir, .. }
-            | ExtractorConfig::MongoCheck { check_log_dir, .. } => {
+            | ExtractorConfig::MongoCheck { check_log_dir, .. }
+            | ExtractorConfig::RedisCheck { check_log_dir, .. } => {
                 let extractor_check_log_dir = format!("{}/{}", project_root, check_log_dir);
                 update_configs.push((
                     EXTRACTOR.to_string(),
@@ -144,7 +166,8 @@ impl TestConfigUtil {
             // sinker/check_log_dir
             SinkerConfig::MysqlCheck { check_log_dir, .. }
             | SinkerConfig::PgCheck { check_log_dir, .. }
-            | SinkerConfig::MongoCheck { check_log_dir, .. } => {
+            | SinkerConfig::MongoCheck { check_log_dir, .. }
+            | SinkerConfig::RedisCheck { check_log_dir, .. } => {
                 let sinker_check_log_dir = if !check_log_dir.is_empty() {
                     format!("{}/{}", project_root, check_log_dir)
                 } else {
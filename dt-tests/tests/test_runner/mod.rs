@@ -1,6 +1,7 @@
 pub mod base_test_runner;
 pub mod check_util;
 pub mod mongo_check_test_runner;
+pub mod mongo_rdb_test_runner;
 pub mod mongo_test_runner;
 pub mod precheck_test_runner;
 pub mod rdb_check_test_runner;
@@ -14,6 +15,7 @@ pub mod rdb_starrocks_test_runner;
 pub mod rdb_struct_test_runner;
 pub mod rdb_test_runner;
 pub mod rdb_util;
+pub mod redis_check_test_runner;
 pub mod redis_cluster_connection;
 pub mod redis_cycle_test_runner;
 pub mod redis_statistic_runner;
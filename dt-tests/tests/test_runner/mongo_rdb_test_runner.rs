@@ -0,0 +1,73 @@
+use std::collections::HashMap;
+
+use dt_common::{
+    config::{sinker_config::SinkerConfig, task_config::TaskConfig},
+    utils::time_util::TimeUtil,
+};
+use dt_task::task_util::TaskUtil;
+use sqlx::{MySql, Pool, Row};
+
+use super::{mongo_test_runner::MongoTestRunner, rdb_util::RdbUtil};
+
+/// runner for tasks flattening mongo documents into mysql tables
+pub struct MongoRdbTestRunner {
+    mongo_runner: MongoTestRunner,
+    dst_conn_pool: Pool<MySql>,
+}
+
+#[allow(dead_code)]
+impl MongoRdbTestRunner {
+    pub async fn new(relative_test_dir: &str) -> anyhow::Result<Self> {
+        let mongo_runner = MongoTestRunner::new(relative_test_dir).await?;
+
+        let config = TaskConfig::new(&mongo_runner.base.task_config_file).unwrap();
+        let dst_url = match config.sinker {
+            SinkerConfig::Mysql { url, .. } => url,
+            _ => panic!("sinker of mongo_to_rdb tests should be mysql"),
+        };
+        let dst_conn_pool = TaskUtil::create_mysql_conn_pool(&dst_url, 1, false, true).await?;
+
+        Ok(Self {
+            mongo_runner,
+            dst_conn_pool,
+        })
+    }
+
+    pub async fn close(&self) -> anyhow::Result<()> {
+        self.dst_conn_pool.close().await;
+        Ok(())
+    }
+
+    pub async fn run_cdc_test(
+        &self,
+        start_millis: u64,
+        parse_millis: u64,
+        dst_expected_counts: &HashMap<(&str, &str), usize>,
+    ) -> anyhow::Result<()> {
+        RdbUtil::execute_sqls_mysql(
+            &self.dst_conn_pool,
+            &self.mongo_runner.base.dst_prepare_sqls,
+        )
+        .await?;
+        self.mongo_runner.execute_prepare_sqls().await?;
+
+        let task = self.mongo_runner.base.spawn_task().await?;
+        TimeUtil::sleep_millis(start_millis).await;
+
+        self.mongo_runner.execute_test_sqls().await?;
+        TimeUtil::sleep_millis(parse_millis).await;
+
+        for ((db, tb), count) in dst_expected_counts.iter() {
+            assert_eq!(self.count_dst_rows(db, tb).await?, *count, "{}.{}", db, tb);
+        }
+
+        self.mongo_runner.base.abort_task(&task).await
+    }
+
+    async fn count_dst_rows(&self, db: &str, tb: &str) -> anyhow::Result<usize> {
+        let sql = format!("SELECT COUNT(*) FROM `{}`.`{}`", db, tb);
+        let row = sqlx::query(&sql).fetch_one(&self.dst_conn_pool).await?;
+        let count: i64 = row.try_get(0)?;
+        Ok(count as usize)
+    }
+}
@@ -218,18 +218,19 @@ impl MongoTestRunner {
 
     pub async fn execute_prepare_sqls(&self) -> anyhow::Result<()> {
         let src_mongo_client = self.src_mongo_client.as_ref().unwrap();
-        let dst_mongo_client = self.dst_mongo_client.as_ref().unwrap();
-
         let src_sqls = Self::slice_sqls_by_db(&self.base.src_prepare_sqls);
-        let dst_sqls = Self::slice_sqls_by_db(&self.base.dst_prepare_sqls);
-
         for (db, sqls) in src_sqls.iter() {
             self.execute_ddls(src_mongo_client, db, sqls).await?;
             self.execute_dmls(src_mongo_client, db, sqls).await?;
         }
-        for (db, sqls) in dst_sqls.iter() {
-            self.execute_ddls(dst_mongo_client, db, sqls).await?;
-            self.execute_dmls(dst_mongo_client, db, sqls).await?;
+
+        // dst may not be mongo, e.g. mongo_to_mysql, whose prepare sqls are executed by the caller
+        if let Some(dst_mongo_client) = self.dst_mongo_client.as_ref() {
+            let dst_sqls = Self::slice_sqls_by_db(&self.base.dst_prepare_sqls);
+            for (db, sqls) in dst_sqls.iter() {
+                self.execute_ddls(dst_mongo_client, db, sqls).await?;
+                self.execute_dmls(dst_mongo_client, db, sqls).await?;
+            }
         }
         Ok(())
     }
@@ -50,6 +50,14 @@ impl RedisClusterConnection {
         &mut self.default_conn
     }
 
+    /// connections of all master nodes, or the default connection if not a cluster
+    pub fn get_all_conns(&mut self) -> Vec<&mut Connection> {
+        if self.node_conn_map.is_empty() {
+            return vec![&mut self.default_conn];
+        }
+        self.node_conn_map.values_mut().collect()
+    }
+
     pub fn get_node_conn_by_key(&mut self, key: &str) -> &mut Connection {
         if self.slot_node_map.is_empty() {
             return self.get_default_conn();
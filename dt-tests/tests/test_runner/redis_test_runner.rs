@@ -12,11 +12,11 @@ use dt_common::{
     utils::{redis_util::RedisUtil, sql_util::SqlUtil, time_util::TimeUtil},
 };
 
-use redis::{Connection, Value};
+use redis::Value;
 
 pub struct RedisTestRunner {
     pub base: BaseTestRunner,
-    src_conn: Connection,
+    src_conn: RedisClusterConnection,
     dst_conn: RedisClusterConnection,
     redis_util: RedisTestUtil,
     filter: RdbFilter,
@@ -35,10 +35,17 @@ impl RedisTestRunner {
 
         let config = TaskConfig::new(&base.task_config_file).unwrap();
         let src_conn = match config.extractor {
-            ExtractorConfig::RedisSnapshot { url, .. }
-            | ExtractorConfig::RedisCdc { url, .. }
-            | ExtractorConfig::RedisSnapshotAndCdc { url, .. } => {
-                RedisUtil::create_redis_conn(&url).await.unwrap()
+            ExtractorConfig::RedisSnapshot {
+                url, is_cluster, ..
+            }
+            | ExtractorConfig::RedisCdc {
+                url, is_cluster, ..
+            }
+            | ExtractorConfig::RedisSnapshotAndCdc {
+                url, is_cluster, ..
+            } => RedisClusterConnection::new(&url, is_cluster).await.unwrap(),
+            ExtractorConfig::RedisScan { url, .. } => {
+                RedisClusterConnection::new(&url, false).await.unwrap()
             }
             _ => {
                 bail! {Error::ConfigError("unsupported extractor config".into())};
@@ -48,6 +55,9 @@ impl RedisTestRunner {
         let dst_conn = match config.sinker {
             SinkerConfig::Redis {
                 url, is_cluster, ..
+            }
+            | SinkerConfig::RedisCheck {
+                url, is_cluster, ..
             } => RedisClusterConnection::new(&url, is_cluster).await.unwrap(),
             _ => {
                 bail! {Error::ConfigError("unsupported sinker config".into())};
@@ -114,26 +124,31 @@ impl RedisTestRunner {
         let key = &heartbeat_db_key[1];
 
         let cmd = format!("SELECT {}", db_id);
-        self.redis_util.execute_cmd(&mut self.src_conn, &cmd);
+        self.redis_util
+            .execute_cmd_in_cluster(&mut self.src_conn, &cmd);
 
         self.execute_prepare_sqls()?;
 
         let cmd = format!("GET {}", self.redis_util.escape_key(key));
-        let result = self.redis_util.execute_cmd(&mut self.src_conn, &cmd);
+        let result = self
+            .redis_util
+            .execute_cmd_in_one_cluster_node(&mut self.src_conn, &cmd);
         assert_eq!(result, Value::Nil);
 
         let task = self.base.spawn_task().await?;
         TimeUtil::sleep_millis(start_millis).await;
         self.base.abort_task(&task).await.unwrap();
 
-        let result = self.redis_util.execute_cmd(&mut self.src_conn, &cmd);
+        let result = self
+            .redis_util
+            .execute_cmd_in_one_cluster_node(&mut self.src_conn, &cmd);
         assert_ne!(result, Value::Nil);
         Ok(())
     }
 
     pub fn execute_prepare_sqls(&mut self) -> anyhow::Result<()> {
         self.redis_util
-            .execute_cmds(&mut self.src_conn, &self.base.src_prepare_sqls.clone());
+            .execute_cmds_in_cluster(&mut self.src_conn, &self.base.src_prepare_sqls.clone());
         self.redis_util
             .execute_cmds_in_cluster(&mut self.dst_conn, &self.base.dst_prepare_sqls.clone());
         Ok(())
@@ -141,16 +156,16 @@ impl RedisTestRunner {
 
     pub fn execute_test_sqls(&mut self) -> anyhow::Result<()> {
         self.redis_util
-            .execute_cmds(&mut self.src_conn, &self.base.src_test_sqls.clone());
+            .execute_cmds_in_cluster(&mut self.src_conn, &self.base.src_test_sqls.clone());
         Ok(())
     }
 
     pub fn compare_all_data(&mut self) -> anyhow::Result<()> {
-        let dbs = if self.dst_conn.is_cluster() {
+        let dbs = if self.src_conn.is_cluster() || self.dst_conn.is_cluster() {
             // a redis cluster strictly supports only database 0
             vec!["0".to_string()]
         } else {
-            self.redis_util.list_dbs(&mut self.src_conn)
+            self.redis_util.list_dbs(self.src_conn.get_default_conn())
         };
         for db in dbs.iter() {
             println!("compare data for db: {}", db);
@@ -161,7 +176,7 @@ impl RedisTestRunner {
 
     fn compare_data(&mut self, db: &str) -> anyhow::Result<()> {
         self.redis_util
-            .execute_cmd(&mut self.src_conn, &format!("SELECT {}", db));
+            .execute_cmd_in_cluster(&mut self.src_conn, &format!("SELECT {}", db));
         self.redis_util
             .execute_cmd_in_cluster(&mut self.dst_conn, &format!("SELECT {}", db));
 
@@ -193,7 +208,11 @@ impl RedisTestRunner {
         // time series
         let mut tsdb_keys = Vec::new();
 
-        let keys = self.redis_util.list_keys(&mut self.src_conn, "*");
+        let mut keys = Vec::new();
+        for conn in self.src_conn.get_all_conns() {
+            keys.extend(self.redis_util.list_keys(conn, "*"));
+        }
+        keys.sort();
         for i in keys.iter() {
             let key = i.clone();
 
@@ -201,7 +220,8 @@ impl RedisTestRunner {
                 continue;
             }
 
-            let key_type = self.redis_util.get_key_type(&mut self.src_conn, &key);
+            let src_node_conn = self.src_conn.get_node_conn_by_key(&key);
+            let key_type = self.redis_util.get_key_type(src_node_conn, &key);
             match key_type.to_lowercase().as_str() {
                 "string" => string_keys.push(key),
                 "hash" => hash_keys.push(key),
@@ -243,7 +263,9 @@ impl RedisTestRunner {
     fn check_expire(&mut self, keys: &Vec<String>) {
         for key in keys {
             let cmd = format!("PTTL {}", self.redis_util.escape_key(key));
-            let src_result = self.redis_util.execute_cmd(&mut self.src_conn, &cmd);
+            let src_result = self
+                .redis_util
+                .execute_cmd_in_one_cluster_node(&mut self.src_conn, &cmd);
             let dst_result = self
                 .redis_util
                 .execute_cmd_in_one_cluster_node(&mut self.dst_conn, &cmd);
@@ -281,7 +303,8 @@ impl RedisTestRunner {
 
     fn compare_hash_entries(&mut self, db: &str, keys: &Vec<String>) {
         for key in keys {
-            let src_kvs = self.redis_util.get_hash_entry(&mut self.src_conn, key);
+            let src_node_conn = self.src_conn.get_node_conn_by_key(key);
+            let src_kvs = self.redis_util.get_hash_entry(src_node_conn, key);
             let dst_node_conn = self.dst_conn.get_node_conn_by_key(key);
             let dst_kvs = self.redis_util.get_hash_entry(dst_node_conn, key);
             println!(
@@ -379,7 +402,9 @@ impl RedisTestRunner {
     }
 
     fn compare_cmd_results(&mut self, cmd: &str, db: &str, key: &str) {
-        let src_result = self.redis_util.execute_cmd(&mut self.src_conn, cmd);
+        let src_result = self
+            .redis_util
+            .execute_cmd_in_one_cluster_node(&mut self.src_conn, cmd);
         let dst_result = self
             .redis_util
             .execute_cmd_in_one_cluster_node(&mut self.dst_conn, cmd);
@@ -402,7 +427,7 @@ impl RedisTestRunner {
     fn print_version_info(&mut self) {
         println!(
             "src: {}",
-            RedisUtil::get_redis_version(&mut self.src_conn).unwrap()
+            RedisUtil::get_redis_version(self.src_conn.get_default_conn()).unwrap()
         );
         let dst_node_conn = self.dst_conn.get_default_conn();
         println!(
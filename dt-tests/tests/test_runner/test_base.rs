@@ -7,13 +7,14 @@ use futures::executor::block_on;
 use crate::test_runner::rdb_test_runner::DST;
 
 use super::{
-    mongo_check_test_runner::MongoCheckTestRunner, mongo_test_runner::MongoTestRunner,
-    precheck_test_runner::PrecheckTestRunner, rdb_check_test_runner::RdbCheckTestRunner,
-    rdb_kafka_rdb_test_runner::RdbKafkaRdbTestRunner, rdb_lua_test_runner::RdbLuaTestRunner,
-    rdb_redis_test_runner::RdbRedisTestRunner, rdb_sql_test_runner::RdbSqlTestRunner,
-    rdb_starrocks_test_runner::RdbStarRocksTestRunner, rdb_struct_test_runner::RdbStructTestRunner,
-    rdb_test_runner::RdbTestRunner, redis_statistic_runner::RedisStatisticTestRunner,
-    redis_test_runner::RedisTestRunner,
+    mongo_check_test_runner::MongoCheckTestRunner, mongo_rdb_test_runner::MongoRdbTestRunner,
+    mongo_test_runner::MongoTestRunner, precheck_test_runner::PrecheckTestRunner,
+    rdb_check_test_runner::RdbCheckTestRunner, rdb_kafka_rdb_test_runner::RdbKafkaRdbTestRunner,
+    rdb_lua_test_runner::RdbLuaTestRunner, rdb_redis_test_runner::RdbRedisTestRunner,
+    rdb_sql_test_runner::RdbSqlTestRunner, rdb_starrocks_test_runner::RdbStarRocksTestRunner,
+    rdb_struct_test_runner::RdbStructTestRunner, rdb_test_runner::RdbTestRunner,
+    redis_check_test_runner::RedisCheckTestRunner,
+    redis_statistic_runner::RedisStatisticTestRunner, redis_test_runner::RedisTestRunner,
 };
 
 pub struct TestBase {}
@@ -179,6 +180,20 @@ impl TestBase {
             .unwrap();
     }
 
+    pub async fn run_mongo_to_rdb_cdc_test_and_check_dst_count(
+        test_dir: &str,
+        start_millis: u64,
+        parse_millis: u64,
+        dst_expected_counts: HashMap<(&str, &str), usize>,
+    ) {
+        let runner = MongoRdbTestRunner::new(test_dir).await.unwrap();
+        runner
+            .run_cdc_test(start_millis, parse_millis, &dst_expected_counts)
+            .await
+            .unwrap();
+        runner.close().await.unwrap();
+    }
+
     pub async fn run_mongo_check_test(test_dir: &str) {
         let runner = MongoCheckTestRunner::new(test_dir).await.unwrap();
         runner.run_check_test().await.unwrap();
@@ -239,6 +254,11 @@ impl TestBase {
             .unwrap();
     }
 
+    pub async fn run_redis_check_test(test_dir: &str) {
+        let mut runner = RedisCheckTestRunner::new(test_dir).await.unwrap();
+        runner.run_check_test().await.unwrap();
+    }
+
     pub async fn run_redis_statistic_test(test_dir: &str) {
         let mut runner = RedisStatisticTestRunner::new(test_dir).await.unwrap();
         runner.run_statistic_test().await.unwrap();